@@ -1,12 +1,13 @@
 use super::lexer_types::{LexerToken, LexerTokenType};
+use crate::core::error::{Diagnostic, ErrorType};
 use regex::Regex;
 
-const KEYWORDS: [&str; 16] = [
+const KEYWORDS: [&str; 21] = [
     "fn", "let", "if", "else", "while", "true", "false", "import", "return", "break", "nothing",
-    "string", "number", "bool", "struct", "export",
+    "string", "number", "bool", "struct", "export", "continue", "impl", "try", "catch", "throw",
 ];
 
-pub fn lex(source: String) -> Vec<LexerToken> {
+pub fn lex(source: String) -> Result<Vec<LexerToken>, Vec<Diagnostic>> {
     let keywords = KEYWORDS.to_vec();
     let mut tokens: Vec<LexerToken> = Vec::new();
 
@@ -122,60 +123,30 @@ pub fn lex(source: String) -> Vec<LexerToken> {
                         }
                     }
                 }
-                '>' => {
-                    if let Some(next) = chars.peek() {
-                        match next {
-                            '=' => {
-                                // for '>='
-                                chars.next(); // Consume the '='
-                                current_token.push(c);
-                                current_token.push('=');
-                                tokens.push(token_with_type(
-                                    current_token.clone(),
-                                    line_counter,
-                                    line_char_counter,
-                                ));
-                                current_token.clear();
-                            }
-                            _ => {
-                                // for '>'
-                                current_token.push(c);
-                                tokens.push(token_with_type(
-                                    current_token.clone(),
-                                    line_counter,
-                                    line_char_counter,
-                                ));
-                                current_token.clear();
-                            }
+                '>' | '<' => {
+                    if is_string {
+                        current_token.push(c);
+                    } else {
+                        if !current_token.is_empty() {
+                            tokens.push(token_with_type(
+                                current_token.clone(),
+                                line_counter,
+                                line_char_counter,
+                            ));
+                            current_token.clear();
                         }
-                    }
-                }
-                '<' => {
-                    if let Some(next) = chars.peek() {
-                        match next {
-                            '=' => {
-                                // for '>='
-                                chars.next(); // Consume the '='
-                                current_token.push(c);
-                                current_token.push('=');
-                                tokens.push(token_with_type(
-                                    current_token.clone(),
-                                    line_counter,
-                                    line_char_counter,
-                                ));
-                                current_token.clear();
-                            }
-                            _ => {
-                                // for '>'
-                                current_token.push(c);
-                                tokens.push(token_with_type(
-                                    current_token.clone(),
-                                    line_counter,
-                                    line_char_counter,
-                                ));
-                                current_token.clear();
-                            }
+                        current_token.push(c);
+                        if let Some('=') = chars.peek() {
+                            // for '>=' and '<='
+                            chars.next(); // Consume the '='
+                            current_token.push('=');
                         }
+                        tokens.push(token_with_type(
+                            current_token.clone(),
+                            line_counter,
+                            line_char_counter,
+                        ));
+                        current_token.clear();
                     }
                 }
                 '!' => {
@@ -215,11 +186,23 @@ pub fn lex(source: String) -> Vec<LexerToken> {
                         )); // push previous token, - 1 since is the previous
                         current_token = String::new();
                     }
-                    tokens.push(token_with_type(
-                        c.to_string(),
-                        line_counter,
-                        line_char_counter,
-                    ));
+                    // for '&&' and '||'
+                    if (c == '&' || c == '|') && chars.peek() == Some(&c) {
+                        chars.next(); // Consume the second '&' or '|'
+                        tokens.push(token_with_type(
+                            format!("{c}{c}"),
+                            line_counter,
+                            line_char_counter,
+                        ));
+                        line_char_counter += 1;
+                        char_counter += 1;
+                    } else {
+                        tokens.push(token_with_type(
+                            c.to_string(),
+                            line_counter,
+                            line_char_counter,
+                        ));
+                    }
                     current_token = String::new();
                 }
                 // special characters
@@ -241,16 +224,21 @@ pub fn lex(source: String) -> Vec<LexerToken> {
                 }
                 // dot and float
                 '.' => {
-                    if current_token.chars().all(|char| char.is_numeric()) {
+                    // an empty token is a dot after ')' or ']': v[0].x
+                    if !current_token.is_empty()
+                        && current_token.chars().all(|char| char.is_numeric())
+                    {
                         current_token.push(c);
                         is_float = !is_float;
                     } else {
                         // prev dot
-                        tokens.push(token_with_type(
-                            current_token,
-                            line_counter,
-                            line_char_counter,
-                        ));
+                        if !current_token.is_empty() {
+                            tokens.push(token_with_type(
+                                current_token,
+                                line_counter,
+                                line_char_counter,
+                            ));
+                        }
                         // dot
                         tokens.push(token_with_type(
                             String::from(c),
@@ -326,7 +314,55 @@ pub fn lex(source: String) -> Vec<LexerToken> {
         char_counter += 1;
     }
 
-    return tokens;
+    resolve_columns(&source, &mut tokens);
+
+    // every token the language does not know is reported
+    let diagnostics: Vec<Diagnostic> = tokens
+        .iter()
+        .filter(|token| token.token_type == LexerTokenType::Unknown)
+        .map(unknown_token)
+        .collect();
+    if diagnostics.is_empty() {
+        Ok(tokens)
+    } else {
+        Err(diagnostics)
+    }
+}
+
+fn unknown_token(token: &LexerToken) -> Diagnostic {
+    let message = if token.value.starts_with('"') {
+        "String literal is not closed".to_string()
+    } else {
+        format!("Unknown token '{}'", token.value)
+    };
+    Diagnostic::new(ErrorType::SyntaxError, &message, token.line, token.at)
+}
+
+// the char counters above point to where each token got flushed,
+// which depends on the char that ended it. this fixes every token
+// column to the 1-based char where its value starts on its line
+fn resolve_columns(source: &str, tokens: &mut [LexerToken]) {
+    let lines: Vec<&str> = source.lines().collect();
+    let mut current_line = 0;
+    let mut cursor = 0; // byte offset on the current line
+
+    for token in tokens.iter_mut() {
+        if token.line != current_line {
+            current_line = token.line;
+            cursor = 0;
+        }
+        let Some(line) = lines.get(token.line.saturating_sub(1)) else {
+            continue;
+        };
+        // a string spanning lines is found by its first one, so
+        // an unclosed string points to its opening quote
+        let value = token.value.lines().next().unwrap_or_default();
+        if let Some(found) = line[cursor..].find(value) {
+            let start = cursor + found;
+            token.at = line[..start].chars().count() + 1;
+            cursor = start + value.len();
+        }
+    }
 }
 
 fn token_with_type(token: String, line: usize, at: usize) -> LexerToken {
@@ -343,6 +379,11 @@ fn token_with_type(token: String, line: usize, at: usize) -> LexerToken {
         "return" => LexerToken::new(LexerTokenType::ReturnKeyword, token, line, at),
         "export" => LexerToken::new(LexerTokenType::ExportKeyword, token, line, at),
         "break" => LexerToken::new(LexerTokenType::BreakKeyword, token, line, at),
+        "continue" => LexerToken::new(LexerTokenType::ContinueKeyword, token, line, at),
+        "impl" => LexerToken::new(LexerTokenType::ImplKeyword, token, line, at),
+        "try" => LexerToken::new(LexerTokenType::TryKeyword, token, line, at),
+        "catch" => LexerToken::new(LexerTokenType::CatchKeyword, token, line, at),
+        "throw" => LexerToken::new(LexerTokenType::ThrowKeyword, token, line, at),
         "nothing" => LexerToken::new(LexerTokenType::NothingKeyword, token, line, at),
         "string" => LexerToken::new(LexerTokenType::StringKeyword, token, line, at),
         "number" => LexerToken::new(LexerTokenType::NumberKeyword, token, line, at),
@@ -365,7 +406,9 @@ fn token_with_type(token: String, line: usize, at: usize) -> LexerToken {
         "-" => LexerToken::new(LexerTokenType::SubtractOperator, token, line, at),
         "*" => LexerToken::new(LexerTokenType::MultiplyOperator, token, line, at),
         "|" => LexerToken::new(LexerTokenType::OrOperator, token, line, at),
+        "||" => LexerToken::new(LexerTokenType::OrOperator, token, line, at),
         "&" => LexerToken::new(LexerTokenType::AmpersandOperator, token, line, at),
+        "&&" => LexerToken::new(LexerTokenType::AmpersandOperator, token, line, at),
         "/" => LexerToken::new(LexerTokenType::DivideOperator, token, line, at),
         ">" => LexerToken::new(LexerTokenType::GreaterThanOperator, token, line, at),
         ">=" => LexerToken::new(LexerTokenType::GreaterThanOrEqualOperator, token, line, at),
@@ -396,3 +439,24 @@ fn is_identifier(token: &String) -> bool {
         .is_some_and(|first_char| first_char.is_alphabetic() || first_char == '_')
         && chars.all(|char| char.is_alphanumeric() || char == '_')
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unclosed_string_points_to_its_opening_quote() {
+        let diagnostics =
+            lex("let s = \"abc\nprintln(s)\n".to_string()).expect_err("the string is never closed");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].message, "String literal is not closed");
+        assert_eq!((diagnostics[0].line, diagnostics[0].column), (1, 9));
+    }
+
+    #[test]
+    fn tokens_after_a_string_keep_their_columns() {
+        let tokens = lex("println(\"a b\", x)".to_string()).expect("the source lexes");
+        let columns: Vec<usize> = tokens.iter().map(|token| token.at).collect();
+        assert_eq!(columns, vec![1, 8, 9, 14, 16, 17]);
+    }
+}
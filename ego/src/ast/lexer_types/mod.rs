@@ -6,6 +6,7 @@ pub enum LexerTokenType {
     ImportKeyword,
     FnKeyword,
     StructKeyword,
+    ImplKeyword,
     WhileKeyword,
     IfKeyword,
     ElseKeyword,
@@ -14,6 +15,10 @@ pub enum LexerTokenType {
     ReturnKeyword,
     ExportKeyword,
     BreakKeyword,
+    ContinueKeyword,
+    TryKeyword,
+    CatchKeyword,
+    ThrowKeyword,
     NothingKeyword,
     StringKeyword,
     NumberKeyword,
@@ -55,6 +60,7 @@ impl fmt::Display for LexerTokenType {
             LexerTokenType::ImportKeyword => write!(f, "ImportKeyword"),
             LexerTokenType::FnKeyword => write!(f, "FnKeyword"),
             LexerTokenType::StructKeyword => write!(f, "StructKeyword"),
+            LexerTokenType::ImplKeyword => write!(f, "ImplKeyword"),
             LexerTokenType::WhileKeyword => write!(f, "WhileKeyword"),
             LexerTokenType::IfKeyword => write!(f, "IfKeyword"),
             LexerTokenType::ElseKeyword => write!(f, "ElseKeyword"),
@@ -63,6 +69,10 @@ impl fmt::Display for LexerTokenType {
             LexerTokenType::ReturnKeyword => write!(f, "ReturnKeyword"),
             LexerTokenType::ExportKeyword => write!(f, "ExportKeyword"),
             LexerTokenType::BreakKeyword => write!(f, "BreakKeyword"),
+            LexerTokenType::ContinueKeyword => write!(f, "ContinueKeyword"),
+            LexerTokenType::TryKeyword => write!(f, "TryKeyword"),
+            LexerTokenType::CatchKeyword => write!(f, "CatchKeyword"),
+            LexerTokenType::ThrowKeyword => write!(f, "ThrowKeyword"),
             LexerTokenType::NothingKeyword => write!(f, "NothingKeyword"),
             LexerTokenType::StringKeyword => write!(f, "StringKeyword"),
             LexerTokenType::NumberKeyword => write!(f, "NumberKeyword"),
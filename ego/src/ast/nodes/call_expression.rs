@@ -26,13 +26,13 @@ impl CallExpression {
         }
     }
 
-    pub fn get_callee(&self) -> String {
+    // name of the called function, None when the callee is
+    // another expression, like fns[0]() or make()()
+    pub fn get_callee(&self) -> Option<String> {
         match self.callee.as_ref() {
-            Expression::Identifier(i) => i.name.clone(),
-            Expression::MemberExpression(x) => x.property.name.clone(),
-            _ => {
-                panic!("Unhandled callee for CallExpression")
-            }
+            Expression::Identifier(i) => Some(i.name.clone()),
+            Expression::MemberExpression(x) => Some(x.property.name.clone()),
+            _ => None,
         }
     }
 }
@@ -0,0 +1,11 @@
+#[derive(Debug, Clone)]
+pub struct ContinueStatement {
+    pub at: usize,
+    pub line: usize,
+}
+
+impl ContinueStatement {
+    pub fn new(at: usize, line: usize) -> ContinueStatement {
+        ContinueStatement { at, line }
+    }
+}
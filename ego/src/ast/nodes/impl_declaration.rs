@@ -0,0 +1,23 @@
+use super::{function_declaration::FunctionDeclaration, identifier::Identifier};
+
+#[derive(Debug, Clone)]
+pub struct ImplDeclaration {
+    pub identifier: Identifier,
+    pub methods: Vec<FunctionDeclaration>,
+    pub at: usize,
+    pub line: usize,
+}
+
+impl ImplDeclaration {
+    pub fn new(identifier: Identifier, at: usize, line: usize) -> ImplDeclaration {
+        ImplDeclaration {
+            identifier,
+            methods: vec![],
+            at,
+            line,
+        }
+    }
+    pub fn add_method(&mut self, method: FunctionDeclaration) {
+        self.methods.push(method);
+    }
+}
@@ -0,0 +1,25 @@
+use super::{index_expression::IndexExpression, Expression};
+
+#[derive(Debug, Clone)]
+pub struct IndexAssignamentNode {
+    pub target: IndexExpression,
+    pub init: Expression,
+    pub at: usize,
+    pub line: usize,
+}
+
+impl IndexAssignamentNode {
+    pub fn new(
+        target: IndexExpression,
+        init: Expression,
+        at: usize,
+        line: usize,
+    ) -> IndexAssignamentNode {
+        IndexAssignamentNode {
+            target,
+            init,
+            at,
+            line,
+        }
+    }
+}
@@ -0,0 +1,21 @@
+use super::{string_literal::StringLiteral, Expression};
+
+#[derive(Debug, Clone)]
+pub struct MapLiteral {
+    pub entries: Vec<(StringLiteral, Expression)>,
+    pub at: usize,
+    pub line: usize,
+}
+
+impl MapLiteral {
+    pub fn new(at: usize, line: usize) -> MapLiteral {
+        MapLiteral {
+            entries: vec![],
+            at,
+            line,
+        }
+    }
+    pub fn add_entry(&mut self, key: StringLiteral, value: Expression) {
+        self.entries.push((key, value));
+    }
+}
@@ -4,21 +4,30 @@ pub mod block;
 pub mod bool;
 pub mod break_statement;
 pub mod call_expression;
+pub mod continue_statement;
 pub mod else_statement;
 pub mod export_statement;
 pub mod function_declaration;
 pub mod group;
 pub mod identifier;
 pub mod if_statement;
+pub mod impl_declaration;
 pub mod import_statement;
+pub mod index_assignament_statement;
+pub mod index_expression;
+pub mod map_literal;
 pub mod member_expression;
 pub mod module;
 pub mod nothing;
 pub mod number;
 pub mod objects;
+pub mod property_assignament_statement;
 pub mod return_statement;
 pub mod string_literal;
 pub mod structs;
+pub mod throw_statement;
+pub mod try_statement;
+pub mod unary_expression;
 pub mod vector;
 pub mod while_statement;
 use std::fmt;
@@ -33,10 +42,14 @@ use crate::ast::{
 use self::{
     assignament_statement::AssignamentNode, binary_expression::BinaryExpression, block::Block,
     bool::Bool, break_statement::BreakStatement, call_expression::CallExpression,
-    else_statement::ElseStatement, function_declaration::FunctionDeclaration, group::Group,
-    identifier::Identifier, if_statement::IfStatement, import_statement::ImportStatement,
-    nothing::Nothing, number::Number, return_statement::ReturnStatement,
-    string_literal::StringLiteral, vector::Vector, while_statement::WhileStatement,
+    continue_statement::ContinueStatement, else_statement::ElseStatement,
+    function_declaration::FunctionDeclaration, group::Group, identifier::Identifier,
+    if_statement::IfStatement, impl_declaration::ImplDeclaration,
+    import_statement::ImportStatement, index_assignament_statement::IndexAssignamentNode,
+    index_expression::IndexExpression, map_literal::MapLiteral, nothing::Nothing, number::Number,
+    property_assignament_statement::PropertyAssignamentNode, return_statement::ReturnStatement,
+    string_literal::StringLiteral, throw_statement::ThrowStatement, try_statement::TryStatement,
+    unary_expression::UnaryExpression, vector::Vector, while_statement::WhileStatement,
 };
 
 #[derive(Debug, Clone)]
@@ -47,13 +60,19 @@ pub enum AstNodeType {
     ReturnStatement(ReturnStatement),
     ExportStatement(ExportStatement),
     BreakStatement(BreakStatement),
+    ContinueStatement(ContinueStatement),
+    TryStatement(TryStatement),
+    ThrowStatement(ThrowStatement),
     ElseStatement(ElseStatement),
     Struct(Struct),
+    ImplDeclaration(ImplDeclaration),
     ObjectType(ObjectType),
     Group(Group),
     Block(Block),
     Expression(Expression),
     AssignamentStatement(AssignamentNode),
+    IndexAssignamentStatement(IndexAssignamentNode),
+    PropertyAssignamentStatement(PropertyAssignamentNode),
     FunctionDeclaration(FunctionDeclaration),
 }
 
@@ -66,13 +85,19 @@ impl AstNodeType {
             AstNodeType::ReturnStatement(v) => v.at,
             AstNodeType::ExportStatement(v) => v.at,
             AstNodeType::BreakStatement(v) => v.at,
+            AstNodeType::ContinueStatement(v) => v.at,
+            AstNodeType::TryStatement(v) => v.at,
+            AstNodeType::ThrowStatement(v) => v.at,
             AstNodeType::ElseStatement(v) => v.at,
             AstNodeType::Group(v) => v.at,
             AstNodeType::Block(_v) => 0,
-            AstNodeType::Expression(_v) => 0,
+            AstNodeType::Expression(v) => v.at(),
             AstNodeType::AssignamentStatement(v) => v.at,
+            AstNodeType::IndexAssignamentStatement(v) => v.at,
+            AstNodeType::PropertyAssignamentStatement(v) => v.at,
             AstNodeType::FunctionDeclaration(v) => v.at,
             AstNodeType::Struct(v) => v.at,
+            AstNodeType::ImplDeclaration(v) => v.at,
             AstNodeType::ObjectType(v) => v.at,
         }
     }
@@ -85,13 +110,19 @@ impl AstNodeType {
             AstNodeType::ReturnStatement(v) => v.line,
             AstNodeType::ExportStatement(v) => v.line,
             AstNodeType::BreakStatement(v) => v.line,
+            AstNodeType::ContinueStatement(v) => v.line,
+            AstNodeType::TryStatement(v) => v.line,
+            AstNodeType::ThrowStatement(v) => v.line,
             AstNodeType::ElseStatement(v) => v.line,
             AstNodeType::Group(v) => v.line,
             AstNodeType::Block(_v) => 0,
-            AstNodeType::Expression(_v) => 0,
+            AstNodeType::Expression(v) => v.line(),
             AstNodeType::AssignamentStatement(v) => v.line,
+            AstNodeType::IndexAssignamentStatement(v) => v.line,
+            AstNodeType::PropertyAssignamentStatement(v) => v.line,
             AstNodeType::FunctionDeclaration(v) => v.line,
             AstNodeType::Struct(v) => v.line,
+            AstNodeType::ImplDeclaration(v) => v.line,
             AstNodeType::ObjectType(v) => v.line,
         }
     }
@@ -107,20 +138,31 @@ impl fmt::Display for AstNodeType {
             AstNodeType::ReturnStatement(_) => write!(f, "ReturnStatement"),
             AstNodeType::ExportStatement(_) => write!(f, "ExportStatement"),
             AstNodeType::BreakStatement(_) => write!(f, "BreakStatement"),
+            AstNodeType::ContinueStatement(_) => write!(f, "ContinueStatement"),
+            AstNodeType::TryStatement(_) => write!(f, "TryStatement"),
+            AstNodeType::ThrowStatement(_) => write!(f, "ThrowStatement"),
             AstNodeType::Block(_) => write!(f, "Block"),
             AstNodeType::Group(_) => write!(f, "Group"),
             AstNodeType::FunctionDeclaration(_) => write!(f, "FunctionDeclaration"),
             AstNodeType::AssignamentStatement(_) => write!(f, "AssignamentStatement"),
+            AstNodeType::IndexAssignamentStatement(_) => {
+                write!(f, "IndexAssignamentStatement")
+            }
+            AstNodeType::PropertyAssignamentStatement(_) => {
+                write!(f, "PropertyAssignamentStatement")
+            }
             AstNodeType::Expression(Expression::StringLiteral(_)) => write!(f, "StringLiteral"),
             AstNodeType::Expression(Expression::Number(_)) => write!(f, "Number"),
             AstNodeType::Expression(Expression::Bool(_)) => write!(f, "Number"),
             AstNodeType::Expression(Expression::Identifier(_)) => write!(f, "Identifier"),
             AstNodeType::Expression(Expression::Vector(_)) => write!(f, "Vector"),
+            AstNodeType::Expression(Expression::MapLiteral(_)) => write!(f, "MapLiteral"),
             AstNodeType::Expression(Expression::Nothing(_)) => write!(f, "Nothing"),
             AstNodeType::Expression(Expression::CallExpression(node)) => {
                 write!(f, "CallExpression: {:#?}", node)
             }
             AstNodeType::Struct(_) => write!(f, "Struct"),
+            AstNodeType::ImplDeclaration(_) => write!(f, "ImplDeclaration"),
             AstNodeType::ObjectType(_) => write!(f, "ObjectType"),
             AstNodeType::Expression(Expression::StructLiteral(_)) => write!(f, "StructLiteral"),
             AstNodeType::Expression(Expression::ObjectLiteral(_)) => write!(f, "ObjectLiteral"),
@@ -130,6 +172,12 @@ impl fmt::Display for AstNodeType {
             AstNodeType::Expression(Expression::BinaryExpression(_)) => {
                 write!(f, "BinaryExpression")
             }
+            AstNodeType::Expression(Expression::UnaryExpression(_)) => {
+                write!(f, "UnaryExpression")
+            }
+            AstNodeType::Expression(Expression::IndexExpression(_)) => {
+                write!(f, "IndexExpression")
+            }
         }
     }
 }
@@ -148,14 +196,57 @@ pub enum Expression {
     Bool(Bool),
     Identifier(Identifier),
     BinaryExpression(BinaryExpression),
+    UnaryExpression(UnaryExpression),
     CallExpression(CallExpression),
     StructLiteral(StructLiteral),
     ObjectLiteral(ObjectLiteral),
     MemberExpression(MemberExpression),
+    IndexExpression(IndexExpression),
     Vector(Vector),
+    MapLiteral(MapLiteral),
     Nothing(Nothing),
 }
 
+impl Expression {
+    pub fn at(&self) -> usize {
+        match self {
+            Expression::StringLiteral(v) => v.at,
+            Expression::Number(v) => v.at,
+            Expression::Bool(v) => v.at,
+            Expression::Identifier(v) => v.at,
+            Expression::BinaryExpression(v) => v.at,
+            Expression::UnaryExpression(v) => v.at,
+            Expression::CallExpression(v) => v.at,
+            Expression::StructLiteral(v) => v.at,
+            Expression::ObjectLiteral(v) => v.at,
+            Expression::MemberExpression(v) => v.at,
+            Expression::IndexExpression(v) => v.at,
+            Expression::Vector(v) => v.at,
+            Expression::MapLiteral(v) => v.at,
+            Expression::Nothing(v) => v.at,
+        }
+    }
+
+    pub fn line(&self) -> usize {
+        match self {
+            Expression::StringLiteral(v) => v.line,
+            Expression::Number(v) => v.line,
+            Expression::Bool(v) => v.line,
+            Expression::Identifier(v) => v.line,
+            Expression::BinaryExpression(v) => v.line,
+            Expression::UnaryExpression(v) => v.line,
+            Expression::CallExpression(v) => v.line,
+            Expression::StructLiteral(v) => v.line,
+            Expression::ObjectLiteral(v) => v.line,
+            Expression::MemberExpression(v) => v.line,
+            Expression::IndexExpression(v) => v.line,
+            Expression::Vector(v) => v.line,
+            Expression::MapLiteral(v) => v.line,
+            Expression::Nothing(v) => v.line,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum Type {
     String,
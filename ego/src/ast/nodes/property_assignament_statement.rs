@@ -0,0 +1,25 @@
+use super::{member_expression::MemberExpression, Expression};
+
+#[derive(Debug, Clone)]
+pub struct PropertyAssignamentNode {
+    pub target: MemberExpression,
+    pub init: Expression,
+    pub at: usize,
+    pub line: usize,
+}
+
+impl PropertyAssignamentNode {
+    pub fn new(
+        target: MemberExpression,
+        init: Expression,
+        at: usize,
+        line: usize,
+    ) -> PropertyAssignamentNode {
+        PropertyAssignamentNode {
+            target,
+            init,
+            at,
+            line,
+        }
+    }
+}
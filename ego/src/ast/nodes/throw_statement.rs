@@ -0,0 +1,14 @@
+use super::Expression;
+
+#[derive(Debug, Clone)]
+pub struct ThrowStatement {
+    pub value: Expression,
+    pub at: usize,
+    pub line: usize,
+}
+
+impl ThrowStatement {
+    pub fn new(value: Expression, at: usize, line: usize) -> ThrowStatement {
+        ThrowStatement { value, at, line }
+    }
+}
@@ -0,0 +1,29 @@
+use super::{block::Block, identifier::Identifier};
+
+#[derive(Debug, Clone)]
+pub struct TryStatement {
+    pub body: Block,
+    // name the caught error is bound to
+    pub error_identifier: Identifier,
+    pub catch_body: Block,
+    pub at: usize,
+    pub line: usize,
+}
+
+impl TryStatement {
+    pub fn new(
+        body: Block,
+        error_identifier: Identifier,
+        catch_body: Block,
+        at: usize,
+        line: usize,
+    ) -> TryStatement {
+        TryStatement {
+            body,
+            error_identifier,
+            catch_body,
+            at,
+            line,
+        }
+    }
+}
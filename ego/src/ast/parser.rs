@@ -1,4 +1,4 @@
-use std::{cell::Cell, process::exit};
+use std::cell::{Cell, RefCell};
 
 use crate::{
     ast::{
@@ -19,20 +19,26 @@ use crate::{
         structs::{Struct, StructLiteral, StructTypeExpr},
         AstNodeType, Expression, LexerToken, LexerTokenType,
     },
-    core::error::{self, ErrorType},
+    core::error::{Diagnostic, ErrorType},
 };
 
 use super::{
     binary_expression::BinaryExpression, break_statement::BreakStatement,
-    else_statement::ElseStatement, if_statement::IfStatement, import_statement::ImportStatement,
-    nothing::Nothing, return_statement::ReturnStatement, vector::Vector,
-    while_statement::WhileStatement, Type,
+    continue_statement::ContinueStatement, else_statement::ElseStatement,
+    if_statement::IfStatement, impl_declaration::ImplDeclaration,
+    import_statement::ImportStatement, index_assignament_statement::IndexAssignamentNode,
+    index_expression::IndexExpression, map_literal::MapLiteral, nothing::Nothing,
+    property_assignament_statement::PropertyAssignamentNode, return_statement::ReturnStatement,
+    throw_statement::ThrowStatement, try_statement::TryStatement,
+    unary_expression::UnaryExpression, vector::Vector, while_statement::WhileStatement, Type,
 };
 
 pub struct Module {
     module_name: String,
     tokens: Vec<LexerToken>,
     current: Cell<usize>,
+    // problems of the statements skipped while parsing
+    diagnostics: RefCell<Vec<Diagnostic>>,
 }
 
 impl Module {
@@ -41,58 +47,52 @@ impl Module {
             module_name,
             tokens,
             current: 0.into(),
+            diagnostics: RefCell::new(vec![]),
         }
     }
 
-    pub fn parse(&mut self) -> ModuleAst {
+    // a statement with a problem is skipped, so every problem
+    // of the module is reported at once
+    pub fn parse(&mut self) -> Result<ModuleAst, Vec<Diagnostic>> {
         let module = ModuleAst::new(&self.module_name);
-        self.tree(module)
+        let module = self.tree(module);
+        let diagnostics = self.diagnostics.take();
+        if diagnostics.is_empty() {
+            Ok(module)
+        } else {
+            Err(diagnostics)
+        }
     }
 
     fn tree(&mut self, mut module_ast: ModuleAst) -> ModuleAst {
-        while self.is_peekable() {
-            let token = self.unsafe_peek();
-
-            match token.token_type {
-                LexerTokenType::LetKeyword => {
-                    let assignment_node = self.assignment_statement();
-                    module_ast.add_child(assignment_node);
-                }
-                LexerTokenType::FnKeyword => {
-                    let function_node = self.function_declaration();
-                    module_ast.add_child(function_node);
-                }
-                LexerTokenType::StructKeyword => {
-                    let function_node = self.struct_declaration();
-                    module_ast.add_child(function_node);
-                }
-                LexerTokenType::Identifier => {
-                    let identifier_node = self.identifier();
-                    module_ast.add_child(identifier_node);
-                }
-                LexerTokenType::OpenCurlyBrace => {
-                    let block_node = self.block();
-                    module_ast.add_child(block_node);
-                }
-                LexerTokenType::IfKeyword => {
-                    let if_node = self.if_statement();
-                    module_ast.add_child(if_node);
-                }
-                LexerTokenType::WhileKeyword => {
-                    let while_node = self.while_statement();
-                    module_ast.add_child(while_node);
-                }
-                LexerTokenType::ImportKeyword => {
-                    let import_node = self.import_statement();
-                    module_ast.add_child(import_node);
-                }
-                LexerTokenType::ExportKeyword => {
-                    let import_node = self.export_statement();
-                    module_ast.add_child(import_node);
-                }
+        while let Ok(token) = self.unsafe_peek() {
+            let start = self.current();
+
+            let node = match token.token_type {
+                LexerTokenType::LetKeyword => self.assignment_statement(),
+                LexerTokenType::FnKeyword => self.function_declaration(),
+                LexerTokenType::StructKeyword => self.struct_declaration(),
+                LexerTokenType::ImplKeyword => self.impl_declaration(),
+                LexerTokenType::Identifier => self.identifier(),
+                LexerTokenType::OpenCurlyBrace => self.block(),
+                LexerTokenType::IfKeyword => self.if_statement(),
+                LexerTokenType::WhileKeyword => self.while_statement(),
+                LexerTokenType::ImportKeyword => self.import_statement(),
+                LexerTokenType::ExportKeyword => self.export_statement(),
+                // not valid outside a loop, but parsed to let
+                // the compiler report it
+                LexerTokenType::BreakKeyword => self.break_statement(),
+                LexerTokenType::ContinueKeyword => self.continue_statement(),
+                LexerTokenType::TryKeyword => self.try_statement(),
+                LexerTokenType::ThrowKeyword => self.throw_statement(),
                 _ => {
                     self.next();
+                    continue;
                 }
+            };
+            match node {
+                Ok(node) => module_ast.add_child(node),
+                Err(diagnostic) => self.recover(diagnostic, start),
             }
         }
 
@@ -100,21 +100,35 @@ impl Module {
     }
 
     // Index handlers:
-    fn peek(&self, token: &str) -> &LexerToken {
+    fn peek(&self, token: &str) -> Result<&LexerToken, Diagnostic> {
         if self.is_peekable() {
-            &self.tokens[self.current.get()]
+            Ok(&self.tokens[self.current.get()])
         } else {
-            error::throw(
+            let (line, column) = self.position();
+            Err(Diagnostic::new(
                 ErrorType::ParsingError,
                 format!("Expected '{token}' but got and early end of module").as_str(),
-                None,
-            );
-            std::process::exit(1);
+                line,
+                column,
+            ))
         }
     }
 
-    fn unsafe_peek(&self) -> &LexerToken {
-        &self.tokens[self.current.get()]
+    // the current token, for the places where one is always
+    // expected. a module ending there reports the early end
+    fn unsafe_peek(&self) -> Result<&LexerToken, Diagnostic> {
+        match self.tokens.get(self.current.get()) {
+            Some(token) => Ok(token),
+            None => {
+                let (line, column) = self.position();
+                Err(Diagnostic::new(
+                    ErrorType::ParsingError,
+                    "Unexpected end of input",
+                    line,
+                    column,
+                ))
+            }
+        }
     }
 
     fn peek_next(&self) -> Option<&LexerToken> {
@@ -133,15 +147,11 @@ impl Module {
         }
     }
 
+    // moving past the end of the module is a no-op, so the
+    // peek after it reports the early end
     fn next(&self) {
-        if self.tokens.len() >= (self.current.get() + 1) {
+        if self.is_peekable() {
             self.current.set(self.current.get() + 1);
-        } else {
-            error::throw(
-                ErrorType::ParsingError,
-                "Peeked an out of bounds token",
-                Some(self.unsafe_peek().line),
-            )
         }
     }
 
@@ -149,119 +159,151 @@ impl Module {
         self.current.get()
     }
 
+    // panic mode recovery: the problem is saved and the tokens
+    // are skipped until the start of the next statement, which
+    // is after a ';', before a '}' or a statement keyword, or
+    // on a later line than the problem
+    fn recover(&self, diagnostic: Diagnostic, start: usize) {
+        let line = diagnostic.line;
+        self.diagnostics.borrow_mut().push(diagnostic);
+
+        // the statement is skipped even if it failed on its
+        // first token
+        if self.current() == start {
+            self.next();
+        }
+        while let Ok(token) = self.unsafe_peek() {
+            match token.token_type {
+                LexerTokenType::EndOfStatement => {
+                    self.next();
+                    return;
+                }
+                LexerTokenType::CloseCurlyBrace
+                | LexerTokenType::LetKeyword
+                | LexerTokenType::FnKeyword
+                | LexerTokenType::StructKeyword
+                | LexerTokenType::ImplKeyword
+                | LexerTokenType::IfKeyword
+                | LexerTokenType::WhileKeyword
+                | LexerTokenType::ImportKeyword
+                | LexerTokenType::ExportKeyword
+                | LexerTokenType::ReturnKeyword
+                | LexerTokenType::BreakKeyword
+                | LexerTokenType::ContinueKeyword
+                | LexerTokenType::TryKeyword
+                | LexerTokenType::ThrowKeyword => return,
+                _ if token.line > line => return,
+                _ => self.next(),
+            }
+        }
+    }
+
+    // line and column of the current token, or of the last
+    // one once the module has been consumed
+    fn position(&self) -> (usize, usize) {
+        match self.tokens.get(self.current()).or(self.tokens.last()) {
+            Some(token) => (token.line, token.at),
+            None => (1, 1),
+        }
+    }
+
     // {}
-    fn block(&self) -> AstNodeType {
+    fn block(&self) -> Result<AstNodeType, Diagnostic> {
         let mut block_node = Block::new();
 
         // check '{'
-        let token = self.unsafe_peek();
+        let token = self.unsafe_peek()?;
         if token.token_type == LexerTokenType::OpenCurlyBrace {
             self.next();
         } else {
-            error::throw(
+            return Err(Diagnostic::new(
                 ErrorType::SyntaxError,
                 format!("Unexpected token '{}' in block openning", token.value).as_str(),
-                Some(token.line),
-            )
+                token.line,
+                token.at,
+            ));
         }
 
         // get inside block ast nodes & check '}'
         let mut closed = false;
 
-        while self.is_peekable() {
-            let token = self.unsafe_peek();
+        while let Ok(token) = self.unsafe_peek() {
+            let start = self.current();
 
             // offset & current are incremented inside each type
             // to avoid "tokens[overflowed_index]"" if loops ends
             // before a '{'
-            match token.token_type {
+            let node = match token.token_type {
                 LexerTokenType::CloseCurlyBrace => {
                     // consume '}'
                     self.next();
                     closed = true;
                     break; // break block loop since it reaches the end
                 }
-                LexerTokenType::LetKeyword => {
-                    let assignment_node = self.assignment_statement();
-                    block_node.add_child(assignment_node);
-                }
-                LexerTokenType::FnKeyword => {
-                    let function_node = self.function_declaration();
-                    block_node.add_child(function_node);
-                }
-                LexerTokenType::Identifier => {
-                    let identifier_node = self.identifier();
-                    block_node.add_child(identifier_node);
-                }
-                LexerTokenType::OpenCurlyBrace => {
-                    let inner_block_node = self.block();
-                    block_node.add_child(inner_block_node);
-                }
-                LexerTokenType::IfKeyword => {
-                    let if_node = self.if_statement();
-                    block_node.add_child(if_node);
-                }
-                LexerTokenType::WhileKeyword => {
-                    let while_node = self.while_statement();
-                    block_node.add_child(while_node);
-                }
-                LexerTokenType::ReturnKeyword => {
-                    let return_node = self.return_statement();
-                    block_node.add_child(return_node);
-                }
-                LexerTokenType::BreakKeyword => {
-                    let token = self.unsafe_peek();
-                    self.next(); // consume 'break'
-                    block_node.add_child(AstNodeType::BreakStatement(BreakStatement::new(
-                        token.at, token.line,
-                    )))
-                }
-                _ => {
-                    error::throw(
-                        ErrorType::SyntaxError,
-                        format!(
-                            "Unexpected token '{}' inside block {{..}}", // generic error for unexpected codes for block parsing
-                            token.value,
-                        )
-                        .as_str(),
-                        Some(token.line),
-                    );
-                }
+                LexerTokenType::LetKeyword => self.assignment_statement(),
+                LexerTokenType::FnKeyword => self.function_declaration(),
+                LexerTokenType::Identifier => self.identifier(),
+                LexerTokenType::OpenCurlyBrace => self.block(),
+                LexerTokenType::IfKeyword => self.if_statement(),
+                LexerTokenType::WhileKeyword => self.while_statement(),
+                LexerTokenType::ReturnKeyword => self.return_statement(),
+                LexerTokenType::BreakKeyword => self.break_statement(),
+                LexerTokenType::ContinueKeyword => self.continue_statement(),
+                LexerTokenType::TryKeyword => self.try_statement(),
+                LexerTokenType::ThrowKeyword => self.throw_statement(),
+                _ => Err(Diagnostic::new(
+                    ErrorType::SyntaxError,
+                    format!(
+                        "Unexpected token '{}' inside block {{..}}", // generic error for unexpected codes for block parsing
+                        token.value,
+                    )
+                    .as_str(),
+                    token.line,
+                    token.at,
+                )),
+            };
+            match node {
+                Ok(node) => block_node.add_child(node),
+                Err(diagnostic) => self.recover(diagnostic, start),
             }
         }
 
         // non closed Block
         if !closed {
-            error::throw(
+            let (line, column) = self.position();
+            return Err(Diagnostic::new(
                 ErrorType::SyntaxError,
                 "Expected '}' for block close",
-                Some(token.line),
-            );
+                line,
+                column,
+            )
+            .with_label(token.line, token.at, "'{' opened here"));
         }
 
-        AstNodeType::Block(block_node)
+        Ok(AstNodeType::Block(block_node))
     }
 
     // (a, b, c)
-    fn group(&self, context: Option<&str>) -> Group {
+    fn group(&self, context: Option<&str>) -> Result<Group, Diagnostic> {
         // where am i
         let context_msg = match context {
             Some(str) => format!(" in {}", str),
             _ => "".to_string(),
         };
 
-        let group_token = self.unsafe_peek();
+        let group_token = self.unsafe_peek()?;
         let mut group_node = Group::new(group_token.at, group_token.line);
 
         // check '('
         if group_token.token_type == LexerTokenType::OpenParenthesis {
             self.next()
         } else {
-            error::throw(
+            return Err(Diagnostic::new(
                 ErrorType::SyntaxError,
                 format!("Unexpected token '{}'{}", group_token.value, context_msg).as_str(),
-                Some(group_token.line),
-            )
+                group_token.line,
+                group_token.at,
+            ));
         }
 
         // get arguments & check ')'
@@ -269,7 +311,7 @@ impl Module {
         let mut closed = false;
 
         while self.is_peekable() {
-            let token = self.unsafe_peek();
+            let token = self.unsafe_peek()?;
 
             // offset & current are incremented inside each type
             // to avoid "tokens[overflowed_index]"" if loops ends
@@ -292,7 +334,7 @@ impl Module {
                     break;
                 }
                 _ => {
-                    let node = self.parse_comparison();
+                    let node = self.parse_logical_or()?;
                     match node {
                         Expression::Identifier(_) => last_token = Some(LexerTokenType::Identifier),
                         Expression::Bool(_) => last_token = Some(LexerTokenType::TrueKeyword),
@@ -307,12 +349,18 @@ impl Module {
                         Expression::BinaryExpression(_) => {
                             last_token = Some(LexerTokenType::Number)
                         }
+                        Expression::UnaryExpression(_) => {
+                            last_token = Some(LexerTokenType::TrueKeyword)
+                        }
                         Expression::StructLiteral(_) => {
                             last_token = Some(LexerTokenType::Identifier)
                         }
                         Expression::Vector(_) => {
                             last_token = Some(LexerTokenType::OpenSquareBracket)
                         }
+                        Expression::MapLiteral(_) => {
+                            last_token = Some(LexerTokenType::OpenCurlyBrace)
+                        }
                         Expression::ObjectLiteral(_) => {
                             // use identifier as a fallback
                             last_token = Some(LexerTokenType::Identifier)
@@ -321,6 +369,10 @@ impl Module {
                             // use identifier as a fallback
                             last_token = Some(LexerTokenType::Identifier)
                         }
+                        Expression::IndexExpression(_) => {
+                            // use identifier as a fallback
+                            last_token = Some(LexerTokenType::Identifier)
+                        }
                     }
                     group_node.add_child(Some(node));
                 }
@@ -329,38 +381,42 @@ impl Module {
 
         // non closed CallExpression
         if !closed {
-            error::throw(
+            let (line, column) = self.position();
+            return Err(Diagnostic::new(
                 ErrorType::SyntaxError,
                 format!("Expected ')' {}", context_msg).as_str(),
-                Some(group_node.line),
+                line,
+                column,
             )
+            .with_label(group_node.line, group_node.at, "'(' opened here"));
         };
 
         // consume ')'
         self.next();
-        group_node
+        Ok(group_node)
     }
 
     // [a, b, x]
-    fn vector(&self, context: Option<&str>) -> Expression {
+    fn vector(&self, context: Option<&str>) -> Result<Expression, Diagnostic> {
         // where am i
         let context_msg = match context {
             Some(str) => format!(" in {}", str),
             _ => "".to_string(),
         };
 
-        let group_token = self.unsafe_peek();
+        let group_token = self.unsafe_peek()?;
         let mut vector_node = Vector::new(group_token.at, group_token.line);
 
         // check '['
         if group_token.token_type == LexerTokenType::OpenSquareBracket {
             self.next()
         } else {
-            error::throw(
+            return Err(Diagnostic::new(
                 ErrorType::SyntaxError,
                 format!("Unexpected token '{}'{}", group_token.value, context_msg).as_str(),
-                Some(group_token.line),
-            )
+                group_token.line,
+                group_token.at,
+            ));
         }
 
         // get arguments & check ']'
@@ -368,16 +424,17 @@ impl Module {
         let mut closed = false;
 
         while self.is_peekable() {
-            let token = self.unsafe_peek();
+            let token = self.unsafe_peek()?;
 
             match token.token_type {
                 LexerTokenType::Comma => {
                     if last_token == Some(LexerTokenType::Comma) {
-                        error::throw(
+                        return Err(Diagnostic::new(
                             ErrorType::MissingMemberError,
-                            format!("<empty> not valid as a vector member").as_str(),
-                            Some(group_token.line),
-                        )
+                            "<empty> not valid as a vector member",
+                            group_token.line,
+                            group_token.at,
+                        ));
                     }
 
                     last_token = Some(LexerTokenType::Comma);
@@ -385,18 +442,19 @@ impl Module {
                 }
                 LexerTokenType::CloseSquareBracket => {
                     if last_token == Some(LexerTokenType::Comma) {
-                        error::throw(
+                        return Err(Diagnostic::new(
                             ErrorType::MissingMemberError,
-                            format!("<empty> not valid as a vector member").as_str(),
-                            Some(group_token.line),
-                        )
+                            "<empty> not valid as a vector member",
+                            group_token.line,
+                            group_token.at,
+                        ));
                     }
 
                     closed = true;
                     break;
                 }
                 _ => {
-                    let node = self.parse_comparison();
+                    let node = self.parse_logical_or()?;
                     match node {
                         Expression::Identifier(_) => last_token = Some(LexerTokenType::Identifier),
                         Expression::Bool(_) => last_token = Some(LexerTokenType::TrueKeyword),
@@ -411,12 +469,18 @@ impl Module {
                         Expression::BinaryExpression(_) => {
                             last_token = Some(LexerTokenType::Number)
                         }
+                        Expression::UnaryExpression(_) => {
+                            last_token = Some(LexerTokenType::TrueKeyword)
+                        }
                         Expression::StructLiteral(_) => {
                             last_token = Some(LexerTokenType::Identifier)
                         }
                         Expression::Vector(_) => {
                             last_token = Some(LexerTokenType::OpenSquareBracket)
                         }
+                        Expression::MapLiteral(_) => {
+                            last_token = Some(LexerTokenType::OpenCurlyBrace)
+                        }
                         Expression::ObjectLiteral(_) => {
                             // use identifier as a fallback
                             last_token = Some(LexerTokenType::Identifier)
@@ -424,6 +488,9 @@ impl Module {
                         Expression::MemberExpression(_) => {
                             last_token = Some(LexerTokenType::Identifier)
                         }
+                        Expression::IndexExpression(_) => {
+                            last_token = Some(LexerTokenType::Identifier)
+                        }
                     }
                     vector_node.add_child(node);
                 }
@@ -432,21 +499,24 @@ impl Module {
 
         // non closed CallExpression
         if !closed {
-            error::throw(
+            let (line, column) = self.position();
+            return Err(Diagnostic::new(
                 ErrorType::SyntaxError,
                 format!("Expected ']' {}", context_msg).as_str(),
-                Some(vector_node.line),
+                line,
+                column,
             )
+            .with_label(vector_node.line, vector_node.at, "'[' opened here"));
         };
 
         // consume ']'
         self.next();
-        Expression::Vector(vector_node)
+        Ok(Expression::Vector(vector_node))
     }
 
     // let a = 20
-    fn assignment_statement(&self) -> AstNodeType {
-        let token = self.unsafe_peek();
+    fn assignment_statement(&self) -> Result<AstNodeType, Diagnostic> {
+        let token = self.unsafe_peek()?;
         let at = token.at;
         let line = token.line;
 
@@ -464,94 +534,100 @@ impl Module {
         };
 
         // consume identifier
-        let token = self.peek("<Identifier>");
+        let token = self.peek("<Identifier>")?;
         let mut identifier_node = Identifier::new(token.value.clone(), token.at, token.line);
 
         // check if is 'let a:' || 'let a ='
         self.next();
         if self.is_peekable() {
-            let token = self.unsafe_peek();
+            let token = self.unsafe_peek()?;
 
             if token.token_type != LexerTokenType::AssignmentOperator
                 && token.token_type != LexerTokenType::Colon
             {
-                error::throw(
+                return Err(Diagnostic::new(
                     ErrorType::SyntaxError,
                     format!("Expected '=' but got '{}'", token.value).as_str(),
-                    Some(token.line),
-                )
+                    token.line,
+                    token.at,
+                ));
             };
         }
 
         // get type anotation or none
-        let type_annotation = self.type_annotation();
+        let type_annotation = self.type_annotation()?;
         identifier_node.set_annotation(type_annotation);
 
         // check next token is '='
-        let token = self.peek("=");
+        let token = self.peek("=")?;
         if token.token_type != LexerTokenType::AssignmentOperator {
-            error::throw(
+            return Err(Diagnostic::new(
                 ErrorType::SyntaxError,
                 format!("Expected '=' but got '{}'", token.value).as_str(),
-                Some(token.line),
-            )
+                token.line,
+                token.at,
+            ));
         };
 
         self.next();
-        let expr = self.parse_comparison();
+        let expr = self.parse_logical_or()?;
         // static type checking
         if let Some(annotation) = type_annotation {
             match &expr {
                 Expression::Bool(_) => {
                     if annotation != Type::Bool {
-                        error::throw(
+                        return Err(Diagnostic::new(
                             ErrorType::TypeError,
                             format!(
                                 "Annotation of type '{}' differs from assigned 'bool' value",
                                 annotation.to_string()
                             )
                             .as_str(),
-                            Some(token.line),
-                        )
+                            token.line,
+                            token.at,
+                        ));
                     }
                 }
                 Expression::StringLiteral(_) => {
                     if annotation != Type::String {
-                        error::throw(
+                        return Err(Diagnostic::new(
                             ErrorType::TypeError,
                             format!(
                                 "Annotation of type '{}' differs from assigned 'string' value",
                                 annotation.to_string()
                             )
                             .as_str(),
-                            Some(token.line),
-                        )
+                            token.line,
+                            token.at,
+                        ));
                     }
                 }
                 Expression::Number(_) => {
                     if annotation != Type::Number {
-                        error::throw(
+                        return Err(Diagnostic::new(
                             ErrorType::TypeError,
                             format!(
                                 "Annotation of type '{}' differs from assigned 'number' value",
                                 annotation.to_string()
                             )
                             .as_str(),
-                            Some(token.line),
-                        )
+                            token.line,
+                            token.at,
+                        ));
                     }
                 }
                 Expression::Nothing(_) => {
                     if annotation != Type::Nothing {
-                        error::throw(
+                        return Err(Diagnostic::new(
                             ErrorType::TypeError,
                             format!(
                                 "Annotation of type '{}' differs from assigned 'nothing' value",
                                 annotation.to_string()
                             )
                             .as_str(),
-                            Some(token.line),
-                        )
+                            token.line,
+                            token.at,
+                        ));
                     }
                 }
                 _ => {}
@@ -560,56 +636,56 @@ impl Module {
 
         // check for final semicolon
         if self.is_peekable() {
-            if self.peek(";").token_type == LexerTokenType::EndOfStatement {
+            if self.peek(";")?.token_type == LexerTokenType::EndOfStatement {
                 // consume ';'
                 self.next();
             }
         }
 
-        AstNodeType::AssignamentStatement(AssignamentNode::new(
+        Ok(AstNodeType::AssignamentStatement(AssignamentNode::new(
             identifier_node,
             expr,
             var_type,
             at,
             line,
-        ))
+        )))
     }
 
     // fn a() {...}
-    fn function_declaration(&self) -> AstNodeType {
+    fn function_declaration(&self) -> Result<AstNodeType, Diagnostic> {
         // consume 'fn' keyword
         self.next();
 
         // consume function identifier
-        let token = self.peek("<Identifier>");
+        let token = self.peek("<Identifier>")?;
         let identifier_node = Identifier::new(token.value.clone(), token.at, token.line);
         self.next();
 
         // consume arguments
-        let group_node = self.group(Some("function_declaration"));
+        let group_node = self.group(Some("function_declaration"))?;
 
         // check for block
-        let token = self.peek("{");
-        let block_node = self.block();
+        let token = self.peek("{")?;
+        let block_node = self.block()?;
         let function_body = match block_node {
             AstNodeType::Block(b) => b,
             _ => {
-                error::throw(
+                return Err(Diagnostic::new(
                     ErrorType::ParsingError,
                     "Expected blockNode as function body",
-                    Some(token.line),
-                );
-                std::process::exit(1);
+                    token.line,
+                    token.at,
+                ));
             }
         };
 
-        AstNodeType::FunctionDeclaration(FunctionDeclaration::new(
+        Ok(AstNodeType::FunctionDeclaration(FunctionDeclaration::new(
             identifier_node,
             group_node,
             function_body,
             token.at,
             token.line,
-        ))
+        )))
     }
 
     // struct Person {
@@ -617,43 +693,123 @@ impl Module {
     //   surname: string,
     //   phone_number: number
     // }
-    fn struct_declaration(&self) -> AstNodeType {
+    fn struct_declaration(&self) -> Result<AstNodeType, Diagnostic> {
         // consume 'struct' keyword
         self.next();
 
         // consume struct identifier
-        let token = self.peek("<Identifier>");
+        let token = self.peek("<Identifier>")?;
         if token.token_type != LexerTokenType::Identifier {
-            error::throw(
+            return Err(Diagnostic::new(
                 ErrorType::SyntaxError,
                 format!("Expected '<identifier>' but got '{}'", token.value).as_str(),
-                Some(token.line),
-            )
+                token.line,
+                token.at,
+            ));
         }
         let identifier_node = Identifier::new(token.value.clone(), token.at, token.line);
         self.next();
 
         // check for block
-        let token = self.peek("{");
-        let node = self.object_type();
+        let token = self.peek("{")?;
+        let node = self.object_type()?;
         let object_type_node = match node {
             AstNodeType::ObjectType(b) => b,
             _ => {
-                error::throw(
+                return Err(Diagnostic::new(
                     ErrorType::ParsingError,
                     "Expected object type for struct declaration",
-                    Some(token.line),
-                );
-                std::process::exit(1);
+                    token.line,
+                    token.at,
+                ));
             }
         };
 
-        AstNodeType::Struct(Struct::new(
+        Ok(AstNodeType::Struct(Struct::new(
             identifier_node,
             object_type_node,
             token.at,
             token.line,
-        ))
+        )))
+    }
+
+    // impl Person {
+    //   fn new(name) {...}
+    //   fn greet(self) {...}
+    // }
+    fn impl_declaration(&self) -> Result<AstNodeType, Diagnostic> {
+        // consume 'impl' keyword
+        let impl_token = self.unsafe_peek()?;
+        self.next();
+
+        // consume struct identifier
+        let token = self.peek("<Identifier>")?;
+        if token.token_type != LexerTokenType::Identifier {
+            return Err(Diagnostic::new(
+                ErrorType::SyntaxError,
+                format!("Expected '<identifier>' but got '{}'", token.value).as_str(),
+                token.line,
+                token.at,
+            ));
+        }
+        let identifier_node = Identifier::new(token.value.clone(), token.at, token.line);
+        let mut impl_node = ImplDeclaration::new(identifier_node, impl_token.at, impl_token.line);
+        self.next();
+
+        // check '{'
+        let token = self.peek("{")?;
+        if token.token_type != LexerTokenType::OpenCurlyBrace {
+            return Err(Diagnostic::new(
+                ErrorType::SyntaxError,
+                format!("Expected '{{' but got '{}'", token.value).as_str(),
+                token.line,
+                token.at,
+            ));
+        }
+        self.next();
+
+        // methods
+        let mut closed = false;
+        while self.is_peekable() {
+            let token = self.unsafe_peek()?;
+            match token.token_type {
+                LexerTokenType::FnKeyword => match self.function_declaration()? {
+                    AstNodeType::FunctionDeclaration(f) => impl_node.add_method(f),
+                    _ => unreachable!("function_declaration always returns a FunctionDeclaration"),
+                },
+                LexerTokenType::CloseCurlyBrace => {
+                    self.next(); // consume '}'
+                    closed = true;
+                    break;
+                }
+                _ => {
+                    return Err(Diagnostic::new(
+                        ErrorType::SyntaxError,
+                        format!(
+                            "Unexpected token '{}' inside impl block, expected a function",
+                            token.value
+                        )
+                        .as_str(),
+                        token.line,
+                        token.at,
+                    ));
+                }
+            }
+        }
+
+        // non closed impl block
+        if !closed {
+            let (line, column) = self.position();
+            return Err(Diagnostic::new(
+                ErrorType::SyntaxError,
+                "Expected '}' to close impl block",
+                line,
+                column,
+            )
+            .with_label(impl_node.line, impl_node.at, "'{' opened here"));
+        }
+
+        Ok(AstNodeType::ImplDeclaration(impl_node))
     }
 
     // {
@@ -661,21 +817,22 @@ impl Module {
     //   ...: string,
     //   ...: number
     // }
-    fn object_type(&self) -> AstNodeType {
+    fn object_type(&self) -> Result<AstNodeType, Diagnostic> {
         // check '{'
-        let token = self.unsafe_peek();
+        let token = self.unsafe_peek()?;
         if token.token_type == LexerTokenType::OpenCurlyBrace {
             self.next();
         } else {
-            error::throw(
+            return Err(Diagnostic::new(
                 ErrorType::SyntaxError,
                 format!(
                     "Unexpected token '{}' in block openning for object type declaration",
                     token.value
                 )
                 .as_str(),
-                Some(token.line),
-            )
+                token.line,
+                token.at,
+            ));
         };
 
         let mut object_type_node = ObjectType::new(token.at, token.line);
@@ -686,39 +843,41 @@ impl Module {
 
         while self.is_peekable() {
             // consume identifier
-            let token = self.peek("<Identifier>");
+            let token = self.peek("<Identifier>")?;
             if token.token_type != LexerTokenType::Identifier {
-                error::throw(
+                return Err(Diagnostic::new(
                     ErrorType::SyntaxError,
                     format!("Expected '<identifier>' but got '{}'", token.value).as_str(),
-                    Some(token.line),
-                )
+                    token.line,
+                    token.at,
+                ));
             }
             let mut identifier_node = Identifier::new(token.value.clone(), token.at, token.line);
 
             // check if is 'field_name:'
             self.next();
             if self.is_peekable() {
-                let token = self.unsafe_peek();
+                let token = self.unsafe_peek()?;
 
                 if token.token_type != LexerTokenType::Colon {
-                    error::throw(
+                    return Err(Diagnostic::new(
                         ErrorType::SyntaxError,
                         format!("Expected ':' but got '{}'", token.value).as_str(),
-                        Some(token.line),
-                    )
+                        token.line,
+                        token.at,
+                    ));
                 };
             }
 
             // get type anotation or none
-            let type_annotation = self.type_annotation();
+            let type_annotation = self.type_annotation()?;
             identifier_node.set_annotation(type_annotation);
 
             // add field to the object_type_node
             object_type_node.add_field(identifier_node);
 
             // check for closing '}' or the ',' after field
-            let end_of_field = self.peek("<,>");
+            let end_of_field = self.peek("<,>")?;
             if LexerTokenType::Comma == end_of_field.token_type {
                 self.next();
             } else if LexerTokenType::CloseCurlyBrace == end_of_field.token_type {
@@ -726,161 +885,171 @@ impl Module {
                 self.next();
                 break;
             } else {
-                error::throw(
+                return Err(Diagnostic::new(
                     ErrorType::SyntaxError,
                     format!("Expected '}}' but got '{}'", end_of_field.value).as_str(),
-                    Some(token.line),
-                )
+                    token.line,
+                    token.at,
+                ));
             };
         }
 
         // non closed Block
         if !closed {
-            error::throw(
+            let (line, column) = self.position();
+            return Err(Diagnostic::new(
                 ErrorType::SyntaxError,
                 "Expected '}' for block close",
-                Some(token.line),
-            );
+                line,
+                column,
+            )
+            .with_label(token.line, token.at, "'{' opened here"));
         };
 
-        AstNodeType::ObjectType(object_type_node)
+        Ok(AstNodeType::ObjectType(object_type_node))
     }
 
     // if (true) {...}
-    fn if_statement(&self) -> AstNodeType {
+    fn if_statement(&self) -> Result<AstNodeType, Diagnostic> {
         // consume 'if' keyword
-        let token = self.unsafe_peek();
+        let token = self.unsafe_peek()?;
         let at = token.at;
         let line = token.line;
 
         // consume expression
         self.next();
-        let expr = self.expression();
+        let expr = self.expression()?;
         let expr_node = match expr {
             AstNodeType::Expression(b) => b,
             _ => {
-                error::throw(
+                return Err(Diagnostic::new(
                     ErrorType::ParsingError,
                     "Expected expression after if",
-                    Some(token.line),
-                );
-                std::process::exit(1);
+                    token.line,
+                    token.at,
+                ));
             }
         };
 
         // consume '{'
-        let token = self.peek("{");
+        let token = self.peek("{")?;
         if token.token_type != LexerTokenType::OpenCurlyBrace {
-            error::throw(
+            return Err(Diagnostic::new(
                 ErrorType::SyntaxError,
                 format!("Unexpected token '{}' in if statement", token.value).as_str(),
-                Some(token.line),
-            )
+                token.line,
+                token.at,
+            ));
         }
 
-        let block = self.block();
+        let block = self.block()?;
         let block_node = match block {
             AstNodeType::Block(b) => b,
             _ => {
-                error::throw(
+                return Err(Diagnostic::new(
                     ErrorType::ParsingError,
                     "Expected blockNode as if arm",
-                    Some(token.line),
-                );
-                std::process::exit(1);
+                    token.line,
+                    token.at,
+                ));
             }
         };
 
         // if there is else statement
         let mut else_node = None;
-        if self.is_peekable() && self.peek("else").token_type == LexerTokenType::ElseKeyword {
-            let token = self.unsafe_peek();
+        if self.is_peekable() && self.peek("else")?.token_type == LexerTokenType::ElseKeyword {
+            let token = self.unsafe_peek()?;
             let at = token.at;
             let line = token.line;
 
             self.next(); // {
-            let token = self.peek("{");
-            let block = self.block();
+            let token = self.peek("{")?;
+            let block = self.block()?;
             let block_node = match block {
                 AstNodeType::Block(b) => b,
                 _ => {
-                    error::throw(
+                    return Err(Diagnostic::new(
                         ErrorType::ParsingError,
                         "Expected blockNode as else arm",
-                        Some(token.line),
-                    );
-                    std::process::exit(1);
+                        token.line,
+                        token.at,
+                    ));
                 }
             };
 
             else_node = Some(ElseStatement::new(block_node, at, line));
         }
 
-        AstNodeType::IfStatement(IfStatement::new(expr_node, block_node, else_node, at, line))
+        Ok(AstNodeType::IfStatement(IfStatement::new(
+            expr_node, block_node, else_node, at, line,
+        )))
     }
 
     // while (true) {...}
-    fn while_statement(&self) -> AstNodeType {
+    fn while_statement(&self) -> Result<AstNodeType, Diagnostic> {
         // consume 'while' keyword
-        let token = self.unsafe_peek();
+        let token = self.unsafe_peek()?;
         let at = token.at;
         let line = token.line;
 
         // consume expression
         self.next();
-        let expr = self.expression();
+        let expr = self.expression()?;
         let expr_node = match expr {
             AstNodeType::Expression(b) => b,
             _ => {
-                error::throw(
+                return Err(Diagnostic::new(
                     ErrorType::ParsingError,
                     "Expected expression after while",
-                    Some(token.line),
-                );
-                std::process::exit(1);
+                    token.line,
+                    token.at,
+                ));
             }
         };
 
         // consume '{'
-        let token = self.peek("{");
+        let token = self.peek("{")?;
         if token.token_type != LexerTokenType::OpenCurlyBrace {
-            error::throw(
+            return Err(Diagnostic::new(
                 ErrorType::SyntaxError,
                 format!(
                     "Expected '{{' but got '{}' after while condition",
                     token.value
                 )
                 .as_str(),
-                Some(token.line),
-            )
+                token.line,
+                token.at,
+            ));
         }
 
-        let block = self.block();
+        let block = self.block()?;
         let block_node = match block {
             AstNodeType::Block(b) => b,
             _ => {
-                error::throw(
+                return Err(Diagnostic::new(
                     ErrorType::ParsingError,
                     "Expected Block {...} after while condition",
-                    Some(token.line),
-                );
-                std::process::exit(1);
+                    token.line,
+                    token.at,
+                ));
             }
         };
 
-        AstNodeType::WhileStatement(WhileStatement::new(expr_node, block_node, at, line))
+        Ok(AstNodeType::WhileStatement(WhileStatement::new(
+            expr_node, block_node, at, line,
+        )))
     }
 
     // import fs
-    fn import_statement(&self) -> AstNodeType {
+    fn import_statement(&self) -> Result<AstNodeType, Diagnostic> {
         // consume 'import' keyword
-        let token = self.unsafe_peek();
+        let token = self.unsafe_peek()?;
         let at = token.at;
         let line = token.line;
 
         // consume '<identifier>'
         self.next();
-        let token = self.unsafe_peek();
+        let token = self.unsafe_peek()?;
         let (modtype, modname) = match token.token_type {
             LexerTokenType::Identifier => (ModuleType::Native, token.value.clone()),
             LexerTokenType::StringLiteral => {
@@ -890,99 +1059,347 @@ impl Module {
                 (ModuleType::Custom, chars.collect())
             }
             _ => {
-                error::throw(
+                return Err(Diagnostic::new(
                     ErrorType::SyntaxError,
                     format!("Unexpected token '{}' after import keyword", token.value).as_str(),
-                    Some(token.line),
-                );
-                exit(1);
+                    token.line,
+                    token.at,
+                ));
             }
         };
         let module = vec![modname];
         self.next();
 
-        return AstNodeType::ImportStatement(ImportStatement::new(
+        Ok(AstNodeType::ImportStatement(ImportStatement::new(
             module,
             modtype,
             vec![],
             at,
             line,
-        ));
+        )))
     }
 
     // export <a>
-    fn export_statement(&self) -> AstNodeType {
+    fn export_statement(&self) -> Result<AstNodeType, Diagnostic> {
         // consume 'export' keyword
-        let token = self.unsafe_peek();
+        let token = self.unsafe_peek()?;
         let at = token.at;
         let line = token.line;
 
         // consume expression
         self.next();
-        let expression_node = self.parse_comparison();
+        let expression_node = self.parse_logical_or()?;
 
         // check for final semicolon
         if self.is_peekable() {
-            if self.peek(";").token_type == LexerTokenType::EndOfStatement {
+            if self.peek(";")?.token_type == LexerTokenType::EndOfStatement {
                 // consume ';'
                 self.next();
             }
         }
 
-        AstNodeType::ExportStatement(ExportStatement::new(expression_node, at, line))
+        Ok(AstNodeType::ExportStatement(ExportStatement::new(
+            expression_node,
+            at,
+            line,
+        )))
     }
 
     // return "hello";
-    fn return_statement(&self) -> AstNodeType {
+    fn return_statement(&self) -> Result<AstNodeType, Diagnostic> {
         // consume 'return' keyword
-        let token = self.unsafe_peek();
+        let token = self.unsafe_peek()?;
         let at = token.at;
         let line = token.line;
 
         // consume expression
         self.next();
-        let expression_node = self.parse_comparison();
+        let expression_node = self.parse_logical_or()?;
 
         // check for final semicolon
         if self.is_peekable() {
-            if self.peek(";").token_type == LexerTokenType::EndOfStatement {
+            if self.peek(";")?.token_type == LexerTokenType::EndOfStatement {
                 // consume ';'
                 self.next();
             }
         }
 
-        AstNodeType::ReturnStatement(ReturnStatement::new(expression_node, at, line))
+        Ok(AstNodeType::ReturnStatement(ReturnStatement::new(
+            expression_node,
+            at,
+            line,
+        )))
+    }
+
+    // break;
+    fn break_statement(&self) -> Result<AstNodeType, Diagnostic> {
+        let token = self.unsafe_peek()?;
+        self.next(); // consume 'break'
+
+        // check for final semicolon
+        if self.is_peekable() && self.peek(";")?.token_type == LexerTokenType::EndOfStatement {
+            // consume ';'
+            self.next();
+        }
+
+        Ok(AstNodeType::BreakStatement(BreakStatement::new(
+            token.at, token.line,
+        )))
+    }
+
+    // continue;
+    fn continue_statement(&self) -> Result<AstNodeType, Diagnostic> {
+        let token = self.unsafe_peek()?;
+        self.next(); // consume 'continue'
+
+        // check for final semicolon
+        if self.is_peekable() && self.peek(";")?.token_type == LexerTokenType::EndOfStatement {
+            // consume ';'
+            self.next();
+        }
+
+        Ok(AstNodeType::ContinueStatement(ContinueStatement::new(
+            token.at, token.line,
+        )))
+    }
+
+    // try {...} catch err {...}
+    fn try_statement(&self) -> Result<AstNodeType, Diagnostic> {
+        // consume 'try' keyword
+        let token = self.unsafe_peek()?;
+        let at = token.at;
+        let line = token.line;
+        self.next();
+
+        let token = self.peek("{")?;
+        if token.token_type != LexerTokenType::OpenCurlyBrace {
+            return Err(Diagnostic::new(
+                ErrorType::SyntaxError,
+                format!("Expected '{{' but got '{}' after try", token.value).as_str(),
+                token.line,
+                token.at,
+            ));
+        }
+        let body = match self.block()? {
+            AstNodeType::Block(b) => b,
+            _ => unreachable!(),
+        };
+
+        // consume 'catch' keyword
+        let token = self.peek("catch")?;
+        if token.token_type != LexerTokenType::CatchKeyword {
+            return Err(Diagnostic::new(
+                ErrorType::SyntaxError,
+                format!("Expected 'catch' but got '{}' after try block", token.value).as_str(),
+                token.line,
+                token.at,
+            ));
+        }
+        self.next();
+
+        // consume error identifier
+        let token = self.peek("<identifier>")?;
+        if token.token_type != LexerTokenType::Identifier {
+            return Err(Diagnostic::new(
+                ErrorType::SyntaxError,
+                format!(
+                    "Expected '<identifier>' but got '{}' after catch",
+                    token.value
+                )
+                .as_str(),
+                token.line,
+                token.at,
+            ));
+        }
+        let error_identifier = Identifier::new(token.value.clone(), token.at, token.line);
+        self.next();
+
+        let token = self.peek("{")?;
+        if token.token_type != LexerTokenType::OpenCurlyBrace {
+            return Err(Diagnostic::new(
+                ErrorType::SyntaxError,
+                format!("Expected '{{' but got '{}' after catch", token.value).as_str(),
+                token.line,
+                token.at,
+            ));
+        }
+        let catch_body = match self.block()? {
+            AstNodeType::Block(b) => b,
+            _ => unreachable!(),
+        };
+
+        Ok(AstNodeType::TryStatement(TryStatement::new(
+            body,
+            error_identifier,
+            catch_body,
+            at,
+            line,
+        )))
+    }
+
+    // throw "message"
+    fn throw_statement(&self) -> Result<AstNodeType, Diagnostic> {
+        // consume 'throw' keyword
+        let token = self.unsafe_peek()?;
+        let at = token.at;
+        let line = token.line;
+
+        // consume expression
+        self.next();
+        let expression_node = self.parse_logical_or()?;
+
+        // check for final semicolon
+        if self.is_peekable() && self.peek(";")?.token_type == LexerTokenType::EndOfStatement {
+            // consume ';'
+            self.next();
+        }
+
+        Ok(AstNodeType::ThrowStatement(ThrowStatement::new(
+            expression_node,
+            at,
+            line,
+        )))
     }
 
     // a | a() | a.value | a = 20 + a
-    fn identifier(&self) -> AstNodeType {
+    fn identifier(&self) -> Result<AstNodeType, Diagnostic> {
         if let Some(next) = self.peek_next() {
             if LexerTokenType::AssignmentOperator == next.token_type {
-                let node = self.assignment_statement();
-                return node;
+                let node = self.assignment_statement()?;
+                return Ok(node);
             }
         }
 
-        let node = self.parse_postfix_expression();
-        AstNodeType::Expression(node)
+        let node = self.parse_factor()?;
+
+        // v[0] = 20 | p.name = "x" | v[0].name = "x"
+        if self.is_peekable()
+            && self.unsafe_peek()?.token_type == LexerTokenType::AssignmentOperator
+        {
+            match &node {
+                Expression::IndexExpression(target) => {
+                    return self.index_assignment_statement(target.clone());
+                }
+                Expression::MemberExpression(target) => {
+                    return self.property_assignment_statement(target.clone());
+                }
+                _ => {}
+            }
+        }
+
+        Ok(AstNodeType::Expression(node))
+    }
+
+    // v[0] = 20
+    fn index_assignment_statement(
+        &self,
+        target: IndexExpression,
+    ) -> Result<AstNodeType, Diagnostic> {
+        self.next(); // consume '='
+        let expr = self.parse_logical_or()?;
+
+        // check for final semicolon
+        if self.is_peekable() && self.peek(";")?.token_type == LexerTokenType::EndOfStatement {
+            // consume ';'
+            self.next();
+        }
+
+        let (at, line) = (target.at, target.line);
+        Ok(AstNodeType::IndexAssignamentStatement(
+            IndexAssignamentNode::new(target, expr, at, line),
+        ))
+    }
+
+    // p.name = "x"
+    fn property_assignment_statement(
+        &self,
+        target: MemberExpression,
+    ) -> Result<AstNodeType, Diagnostic> {
+        self.next(); // consume '='
+        let expr = self.parse_logical_or()?;
+
+        // check for final semicolon
+        if self.is_peekable() && self.peek(";")?.token_type == LexerTokenType::EndOfStatement {
+            // consume ';'
+            self.next();
+        }
+
+        let (at, line) = (target.at, target.line);
+        Ok(AstNodeType::PropertyAssignamentStatement(
+            PropertyAssignamentNode::new(target, expr, at, line),
+        ))
     }
 
     // (2 * 2) + 3
-    fn expression(&self) -> AstNodeType {
-        let expr = self.parse_comparison();
-        AstNodeType::Expression(expr)
+    fn expression(&self) -> Result<AstNodeType, Diagnostic> {
+        let expr = self.parse_logical_or()?;
+        Ok(AstNodeType::Expression(expr))
+    }
+
+    // a || b
+    fn parse_logical_or(&self) -> Result<Expression, Diagnostic> {
+        let mut node = self.parse_logical_and()?;
+
+        while self.is_peekable() {
+            let token = self.unsafe_peek()?;
+            match token.token_type {
+                LexerTokenType::OrOperator => {
+                    // consume the operator
+                    self.next();
+
+                    // get right node
+                    let right = self.parse_logical_and()?;
+                    node = Expression::BinaryExpression(BinaryExpression::new(
+                        token.value.clone(),
+                        Box::new(node),
+                        Box::new(right),
+                        token.at,
+                        token.line,
+                    ));
+                }
+                _ => break,
+            }
+        }
+
+        Ok(node)
+    }
+
+    // a && b
+    fn parse_logical_and(&self) -> Result<Expression, Diagnostic> {
+        let mut node = self.parse_comparison()?;
+
+        while self.is_peekable() {
+            let token = self.unsafe_peek()?;
+            match token.token_type {
+                LexerTokenType::AmpersandOperator => {
+                    // consume the operator
+                    self.next();
+
+                    // get right node
+                    let right = self.parse_comparison()?;
+                    node = Expression::BinaryExpression(BinaryExpression::new(
+                        token.value.clone(),
+                        Box::new(node),
+                        Box::new(right),
+                        token.at,
+                        token.line,
+                    ));
+                }
+                _ => break,
+            }
+        }
+
+        Ok(node)
     }
 
     // 2 > 3
-    fn parse_comparison(&self) -> Expression {
-        let mut node = self.parse_expression();
+    fn parse_comparison(&self) -> Result<Expression, Diagnostic> {
+        let mut node = self.parse_expression()?;
 
         while self.is_peekable() {
-            let token = self.unsafe_peek();
+            let token = self.unsafe_peek()?;
             match token.token_type {
-                LexerTokenType::AmpersandOperator
-                | LexerTokenType::OrOperator
-                | LexerTokenType::GreaterThanOperator
+                LexerTokenType::GreaterThanOperator
                 | LexerTokenType::LessThanOperator
                 | LexerTokenType::EqualityOperator
                 | LexerTokenType::NotEqualOperator
@@ -992,7 +1409,7 @@ impl Module {
                     self.next();
 
                     // get right node
-                    let right = self.parse_expression();
+                    let right = self.parse_expression()?;
                     node = Expression::BinaryExpression(BinaryExpression::new(
                         token.value.clone(),
                         Box::new(node),
@@ -1005,21 +1422,21 @@ impl Module {
             }
         }
 
-        node
+        Ok(node)
     }
 
     // 2 + 3 * 23
-    fn parse_expression(&self) -> Expression {
-        let mut node = self.parse_term();
+    fn parse_expression(&self) -> Result<Expression, Diagnostic> {
+        let mut node = self.parse_term()?;
         while self.is_peekable() {
-            let token = self.unsafe_peek();
+            let token = self.unsafe_peek()?;
             match token.token_type {
                 LexerTokenType::AddOperator | LexerTokenType::SubtractOperator => {
                     // consume the operator
                     self.next();
 
                     // get right node
-                    let right = self.parse_term();
+                    let right = self.parse_term()?;
                     node = Expression::BinaryExpression(BinaryExpression::new(
                         token.value.clone(),
                         Box::new(node),
@@ -1032,22 +1449,22 @@ impl Module {
             }
         }
 
-        node
+        Ok(node)
     }
 
     // 2 * 4
-    fn parse_term(&self) -> Expression {
-        let mut node = self.parse_factor();
+    fn parse_term(&self) -> Result<Expression, Diagnostic> {
+        let mut node = self.parse_factor()?;
 
         while self.is_peekable() {
-            let token = self.unsafe_peek();
+            let token = self.unsafe_peek()?;
             match token.token_type {
                 LexerTokenType::MultiplyOperator | LexerTokenType::DivideOperator => {
                     // consume the operator
                     self.next();
 
                     // get right node
-                    let right = self.parse_factor();
+                    let right = self.parse_factor()?;
                     node = Expression::BinaryExpression(BinaryExpression::new(
                         token.value.clone(),
                         Box::new(node),
@@ -1060,31 +1477,32 @@ impl Module {
             }
         }
 
-        node
+        Ok(node)
     }
 
     // 2 | x | "Hi"
-    fn parse_factor(&self) -> Expression {
-        let token = self.unsafe_peek();
+    fn parse_factor(&self) -> Result<Expression, Diagnostic> {
+        let token = self.peek("<expression>")?;
         let expr = match token.token_type {
             LexerTokenType::OpenParenthesis => {
                 self.next(); // to consume the '('
-                let expr = self.parse_expression();
+                let expr = self.parse_logical_or()?;
 
-                let scoped_token = self.peek(")");
+                let scoped_token = self.peek(")")?;
                 if scoped_token.token_type == LexerTokenType::CloseParenthesis {
                     self.next(); // to consume the ')'
                     expr
                 } else {
-                    error::throw(
+                    return Err(Diagnostic::new(
                         ErrorType::ParsingError,
                         format!("Unexpected token '{}', expected ')'", scoped_token.value).as_str(),
-                        Some(scoped_token.line),
-                    );
-                    std::process::exit(1);
+                        scoped_token.line,
+                        scoped_token.at,
+                    ));
                 }
             }
-            LexerTokenType::OpenSquareBracket => self.vector(Some("assignament statement")),
+            LexerTokenType::OpenSquareBracket => self.vector(Some("assignament statement"))?,
+            LexerTokenType::OpenCurlyBrace => self.map_literal()?,
             LexerTokenType::Number => {
                 let number_node = Number::from_string(token.value.clone(), token.at, token.line);
 
@@ -1092,24 +1510,24 @@ impl Module {
                     self.next(); // consume number itself
                     Expression::Number(node)
                 } else {
-                    error::throw(
+                    return Err(Diagnostic::new(
                         ErrorType::ParsingError,
                         format!("Invalid token '{}' inside of a expression", token.value).as_str(),
-                        Some(token.line),
-                    );
-                    std::process::exit(1);
+                        token.line,
+                        token.at,
+                    ));
                 }
             }
             LexerTokenType::TrueKeyword | LexerTokenType::FalseKeyword => {
                 let node = if let Ok(bool_value) = token.value.parse::<bool>() {
                     Bool::new(bool_value, token.at, token.line)
                 } else {
-                    error::throw(
+                    return Err(Diagnostic::new(
                         ErrorType::ParsingError,
                         format!("Invalid token '{}' inside of a expression", token.value).as_str(),
-                        Some(token.line),
-                    );
-                    std::process::exit(1);
+                        token.line,
+                        token.at,
+                    ));
                 };
 
                 self.next(); // consume keyword
@@ -1130,23 +1548,11 @@ impl Module {
             LexerTokenType::Identifier => {
                 // check the identifier context:
                 //   - variable identifier: x
-                //   - identifier call: x()
                 //   - struct declaration: X {...}
-                if let Some(next) = self.peek_next() {
-                    if next.token_type == LexerTokenType::OpenParenthesis
-                        || next.token_type == LexerTokenType::Dot
-                    {
-                        self.parse_postfix_expression()
-                    } else if next.token_type == LexerTokenType::OpenCurlyBrace {
-                        self.struct_literal()
-                    } else {
-                        self.next();
-                        Expression::Identifier(Identifier::new(
-                            token.value.clone(),
-                            token.at,
-                            token.line,
-                        ))
-                    }
+                // calls, member and index accesses are postfixes
+                let next = self.peek_next();
+                if next.is_some_and(|next| next.token_type == LexerTokenType::OpenCurlyBrace) {
+                    self.struct_literal()?
                 } else {
                     self.next();
                     Expression::Identifier(Identifier::new(
@@ -1160,27 +1566,69 @@ impl Module {
                 self.next(); // consume nothing keyword
                 Expression::Nothing(Nothing::new(token.at, token.line))
             }
+            LexerTokenType::NotOperator => {
+                self.next(); // consume '!'
+                let operand = self.parse_factor()?;
+                // the operand already took its postfixes: !v[0]
+                return Ok(Expression::UnaryExpression(UnaryExpression::new(
+                    token.value.clone(),
+                    Box::new(operand),
+                    token.at,
+                    token.line,
+                )));
+            }
             _ => {
-                error::throw(
-                    error::ErrorType::SyntaxError,
+                return Err(Diagnostic::new(
+                    ErrorType::SyntaxError,
                     format!("Invalid token '{}' inside of a expression", token.value).as_str(),
-                    Some(token.line),
-                );
-                std::process::exit(1);
+                    token.line,
+                    token.at,
+                ));
             }
         };
 
-        expr
+        self.parse_postfix_expression(expr)
     }
 
-    // a.b()
-    fn parse_postfix_expression(&self) -> Expression {
-        let mut expr = self.member_expression();
+    // a.b() | v[0].x | make()() | [1, 2][0]
+    fn parse_postfix_expression(&self, primary: Expression) -> Result<Expression, Diagnostic> {
+        let mut expr = primary;
         while self.is_peekable() {
-            let next = self.unsafe_peek();
+            let next = self.unsafe_peek()?;
             match next.token_type {
+                LexerTokenType::Dot => {
+                    self.next(); // consume '.'
+
+                    // get the identifier
+                    let identifier_token = self.peek("<identifier>")?;
+                    if identifier_token.token_type != LexerTokenType::Identifier {
+                        return Err(Diagnostic::new(
+                            ErrorType::SyntaxError,
+                            format!(
+                                "Expected '<identifier>' but got '{}'",
+                                identifier_token.value
+                            )
+                            .as_str(),
+                            identifier_token.line,
+                            identifier_token.at,
+                        ));
+                    }
+                    let identifier_node = Identifier::new(
+                        identifier_token.value.clone(),
+                        identifier_token.at,
+                        identifier_token.line,
+                    );
+                    self.next(); // consume identifier
+
+                    expr = Expression::MemberExpression(MemberExpression::new(
+                        Box::new(expr),
+                        identifier_node,
+                        identifier_token.at,
+                        identifier_token.line,
+                    ));
+                }
                 LexerTokenType::OpenParenthesis => {
-                    let group_node = self.group(Some("call expression"));
+                    let group_node = self.group(Some("call expression"))?;
                     expr = Expression::CallExpression(CallExpression::new(
                         Box::new(expr),
                         group_node,
@@ -1188,22 +1636,39 @@ impl Module {
                         next.line,
                     ));
                 }
+                LexerTokenType::OpenSquareBracket => {
+                    self.next(); // consume '['
+                    let index = self.parse_logical_or()?;
+
+                    // check ']'
+                    let token = self.peek("]")?;
+                    if token.token_type != LexerTokenType::CloseSquareBracket {
+                        return Err(Diagnostic::new(
+                            ErrorType::SyntaxError,
+                            format!("Expected ']' but got '{}'", token.value).as_str(),
+                            token.line,
+                            token.at,
+                        ));
+                    };
+                    self.next(); // consume ']'
+
+                    expr = Expression::IndexExpression(IndexExpression::new(
+                        Box::new(expr),
+                        Box::new(index),
+                        next.at,
+                        next.line,
+                    ));
+                }
                 LexerTokenType::OpenCurlyBrace => {
                     let struct_type = match expr {
                         Expression::Identifier(i) => StructTypeExpr::Identifier(i),
                         Expression::MemberExpression(m) => {
                             StructTypeExpr::MemberExpression(Box::new(m))
                         }
-                        _ => {
-                            error::throw(
-                                ErrorType::ParsingError,
-                                "Expected 'object literal' for struct initialization",
-                                Some(next.line),
-                            );
-                            std::process::exit(1);
-                        }
+                        // the block of an statement: if v[0] {...}
+                        _ => break,
                     };
-                    let object_literal = self.object_literal();
+                    let object_literal = self.object_literal()?;
 
                     expr = Expression::StructLiteral(StructLiteral::new(
                         struct_type,
@@ -1211,54 +1676,12 @@ impl Module {
                         next.at,
                         next.line,
                     ));
-                    break; // after struct literal is not another member_expression enabled
                 }
                 _ => break,
             }
         }
 
-        expr
-    }
-
-    // person.name.to_string
-    fn member_expression(&self) -> Expression {
-        // get the identifier
-        let identifier_token = self.unsafe_peek();
-        let mut node = Expression::Identifier(Identifier::new(
-            identifier_token.value.clone(),
-            identifier_token.at,
-            identifier_token.line,
-        ));
-        self.next();
-
-        while self.is_peekable() {
-            if let LexerTokenType::Dot = self.unsafe_peek().token_type {
-                // consume dot
-                self.next();
-
-                // get the identifier
-                let identifier_token = self.peek("<identifier>");
-                let identifier_node = Identifier::new(
-                    identifier_token.value.clone(),
-                    identifier_token.at,
-                    identifier_token.line,
-                );
-
-                node = Expression::MemberExpression(MemberExpression::new(
-                    Box::new(node.clone()),
-                    identifier_node,
-                    identifier_token.at,
-                    identifier_token.line,
-                ));
-                // consume identifier
-                self.next();
-            } else {
-                // end of member expression
-                break;
-            }
-        }
-
-        node
+        Ok(expr)
     }
 
     // Person {
@@ -1266,28 +1689,29 @@ impl Module {
     //   surname: string,
     //   phone_number: number
     // }
-    fn struct_literal(&self) -> Expression {
+    fn struct_literal(&self) -> Result<Expression, Diagnostic> {
         // consume struct identifier
-        let token = self.peek("<Identifier>");
+        let token = self.peek("<Identifier>")?;
         if token.token_type != LexerTokenType::Identifier {
-            error::throw(
+            return Err(Diagnostic::new(
                 ErrorType::SyntaxError,
                 format!("Expected '<identifier>' but got '{}'", token.value).as_str(),
-                Some(token.line),
-            )
+                token.line,
+                token.at,
+            ));
         }
         let identifier_node = Identifier::new(token.value.clone(), token.at, token.line);
 
         // check for block
         self.next();
-        let object_literal_node = self.object_literal();
+        let object_literal_node = self.object_literal()?;
 
-        Expression::StructLiteral(StructLiteral::new(
+        Ok(Expression::StructLiteral(StructLiteral::new(
             StructTypeExpr::Identifier(identifier_node),
             object_literal_node,
             token.at,
             token.line,
-        ))
+        )))
     }
 
     // {
@@ -1295,21 +1719,22 @@ impl Module {
     //   ...: value,
     //   ...: value
     // }
-    fn object_literal(&self) -> ObjectLiteral {
+    fn object_literal(&self) -> Result<ObjectLiteral, Diagnostic> {
         // check '{'
-        let token = self.unsafe_peek();
+        let token = self.unsafe_peek()?;
         if token.token_type == LexerTokenType::OpenCurlyBrace {
             self.next();
         } else {
-            error::throw(
+            return Err(Diagnostic::new(
                 ErrorType::SyntaxError,
                 format!(
                     "Unexpected token '{}' in block openning for object literal declaration",
                     token.value
                 )
                 .as_str(),
-                Some(token.line),
-            )
+                token.line,
+                token.at,
+            ));
         };
 
         let mut object_literal_node = ObjectLiteral::new(token.at, token.line);
@@ -1320,39 +1745,41 @@ impl Module {
 
         while self.is_peekable() {
             // consume identifier
-            let token = self.peek("<Identifier>");
+            let token = self.peek("<Identifier>")?;
             if token.token_type != LexerTokenType::Identifier {
-                error::throw(
+                return Err(Diagnostic::new(
                     ErrorType::SyntaxError,
                     format!("Expected '<identifier>' but got '{}'", token.value).as_str(),
-                    Some(token.line),
-                )
+                    token.line,
+                    token.at,
+                ));
             }
             let identifier_node = Identifier::new(token.value.clone(), token.at, token.line);
 
             // check if is 'field_name:'
             self.next();
             if self.is_peekable() {
-                let token = self.unsafe_peek();
+                let token = self.unsafe_peek()?;
 
                 if token.token_type != LexerTokenType::Colon {
-                    error::throw(
+                    return Err(Diagnostic::new(
                         ErrorType::SyntaxError,
                         format!("Expected ':' but got '{}'", token.value).as_str(),
-                        Some(token.line),
-                    )
+                        token.line,
+                        token.at,
+                    ));
                 };
             }
 
             // get field expression
             self.next();
-            let expression_node = self.parse_comparison();
+            let expression_node = self.parse_logical_or()?;
 
             // add field to the object_type_node
             object_literal_node.add_field(identifier_node, expression_node);
 
             // check for closing '}' or the ',' after field
-            let end_of_field = self.peek("<,>");
+            let end_of_field = self.peek("<,>")?;
             if LexerTokenType::Comma == end_of_field.token_type {
                 self.next();
             } else if LexerTokenType::CloseCurlyBrace == end_of_field.token_type {
@@ -1360,59 +1787,156 @@ impl Module {
                 self.next();
                 break;
             } else {
-                error::throw(
+                return Err(Diagnostic::new(
                     ErrorType::SyntaxError,
                     format!("Expected '}}' but got '{}'", end_of_field.value).as_str(),
-                    Some(token.line),
-                )
+                    token.line,
+                    token.at,
+                ));
             };
         }
 
         // non closed Block
         if !closed {
-            error::throw(
+            let (line, column) = self.position();
+            return Err(Diagnostic::new(
                 ErrorType::SyntaxError,
                 "Expected '}' for block close",
-                Some(token.line),
+                line,
+                column,
+            )
+            .with_label(token.line, token.at, "'{' opened here"));
+        };
+
+        Ok(object_literal_node)
+    }
+
+    // {
+    //   "key": value,
+    //   "...": value
+    // }
+    fn map_literal(&self) -> Result<Expression, Diagnostic> {
+        // consume '{'
+        let token = self.unsafe_peek()?;
+        self.next();
+
+        let mut map_node = MapLiteral::new(token.at, token.line);
+        let mut closed = false;
+
+        while self.is_peekable() {
+            let key_token = self.unsafe_peek()?;
+            match key_token.token_type {
+                LexerTokenType::CloseCurlyBrace => {
+                    closed = true;
+                    self.next();
+                    break;
+                }
+                LexerTokenType::StringLiteral => {}
+                _ => {
+                    return Err(Diagnostic::new(
+                        ErrorType::SyntaxError,
+                        format!("Expected a string key but got '{}'", key_token.value).as_str(),
+                        key_token.line,
+                        key_token.at,
+                    ));
+                }
+            }
+
+            let mut chars = key_token.value.chars();
+            chars.next();
+            chars.next_back();
+            let key_node = StringLiteral::new(
+                key_token.value.clone(),
+                chars.collect(),
+                key_token.at,
+                key_token.line,
             );
+
+            // check ':' after the key
+            self.next();
+            let colon = self.peek(":")?;
+            if colon.token_type != LexerTokenType::Colon {
+                return Err(Diagnostic::new(
+                    ErrorType::SyntaxError,
+                    format!("Expected ':' but got '{}'", colon.value).as_str(),
+                    colon.line,
+                    colon.at,
+                ));
+            }
+
+            // get entry value
+            self.next();
+            let value_node = self.parse_logical_or()?;
+            map_node.add_entry(key_node, value_node);
+
+            // check for closing '}' or the ',' after the entry
+            let end_of_entry = self.peek("<,>")?;
+            if LexerTokenType::Comma == end_of_entry.token_type {
+                self.next();
+            } else if LexerTokenType::CloseCurlyBrace == end_of_entry.token_type {
+                closed = true;
+                self.next();
+                break;
+            } else {
+                return Err(Diagnostic::new(
+                    ErrorType::SyntaxError,
+                    format!("Expected '}}' but got '{}'", end_of_entry.value).as_str(),
+                    end_of_entry.line,
+                    end_of_entry.at,
+                ));
+            };
+        }
+
+        // non closed map
+        if !closed {
+            let (line, column) = self.position();
+            return Err(Diagnostic::new(
+                ErrorType::SyntaxError,
+                "Expected '}' for map close",
+                line,
+                column,
+            )
+            .with_label(token.line, token.at, "'{' opened here"));
         };
 
-        object_literal_node
+        Ok(Expression::MapLiteral(map_node))
     }
 
     // : bool | : string | : number | : nothing
-    fn type_annotation(&self) -> Option<Type> {
-        if self.peek(":").token_type == LexerTokenType::Colon {
+    fn type_annotation(&self) -> Result<Option<Type>, Diagnostic> {
+        if self.peek(":")?.token_type == LexerTokenType::Colon {
             // consume ':'
             self.next();
             if self.is_peekable() {
-                let possible_type = self.unsafe_peek();
+                let possible_type = self.unsafe_peek()?;
                 self.next(); // consume 'annotated type'
                 match possible_type.token_type {
-                    LexerTokenType::NumberKeyword => Some(Type::Number),
-                    LexerTokenType::StringKeyword => Some(Type::String),
-                    LexerTokenType::BoolKeyword => Some(Type::Bool),
-                    LexerTokenType::NothingKeyword => Some(Type::Nothing),
+                    LexerTokenType::NumberKeyword => Ok(Some(Type::Number)),
+                    LexerTokenType::StringKeyword => Ok(Some(Type::String)),
+                    LexerTokenType::BoolKeyword => Ok(Some(Type::Bool)),
+                    LexerTokenType::NothingKeyword => Ok(Some(Type::Nothing)),
                     _ => {
-                        error::throw(
+                        return Err(Diagnostic::new(
                             ErrorType::InvalidTypeAnnotation,
                             format!("Expected type after ':' but got '{}'", possible_type.value)
                                 .as_str(),
-                            None,
-                        );
-                        std::process::exit(1);
+                            possible_type.line,
+                            possible_type.at,
+                        )
+                        .with_help("the types are bool, string, number and nothing"));
                     }
                 }
             } else {
-                error::throw(
+                let (line, column) = self.position();
+                return Err(Diagnostic::new(
                     ErrorType::ParsingError,
-                    format!("Expected type after ':' but got and early end of module").as_str(),
-                    None,
-                );
-                std::process::exit(1);
+                    "Expected type after ':' but got and early end of module",
+                    line,
+                    column,
+                ));
             }
         } else {
-            None
+            Ok(None)
         }
     }
 }
@@ -0,0 +1,54 @@
+use std::fs;
+
+use crate::ast::lex;
+use crate::ast::Module;
+use crate::compiler::Compiler;
+use crate::core::error;
+use crate::core::error::ErrorType;
+use self_vm::Renderer;
+
+pub struct Check {
+    args: Vec<String>,
+}
+
+impl Check {
+    pub fn new(args: Vec<String>) -> Check {
+        Check { args }
+    }
+    pub fn exec(&self) -> Result<(), i32> {
+        let module_name = if self.args.len() > 0 {
+            self.args[0].clone()
+        } else {
+            "main.ego".to_string() // default lookup on a ego project
+        };
+
+        let file_content = fs::read_to_string(&module_name).map_err(|_| {
+            error::throw(
+                ErrorType::FatalError,
+                format!("Cannot read {}\n", module_name).as_str(),
+                None,
+            );
+            1
+        })?;
+        let renderer = Renderer::from_args(&self.args);
+
+        // the module is compiled but not run
+        let diagnostics = match lex(file_content.clone()) {
+            Ok(tokens) => match Module::new(module_name.clone(), tokens).parse() {
+                Ok(ast) => match Compiler::new(ast, file_content.clone()).gen_bytecode() {
+                    Ok(_) => vec![],
+                    Err(diagnostics) => diagnostics,
+                },
+                Err(diagnostics) => diagnostics,
+            },
+            Err(diagnostics) => diagnostics,
+        };
+
+        if !diagnostics.is_empty() {
+            error::report(&diagnostics, &module_name, &file_content, &renderer);
+            return Err(1);
+        }
+        println!("{module_name}: no problems found");
+        Ok(())
+    }
+}
@@ -7,6 +7,8 @@ use crate::ast::Module;
 use crate::compiler::Compiler;
 use crate::core::error;
 use crate::core::error::ErrorType;
+use self_vm::Program;
+use self_vm::Renderer;
 
 pub struct Compile {
     args: Vec<String>,
@@ -19,23 +21,37 @@ impl Compile {
     pub fn debug(&self) -> bool {
         self.args.contains(&"-d".to_string())
     }
-    pub fn exec(&self) {
-        let (module_name, out_name) = if self.args.len() > 1 {
-            (self.args[0].clone(), self.args[1].clone())
+    // the sources are only stored in the program on request,
+    // compiled programs are shipped without them
+    pub fn embed_sources(&self) -> bool {
+        self.args.contains(&"--embed-sources".to_string())
+    }
+    pub fn exec(&self) -> Result<(), i32> {
+        let paths: Vec<&String> = self
+            .args
+            .iter()
+            .filter(|arg| !arg.starts_with('-'))
+            .collect();
+        let (module_name, out_name) = if paths.len() > 1 {
+            (paths[0].clone(), paths[1].clone())
         } else {
             ("main.ego".to_string(), "main.b".to_string()) // default lookup on a ego project
         };
 
-        let file_content = fs::read_to_string(&module_name).unwrap_or_else(|_| {
+        let file_content = fs::read_to_string(&module_name).map_err(|_| {
             error::throw(
                 ErrorType::FatalError,
                 format!("Cannot read {}\n", module_name).as_str(),
                 None,
             );
-            std::process::exit(1); // to avoid types error
-        });
+            1
+        })?;
+        let renderer = Renderer::from_args(&self.args);
 
-        let tokens = lex(file_content);
+        let tokens = lex(file_content.clone()).map_err(|diagnostics| {
+            error::report(&diagnostics, &module_name, &file_content, &renderer);
+            1
+        })?;
         if self.debug() {
             println!("\nLexer tokens: \n-------------");
             for (i, token) in tokens.iter().enumerate() {
@@ -43,28 +59,42 @@ impl Compile {
             }
         }
 
-        let mut module = Module::new(module_name, tokens);
-        let ast = module.parse();
+        let mut module = Module::new(module_name.clone(), tokens);
+        let ast = module.parse().map_err(|diagnostics| {
+            error::report(&diagnostics, &module_name, &file_content, &renderer);
+            1
+        })?;
         if self.debug() {
             println!("\nAst nodes: \n---------------\n{:#?}", ast);
         }
 
-        let mut compiler = Compiler::new(ast);
-        let bytecode = compiler.gen_bytecode();
+        let mut compiler = Compiler::new(ast, file_content.clone());
+        let bytecode = compiler.gen_bytecode().map_err(|diagnostics| {
+            error::report(&diagnostics, &module_name, &file_content, &renderer);
+            1
+        })?;
+        let program = Program {
+            bytecode,
+            line_table: compiler.line_table(),
+            exports: compiler.exports(),
+            imports: compiler.imports(),
+            embed_sources: self.embed_sources(),
+        };
 
         let mut file = match File::create(&out_name) {
             Ok(file) => file,
             Err(_) => {
                 error::throw(ErrorType::SyntaxError, "Cannot write file", None);
-                unreachable!()
+                return Err(1);
             }
         };
-        match file.write_all(&bytecode) {
+        match file.write_all(&program.to_bytes()) {
             Ok(_) => {}
             Err(_) => {
                 error::throw(ErrorType::SyntaxError, "Cannot write file", None);
-                unreachable!()
+                return Err(1);
             }
         };
+        Ok(())
     }
 }
@@ -0,0 +1,76 @@
+use std::fs;
+
+use crate::core::error;
+use crate::core::error::ErrorType;
+use self_vm::Renderer;
+
+pub struct Exec {
+    args: Vec<String>,
+}
+
+impl Exec {
+    pub fn new(args: Vec<String>) -> Exec {
+        Exec { args }
+    }
+    // ego options go before the program path, everything after
+    // it belongs to the program: ego exec --no-verify app.b -d
+    fn options(&self) -> &[String] {
+        match self.path_index() {
+            Some(index) => &self.args[..index],
+            None => &self.args,
+        }
+    }
+    fn path_index(&self) -> Option<usize> {
+        self.args.iter().position(|arg| !arg.starts_with('-'))
+    }
+    pub fn verify(&self) -> bool {
+        !self.options().contains(&"--no-verify".to_string())
+    }
+    pub fn debug(&self) -> bool {
+        self.options().contains(&"-d".to_string())
+    }
+    pub fn exec(&self) -> Result<(), i32> {
+        let program_name = match self.path_index() {
+            Some(index) => self.args[index].clone(),
+            None => "main.b".to_string(), // default output of ego compile
+        };
+
+        let bytes = fs::read(&program_name).map_err(|_| {
+            error::throw(
+                ErrorType::FatalError,
+                format!("Cannot read {}\n", program_name).as_str(),
+                None,
+            );
+            1
+        })?;
+
+        let renderer = Renderer::from_args(self.options());
+
+        // files that are not valid programs exit with 2, errors
+        // raised while running them with 1
+        let mut vm = match self_vm::load(&bytes) {
+            Ok(vm) => vm,
+            Err(err) => {
+                eprintln!("{}", renderer.render(&err.report()));
+                return Err(2);
+            }
+        };
+        vm.set_verification(self.verify());
+        vm.set_debug(self.debug());
+
+        // os.args() sees the program path and its arguments
+        let args = match self.path_index() {
+            Some(index) => self.args[index..].to_vec(),
+            None => vec![program_name],
+        };
+        let execution = vm.run(&args);
+        if let Some(traceback) = execution.traceback {
+            eprintln!("{traceback}");
+        }
+        if let Some(err) = execution.error {
+            eprintln!("{}", renderer.render(&err.report()));
+            return Err(1);
+        }
+        Ok(())
+    }
+}
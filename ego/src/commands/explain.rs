@@ -0,0 +1,47 @@
+use crate::core::error;
+use crate::core::error::ErrorType;
+use self_vm::Explanation;
+use self_vm::VM_ERRORS;
+
+pub struct Explain {
+    args: Vec<String>,
+}
+
+impl Explain {
+    pub fn new(args: Vec<String>) -> Explain {
+        Explain { args }
+    }
+    pub fn exec(&self) -> Result<(), i32> {
+        let Some(code) = self.args.first() else {
+            // without a code lists all of them
+            for explanation in error::ERRORS.iter().chain(VM_ERRORS) {
+                println!("{}  {}", explanation.code, explanation.title);
+            }
+            return Ok(());
+        };
+
+        let Some(explanation) = error::explain(code) else {
+            error::throw(
+                ErrorType::EgoUsageError,
+                format!(
+                    "{} is not an error code, run 'ego explain' to list them",
+                    code
+                )
+                .as_str(),
+                None,
+            );
+            return Err(1);
+        };
+        print_explanation(explanation);
+        Ok(())
+    }
+}
+
+fn print_explanation(explanation: &Explanation) {
+    println!("{}: {}\n", explanation.code, explanation.title);
+    println!("{}\n", explanation.description);
+    println!("Example:\n");
+    for line in explanation.example.lines() {
+        println!("    {line}");
+    }
+}
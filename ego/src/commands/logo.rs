@@ -6,7 +6,7 @@ impl Logo {
     pub fn new(args: Vec<String>) -> Logo {
         Logo { args }
     }
-    pub fn exec(&self) {
+    pub fn exec(&self) -> Result<(), i32> {
         println!(
             "
               $$$$$$$$$$$$$$$$$$$$$  
@@ -23,6 +23,7 @@ impl Logo {
             $$$$$$$$$$$$$$$$$$$$$$$$$ 
               $$$$$$$$$$$$$$$$$$$$$     
            "
-        )
+        );
+        Ok(())
     }
 }
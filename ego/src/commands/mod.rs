@@ -1,4 +1,7 @@
+pub mod check;
 pub mod compile;
+pub mod exec;
+pub mod explain;
 pub mod logo;
 pub mod new;
 pub mod run;
@@ -7,7 +10,10 @@ use self::logo::Logo;
 use self::new::New;
 use self::run::Run;
 
+use crate::commands::check::Check;
 use crate::commands::compile::Compile;
+use crate::commands::exec::Exec;
+use crate::commands::explain::Explain;
 use crate::core::error;
 use crate::core::error::ErrorType;
 use std::env;
@@ -17,15 +23,21 @@ pub enum Command {
     Logo(Logo),
     New(New),
     Compile(Compile),
+    Exec(Exec),
+    Check(Check),
+    Explain(Explain),
 }
 
 impl Command {
-    pub fn parse() -> Command {
+    pub fn parse() -> Result<Command, i32> {
         let args: Vec<String> = env::args().collect();
         if args.len() >= 2 {
             let command = args[1].clone();
             let remaining_args = &args[2..];
-            return Command::cmd_from_str(command.as_str(), remaining_args.to_vec());
+            Ok(Command::cmd_from_str(
+                command.as_str(),
+                remaining_args.to_vec(),
+            ))
         } else {
             // print help message instead of error
             error::throw(
@@ -33,8 +45,8 @@ impl Command {
                 "a command is required to execute ego",
                 None,
             );
-            std::process::exit(1); // to avoid types error
-        };
+            Err(1)
+        }
     }
     fn cmd_from_str(command: &str, args: Vec<String>) -> Command {
         match command {
@@ -42,6 +54,9 @@ impl Command {
             "logo" => Command::Logo(Logo::new(args)),
             "new" => Command::New(New::new(args)),
             "compile" => Command::Compile(Compile::new(args)),
+            "exec" => Command::Exec(Exec::new(args)),
+            "check" => Command::Check(Check::new(args)),
+            "explain" => Command::Explain(Explain::new(args)),
             _ => Command::Run(Run::new(
                 [command.to_string()]
                     .into_iter()
@@ -50,12 +65,16 @@ impl Command {
             )), // if unknown command, assumes it's a .ego file
         }
     }
-    pub fn exec(&self) {
+    // the error is the exit code of the process
+    pub fn exec(&self) -> Result<(), i32> {
         match self {
             Command::Run(v) => v.exec(),
             Command::Logo(v) => v.exec(),
             Command::New(v) => v.exec(),
             Command::Compile(v) => v.exec(),
+            Command::Exec(v) => v.exec(),
+            Command::Check(v) => v.exec(),
+            Command::Explain(v) => v.exec(),
         }
     }
 }
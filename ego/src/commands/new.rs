@@ -12,7 +12,7 @@ impl New {
     pub fn new(args: Vec<String>) -> New {
         New { args }
     }
-    pub fn exec(&self) {
+    pub fn exec(&self) -> Result<(), i32> {
         println!("\n ◔ Creating new ego project");
 
         let mut path = PathBuf::from(self.args[0].clone());
@@ -25,7 +25,7 @@ impl New {
                         Ok(_) => {}
                         Err(_) => {
                             println!(" ⅹ Failed to create main.ego file");
-                            std::process::exit(1);
+                            return Err(1);
                         }
                     }
                 }
@@ -44,20 +44,20 @@ impl New {
                                 );
                                 if let Err(e) = file.write_all(config_data.as_bytes()) {
                                     println!(" ⅹ Failed to write to ego.toml: {}", e);
-                                    std::process::exit(1);
+                                    return Err(1);
                                 }
                             } else {
                                 println!(" ⅹ Failed to retrieve directory name for config file");
-                                std::process::exit(1);
+                                return Err(1);
                             }
                         } else {
                             println!(" ⅹ Failed to retrieve parent directory for config file");
-                            std::process::exit(1);
+                            return Err(1);
                         }
                     }
                     Err(_) => {
                         println!(" ⅹ Failed to create ego.toml");
-                        std::process::exit(1);
+                        return Err(1);
                     }
                 }
 
@@ -65,5 +65,6 @@ impl New {
             }
             Err(_) => println!(" ⅹ Failed to create new ego project"),
         }
+        Ok(())
     }
 }
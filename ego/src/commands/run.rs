@@ -7,6 +7,7 @@ use crate::ast::Module;
 use crate::compiler::Compiler;
 use crate::core::error;
 use crate::core::error::ErrorType;
+use self_vm::Renderer;
 
 pub struct Run {
     args: Vec<String>,
@@ -19,23 +20,27 @@ impl Run {
     pub fn debug(&self) -> bool {
         self.args.contains(&"-d".to_string())
     }
-    pub fn exec(&self) {
+    pub fn exec(&self) -> Result<(), i32> {
         let module_name = if self.args.len() > 0 {
             self.args[0].clone()
         } else {
             "main.ego".to_string() // default lookup on a ego project
         };
 
-        let file_content = fs::read_to_string(&module_name).unwrap_or_else(|_| {
+        let file_content = fs::read_to_string(&module_name).map_err(|_| {
             error::throw(
                 ErrorType::FatalError,
                 format!("Cannot read {}\n", module_name).as_str(),
                 None,
             );
-            std::process::exit(1); // to avoid types error
-        });
+            1
+        })?;
+        let renderer = Renderer::from_args(&self.args);
 
-        let tokens = lex(file_content);
+        let tokens = lex(file_content.clone()).map_err(|diagnostics| {
+            error::report(&diagnostics, &module_name, &file_content, &renderer);
+            1
+        })?;
         if self.debug() {
             println!("\nLexer tokens: \n-------------");
             for (i, token) in tokens.iter().enumerate() {
@@ -43,15 +48,21 @@ impl Run {
             }
         }
 
-        let mut module = Module::new(module_name, tokens);
-        let ast = module.parse();
+        let mut module = Module::new(module_name.clone(), tokens);
+        let ast = module.parse().map_err(|diagnostics| {
+            error::report(&diagnostics, &module_name, &file_content, &renderer);
+            1
+        })?;
         if self.debug() {
             println!("\nAst nodes: \n---------------\n{:#?}", ast);
         }
 
         if self.args.contains(&"--bytes".to_string()) {
-            let mut compiler = Compiler::new(ast);
-            let bytecode = compiler.gen_bytecode();
+            let mut compiler = Compiler::new(ast, file_content.clone());
+            let bytecode = compiler.gen_bytecode().map_err(|diagnostics| {
+                error::report(&diagnostics, &module_name, &file_content, &renderer);
+                1
+            })?;
             let bytecode_string: String = bytecode
                 .iter()
                 .map(|byte| format!("{:02X}", byte))
@@ -62,21 +73,33 @@ impl Run {
                 Ok(file) => file,
                 Err(_) => {
                     error::throw(ErrorType::SyntaxError, "Cannot write file", None);
-                    unreachable!()
+                    return Err(1);
                 }
             };
             match file.write_all(&bytecode) {
                 Ok(_) => println!("Bytes saved in bytecode.bin\n{}", bytecode_string),
                 Err(_) => {
                     error::throw(ErrorType::SyntaxError, "Cannot write file", None);
-                    unreachable!()
+                    return Err(1);
                 }
             };
         } else {
-            let mut compiler = Compiler::new(ast);
-            let bytecode = compiler.gen_bytecode();
-            let mut vm = self_vm::new(bytecode);
-            vm.run(&self.args);
+            let mut compiler = Compiler::new(ast, file_content.clone());
+            let bytecode = compiler.gen_bytecode().map_err(|diagnostics| {
+                error::report(&diagnostics, &module_name, &file_content, &renderer);
+                1
+            })?;
+            let mut vm = self_vm::new_with_line_table(bytecode, compiler.line_table());
+            vm.set_debug(self.debug());
+            let execution = vm.run(&self.args);
+            if let Some(traceback) = execution.traceback {
+                eprintln!("{traceback}");
+            }
+            if let Some(err) = execution.error {
+                eprintln!("{}", renderer.render(&err.report()));
+                return Err(1);
+            }
         }
+        Ok(())
     }
 }
@@ -0,0 +1,639 @@
+/*
+    CHECKS THE NAMES OF A MODULE BEFORE COMPILING IT: EVERY
+    IDENTIFIER MUST BE DECLARED BEFORE IT'S USED AND ONLY
+    ONCE ON EACH BLOCK. A NAME DECLARED INSIDE A BLOCK IS
+    ONLY VISIBLE UNTIL THE BLOCK ENDS
+*/
+
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    ast::{
+        assignament_statement::{AssignamentNode, VarType},
+        block::Block,
+        function_declaration::FunctionDeclaration,
+        identifier::Identifier,
+        import_statement::{ImportStatement, ModuleType},
+        member_expression::MemberExpression,
+        module::ModuleAst,
+        structs::StructTypeExpr,
+        AstNodeType, Expression,
+    },
+    core::error::{Diagnostic, ErrorType},
+};
+
+use super::resolver::import_name;
+
+// callees compiled to their own instructions instead of
+// being loaded by name
+const INTRINSICS: [&str; 3] = ["print", "println", "call"];
+
+#[derive(Debug, Clone, Copy)]
+struct Position {
+    line: usize,
+    column: usize,
+}
+
+// the module or a function
+struct Scope {
+    // every name the body declares, wherever it is. nested
+    // functions see them no matter the order
+    declared: HashMap<String, Position>,
+    // blocks open on the body, the body itself first and the
+    // innermost last
+    blocks: Vec<BlockNames>,
+    // names declared by blocks that already ended
+    ended: HashMap<String, Position>,
+    // imported native modules and the members they export
+    modules: HashMap<String, Vec<String>>,
+}
+
+impl Scope {
+    fn new(declared: HashMap<String, Position>) -> Scope {
+        Scope {
+            declared,
+            blocks: vec![BlockNames::default()],
+            ended: HashMap::new(),
+            modules: HashMap::new(),
+        }
+    }
+
+    // true if the declaration of the name was already checked
+    // on one of the open blocks
+    fn is_defined(&self, name: &str) -> bool {
+        self.blocks.iter().any(|block| block.defined.contains(name))
+    }
+}
+
+#[derive(Default)]
+struct BlockNames {
+    // declared with let, fn, struct or import, to find the
+    // duplicated ones
+    declared: HashMap<String, Position>,
+    // the declared ones and the ones assigned without let
+    defined: HashSet<String>,
+}
+
+pub struct Analyzer {
+    // the module first, innermost function last
+    scopes: Vec<Scope>,
+    // functions declared by the impl blocks of each struct
+    methods: HashMap<String, HashMap<String, Position>>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Analyzer {
+    pub fn new() -> Analyzer {
+        Analyzer {
+            scopes: vec![],
+            methods: HashMap::new(),
+            diagnostics: vec![],
+        }
+    }
+
+    pub fn analyze(mut self, module: &ModuleAst) -> Result<(), Vec<Diagnostic>> {
+        let mut declarations = vec![];
+        collect_declarations(&module.children, &mut declarations);
+        let mut declared = HashMap::new();
+        for declaration in declarations {
+            declared
+                .entry(declaration.name)
+                .or_insert(declaration.position);
+        }
+
+        self.scopes.push(Scope::new(declared));
+        self.check_nodes(&module.children);
+        self.scopes.pop();
+
+        if self.diagnostics.is_empty() {
+            Ok(())
+        } else {
+            Err(self.diagnostics)
+        }
+    }
+
+    fn scope(&mut self) -> &mut Scope {
+        self.scopes
+            .last_mut()
+            .expect("the module scope is always open")
+    }
+
+    // true if one of the first `scopes` scopes declares the name
+    fn is_declared_on(&self, scopes: usize, name: &str) -> bool {
+        self.scopes[..scopes]
+            .iter()
+            .any(|scope| scope.declared.contains_key(name))
+    }
+
+    fn check_nodes(&mut self, nodes: &[AstNodeType]) {
+        for node in nodes {
+            self.check_node(node);
+        }
+    }
+
+    fn check_node(&mut self, node: &AstNodeType) {
+        match node {
+            AstNodeType::AssignamentStatement(node) => self.check_assignament(node),
+            AstNodeType::FunctionDeclaration(node) => {
+                self.declare(&node.identifier);
+                self.check_function(node);
+            }
+            AstNodeType::Struct(node) => self.declare(&node.identifier),
+            AstNodeType::ImplDeclaration(node) => {
+                self.reference(&node.identifier, "struct");
+                for method in &node.methods {
+                    self.declare_method(&node.identifier.name, &method.identifier);
+                    self.check_function(method);
+                }
+            }
+            AstNodeType::ImportStatement(node) => self.check_import(node),
+            AstNodeType::ExportStatement(node) => {
+                // exports are read once the module has run, so the
+                // name can be declared after them
+                if let Expression::Identifier(identifier) = &node.value {
+                    if !self.scopes[0].declared.contains_key(&identifier.name) {
+                        self.undeclared(identifier, None);
+                    }
+                }
+            }
+            AstNodeType::IfStatement(node) => {
+                self.check_expression(&node.condition);
+                self.check_block(&node.body, None);
+                if let Some(else_node) = &node.else_node {
+                    self.check_block(&else_node.body, None);
+                }
+            }
+            AstNodeType::WhileStatement(node) => {
+                self.check_expression(&node.condition);
+                self.check_block(&node.body, None);
+            }
+            AstNodeType::TryStatement(node) => {
+                self.check_block(&node.body, None);
+                self.check_block(&node.catch_body, Some(&node.error_identifier));
+            }
+            AstNodeType::ReturnStatement(node) => self.check_expression(&node.value),
+            AstNodeType::ThrowStatement(node) => self.check_expression(&node.value),
+            AstNodeType::IndexAssignamentStatement(node) => {
+                self.check_expression(&node.target.object);
+                self.check_expression(&node.target.index);
+                self.check_expression(&node.init);
+            }
+            AstNodeType::PropertyAssignamentStatement(node) => {
+                self.check_expression(&node.target.object);
+                self.check_expression(&node.init);
+            }
+            AstNodeType::Expression(node) => self.check_expression(node),
+            AstNodeType::Block(node) => self.check_block(node, None),
+            _ => {}
+        }
+    }
+
+    // binding: the catch identifier, declared on the catch block
+    fn check_block(&mut self, node: &Block, binding: Option<&Identifier>) {
+        self.scope().blocks.push(BlockNames::default());
+        if let Some(identifier) = binding {
+            self.declare(identifier);
+        }
+        self.check_nodes(&node.children);
+
+        let scope = self.scope();
+        if let Some(block) = scope.blocks.pop() {
+            for (name, position) in block.declared {
+                scope.ended.entry(name).or_insert(position);
+            }
+        }
+    }
+
+    fn check_assignament(&mut self, node: &AssignamentNode) {
+        self.check_expression(&node.init);
+
+        match node.var_type {
+            VarType::Let | VarType::Const => self.declare(&node.identifier),
+            // assigning a name nobody declared declares it on the
+            // current scope, as the vm does
+            VarType::None => {
+                let name = node.identifier.name.clone();
+                if !self.is_declared_on(self.scopes.len() - 1, &name) {
+                    let scope = self.scope();
+                    if !scope.is_defined(&name) {
+                        if let Some(block) = scope.blocks.last_mut() {
+                            block.defined.insert(name);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn check_function(&mut self, node: &FunctionDeclaration) {
+        let parameters: Vec<&Identifier> = node
+            .parameters
+            .children
+            .iter()
+            .filter_map(|parameter| match parameter {
+                Some(Expression::Identifier(identifier)) => Some(identifier),
+                _ => None,
+            })
+            .collect();
+
+        let mut declared = HashMap::new();
+        for parameter in &parameters {
+            declared
+                .entry(parameter.name.clone())
+                .or_insert(position(parameter));
+        }
+        let mut declarations = vec![];
+        collect_declarations(&node.body.children, &mut declarations);
+        for declaration in declarations {
+            // assigning a name of an enclosing scope changes it
+            // instead of declaring a local
+            if declaration.assignment && self.is_declared_on(self.scopes.len(), &declaration.name) {
+                continue;
+            }
+            declared
+                .entry(declaration.name)
+                .or_insert(declaration.position);
+        }
+
+        self.scopes.push(Scope::new(declared));
+        for parameter in parameters {
+            self.declare(parameter);
+        }
+        self.check_nodes(&node.body.children);
+        self.scopes.pop();
+    }
+
+    fn check_import(&mut self, node: &ImportStatement) {
+        let name = import_name(node);
+        if let ModuleType::Native = node.module_type {
+            match self_vm::native_module_members(&node.module[0]) {
+                Some(members) => {
+                    self.scope().modules.insert(name.clone(), members);
+                }
+                None => self.diagnostics.push(
+                    Diagnostic::new(
+                        ErrorType::ReferenceError,
+                        format!("there is no native module '{}'", node.module[0]).as_str(),
+                        node.line,
+                        node.at,
+                    )
+                    .with_help(
+                        "custom modules are imported by their path, like import \"./utils\"",
+                    ),
+                ),
+            }
+        }
+
+        self.declare(&Identifier::new(name, node.at, node.line));
+    }
+
+    fn check_expression(&mut self, node: &Expression) {
+        match node {
+            Expression::Identifier(identifier) => {
+                self.reference(identifier, "let");
+            }
+            Expression::BinaryExpression(node) => {
+                self.check_expression(&node.left);
+                self.check_expression(&node.right);
+            }
+            Expression::UnaryExpression(node) => self.check_expression(&node.operand),
+            Expression::CallExpression(node) => {
+                match node.callee.as_ref() {
+                    Expression::Identifier(identifier)
+                        if INTRINSICS.contains(&identifier.name.as_str()) => {}
+                    callee => self.check_expression(callee),
+                }
+                for argument in node.arguments.children.iter().flatten() {
+                    self.check_expression(argument);
+                }
+            }
+            Expression::StructLiteral(node) => {
+                match &node.identifier {
+                    StructTypeExpr::Identifier(identifier) => {
+                        self.reference(identifier, "struct");
+                    }
+                    StructTypeExpr::MemberExpression(member) => self.check_member(member),
+                }
+                for (_, value) in &node.fields.fields {
+                    self.check_expression(value);
+                }
+            }
+            Expression::ObjectLiteral(node) => {
+                for (_, value) in &node.fields {
+                    self.check_expression(value);
+                }
+            }
+            Expression::MemberExpression(node) => self.check_member(node),
+            Expression::IndexExpression(node) => {
+                self.check_expression(&node.object);
+                self.check_expression(&node.index);
+            }
+            Expression::Vector(node) => {
+                for child in &node.children {
+                    self.check_expression(child);
+                }
+            }
+            Expression::MapLiteral(node) => {
+                for (_, value) in &node.entries {
+                    self.check_expression(value);
+                }
+            }
+            Expression::StringLiteral(_)
+            | Expression::Number(_)
+            | Expression::Bool(_)
+            | Expression::Nothing(_) => {}
+        }
+    }
+
+    // members of native modules are known, the ones of structs
+    // and custom modules only once the program runs
+    fn check_member(&mut self, node: &MemberExpression) {
+        let Expression::Identifier(object) = node.object.as_ref() else {
+            self.check_expression(&node.object);
+            return;
+        };
+        let Some(scope) = self.reference(object, "let") else {
+            return;
+        };
+        let Some(members) = self.scopes[scope].modules.get(&object.name) else {
+            return;
+        };
+
+        if !members.contains(&node.property.name) {
+            let diagnostic = Diagnostic::new(
+                ErrorType::ReferenceError,
+                format!(
+                    "'{}' is not a member of the '{}' module",
+                    node.property.name, object.name
+                )
+                .as_str(),
+                node.property.line,
+                node.property.at,
+            )
+            .with_help(
+                format!(
+                    "the members of '{}' are {}",
+                    object.name,
+                    members.join(", ")
+                )
+                .as_str(),
+            );
+            self.diagnostics.push(diagnostic);
+        }
+    }
+
+    // index of the scope the name is found on, None if it's not
+    // declared there yet. keyword: the one declaring this kind
+    // of name, suggested when it's not declared
+    fn reference(&mut self, identifier: &Identifier, keyword: &str) -> Option<usize> {
+        let name = &identifier.name;
+        let current = self.scopes.len() - 1;
+        if self.scopes[current].is_defined(name) {
+            return Some(current);
+        }
+        if let Some(declaration) = self.scopes[current].ended.get(name).copied() {
+            let diagnostic = Diagnostic::new(
+                ErrorType::ReferenceError,
+                format!("'{}' is only visible inside the block declaring it", name).as_str(),
+                identifier.line,
+                identifier.at,
+            )
+            .with_label(declaration.line, declaration.column, "declared here")
+            .with_help("declare it before the block to use it after it");
+            self.diagnostics.push(diagnostic);
+            return None;
+        }
+        if let Some(declaration) = self.scopes[current].declared.get(name).copied() {
+            let diagnostic = Diagnostic::new(
+                ErrorType::ReferenceError,
+                format!("'{}' is used before its declaration", name).as_str(),
+                identifier.line,
+                identifier.at,
+            )
+            .with_label(declaration.line, declaration.column, "declared here");
+            self.diagnostics.push(diagnostic);
+            return None;
+        }
+        if let Some(scope) = (0..current)
+            .rev()
+            .find(|scope| self.scopes[*scope].declared.contains_key(name))
+        {
+            return Some(scope);
+        }
+
+        self.undeclared(identifier, Some(keyword));
+        None
+    }
+
+    fn undeclared(&mut self, identifier: &Identifier, keyword: Option<&str>) {
+        let mut diagnostic = Diagnostic::new(
+            ErrorType::ReferenceError,
+            format!("'{}' is not declared", identifier.name).as_str(),
+            identifier.line,
+            identifier.at,
+        );
+        if let Some(keyword) = keyword {
+            diagnostic = diagnostic
+                .with_help(format!("declare it with '{keyword}' before using it").as_str());
+        }
+        self.diagnostics.push(diagnostic);
+    }
+
+    // the same name can't be declared twice on a block or on the
+    // blocks it's nested in. blocks don't get their own slots, so
+    // the inner declaration would overwrite the outer one
+    fn declare(&mut self, identifier: &Identifier) {
+        let name = identifier.name.clone();
+        let first = self
+            .scope()
+            .blocks
+            .iter()
+            .find_map(|block| block.declared.get(&name).copied());
+        if let Some(first) = first {
+            self.duplicated(identifier, first);
+            return;
+        }
+
+        if let Some(block) = self.scope().blocks.last_mut() {
+            block.declared.insert(name.clone(), position(identifier));
+            block.defined.insert(name);
+        }
+    }
+
+    // a struct can't have two functions with the same name, even
+    // on different impl blocks. the last one would replace the rest
+    fn declare_method(&mut self, struct_name: &str, identifier: &Identifier) {
+        let methods = self.methods.entry(struct_name.to_string()).or_default();
+        match methods.get(&identifier.name).copied() {
+            Some(first) => self.duplicated(identifier, first),
+            None => {
+                methods.insert(identifier.name.clone(), position(identifier));
+            }
+        }
+    }
+
+    fn duplicated(&mut self, identifier: &Identifier, first: Position) {
+        let diagnostic = Diagnostic::new(
+            ErrorType::DuplicateDeclaration,
+            format!("'{}' is already declared", identifier.name).as_str(),
+            identifier.line,
+            identifier.at,
+        )
+        .with_label(first.line, first.column, "first declared here");
+        self.diagnostics.push(diagnostic);
+    }
+}
+
+struct Declaration {
+    name: String,
+    position: Position,
+    // declared by assigning it without let
+    assignment: bool,
+}
+
+impl Declaration {
+    fn new(identifier: &Identifier, assignment: bool) -> Declaration {
+        Declaration {
+            name: identifier.name.clone(),
+            position: position(identifier),
+            assignment,
+        }
+    }
+}
+
+fn position(identifier: &Identifier) -> Position {
+    Position {
+        line: identifier.line,
+        column: identifier.at,
+    }
+}
+
+// names declared by the nodes, looking into nested blocks
+// but not into the functions declared on them
+fn collect_declarations(nodes: &[AstNodeType], declarations: &mut Vec<Declaration>) {
+    for node in nodes {
+        match node {
+            AstNodeType::AssignamentStatement(node) => declarations.push(Declaration::new(
+                &node.identifier,
+                matches!(node.var_type, VarType::None),
+            )),
+            AstNodeType::FunctionDeclaration(node) => {
+                declarations.push(Declaration::new(&node.identifier, false))
+            }
+            AstNodeType::Struct(node) => {
+                declarations.push(Declaration::new(&node.identifier, false))
+            }
+            AstNodeType::ImportStatement(node) => declarations.push(Declaration::new(
+                &Identifier::new(import_name(node), node.at, node.line),
+                false,
+            )),
+            AstNodeType::IfStatement(node) => {
+                collect_declarations(&node.body.children, declarations);
+                if let Some(else_node) = &node.else_node {
+                    collect_declarations(&else_node.body.children, declarations);
+                }
+            }
+            AstNodeType::WhileStatement(node) => {
+                collect_declarations(&node.body.children, declarations);
+            }
+            AstNodeType::TryStatement(node) => {
+                collect_declarations(&node.body.children, declarations);
+                declarations.push(Declaration::new(&node.error_identifier, false));
+                collect_declarations(&node.catch_body.children, declarations);
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::{lex, Module};
+
+    fn analyze(source: &str) -> Vec<Diagnostic> {
+        let tokens = lex(source.to_string()).expect("the source lexes");
+        let ast = Module::new("main.ego".to_string(), tokens)
+            .parse()
+            .expect("the source parses");
+        match Analyzer::new().analyze(&ast) {
+            Ok(()) => vec![],
+            Err(diagnostics) => diagnostics,
+        }
+    }
+
+    fn messages(source: &str) -> Vec<String> {
+        analyze(source)
+            .into_iter()
+            .map(|diagnostic| diagnostic.message)
+            .collect()
+    }
+
+    #[test]
+    fn undeclared_names_are_reported() {
+        assert_eq!(messages("println(x)"), vec!["'x' is not declared"]);
+        assert_eq!(
+            messages("println(x)\nlet x = 1"),
+            vec!["'x' is used before its declaration"]
+        );
+    }
+
+    #[test]
+    fn duplicated_names_on_a_block_are_reported() {
+        let diagnostics = analyze("let v = 1\nlet v = 2");
+        assert_eq!(diagnostics.len(), 1);
+        assert!(matches!(
+            diagnostics[0].kind,
+            ErrorType::DuplicateDeclaration
+        ));
+    }
+
+    #[test]
+    fn nested_blocks_cant_declare_a_name_again() {
+        assert_eq!(
+            messages("let v = 1\nif true {\n  let v = 2\n}"),
+            vec!["'v' is already declared"]
+        );
+        assert_eq!(
+            messages("fn f() {\n  let v = 1\n  while true {\n    let v = 2\n  }\n}"),
+            vec!["'v' is already declared"]
+        );
+        // sibling blocks don't see each other names
+        assert!(messages("if true {\n  let v = 1\n} else {\n  let v = 2\n}").is_empty());
+    }
+
+    #[test]
+    fn duplicated_methods_are_reported() {
+        let point = "struct P {\n  x: number\n}\n";
+        let get = "impl P {\n  fn get(self) {\n    return 1\n  }\n}\n";
+        assert!(messages(&format!("{point}{get}")).is_empty());
+        // on the same impl and on another impl of the struct
+        let new = "  fn new(x) {\n    return 1\n  }\n";
+        assert_eq!(
+            messages(&format!("{point}impl P {{\n{new}{new}}}")),
+            vec!["'new' is already declared"]
+        );
+        assert_eq!(
+            messages(&format!("{point}{get}{get}")),
+            vec!["'get' is already declared"]
+        );
+    }
+
+    #[test]
+    fn catch_identifier_only_exists_inside_its_block() {
+        assert!(messages("try {\n} catch err {\n  println(err)\n}").is_empty());
+        assert_eq!(
+            messages("try {\n} catch err {\n}\nprintln(err)"),
+            vec!["'err' is only visible inside the block declaring it"]
+        );
+    }
+
+    #[test]
+    fn block_names_are_not_visible_after_the_block() {
+        assert_eq!(
+            messages("while false {\n  let j = 1\n}\nprintln(j)"),
+            vec!["'j' is only visible inside the block declaring it"]
+        );
+        assert!(messages("let j = 0\nwhile false {\n  j = 1\n}\nprintln(j)").is_empty());
+    }
+}
@@ -2,8 +2,6 @@ use std::collections::HashMap;
 
 use self_vm::get_codes_map;
 
-use crate::core::error::{self, ErrorType};
-
 pub struct Bytecode {
     table: HashMap<String, u8>,
 }
@@ -20,14 +18,8 @@ impl Bytecode {
     }
 }
 
-pub fn get_bytecode(item: String) -> u8 {
+// None for an instruction the vm doesn't know
+pub fn get_bytecode(item: &str) -> Option<u8> {
     let mut bytecode_handler = Bytecode::get_handler();
-
-    let error_msg = format!("instruction not recognized: {}", item);
-    if let Some(bytecode) = bytecode_handler.get_bytecode_representation(item) {
-        bytecode
-    } else {
-        error::throw(ErrorType::CompilationError, &error_msg, None);
-        std::process::exit(1)
-    }
+    bytecode_handler.get_bytecode_representation(item.to_string())
 }
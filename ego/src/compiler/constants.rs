@@ -0,0 +1,40 @@
+use std::collections::HashMap;
+
+// strings used by a module: identifiers, property names and
+// string literals. each one is stored once on the constants
+// section that starts the module bytecode, and instructions
+// reference it by index
+#[derive(Default)]
+pub struct ConstantPool {
+    strings: Vec<String>,
+    indexes: HashMap<String, u32>,
+}
+
+impl ConstantPool {
+    pub fn new() -> ConstantPool {
+        ConstantPool::default()
+    }
+
+    pub fn add(&mut self, value: &str) -> u32 {
+        if let Some(index) = self.indexes.get(value) {
+            return *index;
+        }
+
+        let index = self.strings.len() as u32;
+        self.strings.push(value.to_string());
+        self.indexes.insert(value.to_string(), index);
+        index
+    }
+
+    // [u32 count] followed by each string as [u32 length][bytes]
+    pub fn to_bytecode(&self) -> Vec<u8> {
+        let mut bytecode = vec![];
+        bytecode.extend_from_slice(&(self.strings.len() as u32).to_le_bytes());
+        for string in &self.strings {
+            bytecode.extend_from_slice(&(string.len() as u32).to_le_bytes());
+            bytecode.extend_from_slice(string.as_bytes());
+        }
+
+        bytecode
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{
     ast::{call_expression::CallExpression, Expression},
-    compiler::{bytecode::get_bytecode, Compiler},
+    compiler::Compiler,
 };
 
 use self_vm::utils::{
@@ -8,20 +8,20 @@ use self_vm::utils::{
     Number,
 };
 
-pub fn call_as_bytecode(node: &CallExpression) -> Vec<u8> {
+pub fn call_as_bytecode(compiler: &mut Compiler, node: &CallExpression) -> Vec<u8> {
     let mut bytecode = vec![];
 
     // load arguments
     for argument in &node.arguments.children {
         if let Some(arg) = argument {
-            bytecode.extend_from_slice(&Compiler::compile_expression(&arg, false))
+            bytecode.extend_from_slice(&compiler.compile_expression(&arg, false))
         } else {
             // push nothing to bytecode
         }
     }
 
     // call instruction bytecode
-    let call_bytecode = get_bytecode("ffi_call".to_string());
+    let call_bytecode = compiler.opcode("ffi_call");
     bytecode.push(call_bytecode);
 
     // number of args bytecode
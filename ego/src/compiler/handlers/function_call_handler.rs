@@ -1,40 +1,25 @@
-use crate::{
-    ast::{call_expression::CallExpression, string_literal::StringLiteral, Expression},
-    compiler::{self, bytecode::get_bytecode, Compiler},
-};
+use crate::{ast::call_expression::CallExpression, compiler::Compiler};
 
 use self_vm::utils::{to_bytes::bytes_from_32, Number};
 
-pub fn function_call_as_bytecode(node: &CallExpression, drop_value: bool) -> Vec<u8> {
+pub fn function_call_as_bytecode(
+    compiler: &mut Compiler,
+    node: &CallExpression,
+    drop_value: bool,
+) -> Vec<u8> {
     let mut bytecode = vec![];
 
-    // callee
-    let identifier_bytecode = match node.callee.as_ref() {
-        Expression::MemberExpression(x) => {
-            Compiler::compile_expression(&Expression::MemberExpression(x.clone()), false)
-        }
-        Expression::Identifier(x) => Compiler::compile_expression(
-            &Expression::StringLiteral(StringLiteral::new(
-                node.get_callee(),
-                node.get_callee(),
-                0,
-                0,
-            )),
-            false,
-        ),
-        _ => {
-            // TODO: use self-vm errors system
-            panic!("compilation error: invalid callee for a function call")
-        }
-    };
-    bytecode.extend_from_slice(&identifier_bytecode);
+    // callee, any expression evaluating to a function: f,
+    // p.method, fns[0] or make()
+    let callee_bytecode = compiler.compile_expression(node.callee.as_ref(), false);
+    bytecode.extend_from_slice(&callee_bytecode);
 
     // load arguments
-    let (args_len, args) = compiler::Compiler::compile_group(&node.arguments);
+    let (args_len, args) = compiler.compile_group(&node.arguments);
     bytecode.extend_from_slice(&args);
 
     // instruction bytecode
-    let opcode_bytecode = get_bytecode("call".to_string());
+    let opcode_bytecode = compiler.opcode("call");
     bytecode.push(opcode_bytecode);
 
     // number of args bytecode
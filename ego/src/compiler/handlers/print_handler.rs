@@ -2,7 +2,7 @@ use std::collections::btree_map;
 
 use crate::{
     ast::{call_expression::CallExpression, Expression},
-    compiler::{self, bytecode::get_bytecode, Compiler},
+    compiler::Compiler,
 };
 
 use self_vm::utils::{
@@ -10,15 +10,15 @@ use self_vm::utils::{
     Number,
 };
 
-pub fn print_as_bytecode(node: &CallExpression) -> Vec<u8> {
+pub fn print_as_bytecode(compiler: &mut Compiler, node: &CallExpression, opcode: &str) -> Vec<u8> {
     let mut bytecode = vec![];
 
     // load arguments
-    let (args_len, args) = compiler::Compiler::compile_group(&node.arguments);
+    let (args_len, args) = compiler.compile_group(&node.arguments);
     bytecode.extend_from_slice(&args);
 
     // print instruction bytecode
-    let print_bytecode = get_bytecode(node.get_callee());
+    let print_bytecode = compiler.opcode(opcode);
     bytecode.push(print_bytecode);
 
     // number of args bytecode
@@ -1,11 +1,20 @@
+mod analyzer;
 mod bytecode;
+mod constants;
 mod handlers;
+mod resolver;
 
 use std::fs;
 
+use crate::ast::binary_expression::BinaryExpression;
+use crate::ast::bool::Bool;
+use crate::ast::break_statement::BreakStatement;
+use crate::ast::continue_statement::ContinueStatement;
 use crate::ast::export_statement::ExportStatement;
 use crate::ast::return_statement::ReturnStatement;
 use crate::ast::structs::StructTypeExpr;
+use crate::ast::throw_statement::ThrowStatement;
+use crate::ast::try_statement::TryStatement;
 use crate::ast::{lex, Module};
 use crate::{
     ast::{
@@ -13,13 +22,17 @@ use crate::{
         objects::ObjectLiteral,
         string_literal::StringLiteral,
     },
-    core::error::{self, ErrorType},
+    core::error::{Diagnostic, ErrorType},
 };
+use analyzer::Analyzer;
 use bytecode::get_bytecode;
+use constants::ConstantPool;
+use resolver::{Resolver, Variable};
 use self_vm::utils::{
     to_bytes::{bytes_from_32, bytes_from_64, bytes_from_float},
     Number,
 };
+use self_vm::LineTable;
 
 use crate::ast::{
     assignament_statement::{AssignamentNode, VarType},
@@ -27,67 +40,196 @@ use crate::ast::{
     function_declaration::FunctionDeclaration,
     group::Group,
     if_statement::IfStatement,
+    impl_declaration::ImplDeclaration,
+    index_assignament_statement::IndexAssignamentNode,
     module::ModuleAst,
     objects::ObjectType,
+    property_assignament_statement::PropertyAssignamentNode,
     structs::Struct,
     while_statement::WhileStatement,
     AstNodeType, Expression, Type,
 };
 
-pub fn gen_bytecode(modulename: String, code: String, args: &Vec<String>) -> Vec<u8> {
+pub fn gen_bytecode(
+    modulename: String,
+    code: String,
+    args: &Vec<String>,
+) -> Result<(Vec<u8>, LineTable), Vec<Diagnostic>> {
     let debug = args.contains(&"-d".to_string());
-    let tokens = lex(code);
+    let tokens = lex(code.clone())?;
     if debug {
         println!("\n--- TOKEN ----------\n");
         println!("{:#?}", tokens);
     }
-    let mut module = Module::new(modulename, tokens);
-    let ast = module.parse();
+    let mut module = Module::new(format!("{}.ego", modulename), tokens);
+    let ast = module.parse()?;
     if debug {
         println!("\n--- AST ----------\n");
         println!("{:#?}", ast);
     }
-    let mut compiler = Compiler::new(ast);
-    compiler.gen_bytecode()
+    let mut compiler = Compiler::new(ast, code);
+    let bytecode = compiler.gen_bytecode()?;
+    Ok((bytecode, compiler.line_table()))
 }
 
 pub struct Compiler {
     ast: ModuleAst,
     bytecode: Vec<u8>,
+    loops: Vec<LoopContext>,
+    // number of try blocks the compiled code is nested in
+    try_depth: usize,
+    line_table: LineTable,
+    // index of the compiled module on the line table files
+    file: usize,
+    resolver: Resolver,
+    constants: ConstantPool,
+    // problems found while compiling. the compilation goes on
+    // after them, but its bytecode is discarded
+    diagnostics: Vec<Diagnostic>,
+}
+
+// a while loop being compiled. break jumps can't be resolved
+// until the loop body length is known, so their positions
+// are saved to be patched once the loop is fully compiled
+struct LoopContext {
+    start: usize,
+    breaks: Vec<usize>,
+    // try depth at the loop start. break and continue must
+    // leave the try blocks opened inside the loop
+    try_depth: usize,
 }
 
 impl Compiler {
-    pub fn new(ast: ModuleAst) -> Compiler {
+    pub fn new(ast: ModuleAst, source: String) -> Compiler {
+        let mut line_table = LineTable::new();
+        let file = line_table.add_file(ast.module_name.clone(), source);
+        let resolver = Resolver::new(&ast.children);
         Compiler {
             ast,
             bytecode: vec![],
+            loops: vec![],
+            try_depth: 0,
+            line_table,
+            file,
+            resolver,
+            constants: ConstantPool::new(),
+            diagnostics: vec![],
         }
     }
 
-    pub fn gen_bytecode(&mut self) -> Vec<u8> {
+    // positions of the generated bytecode on the ego source
+    pub fn line_table(&self) -> LineTable {
+        self.line_table.clone()
+    }
+
+    // names exported by the module
+    pub fn exports(&self) -> Vec<String> {
+        let mut exports = vec![];
+        for node in &self.ast.children {
+            if let AstNodeType::ExportStatement(ExportStatement {
+                value: Expression::Identifier(identifier),
+                ..
+            }) = node
+            {
+                exports.push(identifier.name.clone());
+            }
+        }
+        exports
+    }
+
+    // modules imported by the module, as written on the imports
+    pub fn imports(&self) -> Vec<String> {
+        let mut imports = vec![];
+        for node in &self.ast.children {
+            if let AstNodeType::ImportStatement(import) = node {
+                imports.push(import.module[0].clone());
+            }
+        }
+        imports
+    }
+
+    pub fn gen_bytecode(&mut self) -> Result<Vec<u8>, Vec<Diagnostic>> {
+        // names are checked before emitting anything, the
+        // bytecode of a module using undeclared ones is useless
+        Analyzer::new().analyze(&self.ast)?;
+
         let mut counter = 0;
         while counter < self.ast.children.len() {
-            let node_bytecode = Compiler::gen_node_bytecode(&self.ast.children[counter]);
+            let node = self.ast.children[counter].clone();
+            let node_bytecode = self.gen_node_bytecode(&node, self.bytecode.len());
             self.bytecode.extend_from_slice(&node_bytecode);
             counter += 1;
         }
 
-        self.bytecode.clone()
+        // the constants section goes first, so the positions
+        // of the code are shifted by its length
+        let mut bytecode = self.constants.to_bytecode();
+        let mut line_table = LineTable::new();
+        line_table.extend(std::mem::take(&mut self.line_table), bytecode.len());
+        self.line_table = line_table;
+        bytecode.extend_from_slice(&self.bytecode);
+
+        if self.diagnostics.is_empty() {
+            Ok(bytecode)
+        } else {
+            Err(std::mem::take(&mut self.diagnostics))
+        }
+    }
+
+    // opcode of an instruction. the names are written by the
+    // compiler itself, an unknown one is reported on the
+    // statement being compiled
+    fn opcode(&mut self, instruction: &str) -> u8 {
+        if let Some(opcode) = get_bytecode(instruction) {
+            return opcode;
+        }
+        let (line, column) = match self.line_table.entries.last() {
+            Some(entry) => (entry.line, entry.column),
+            None => (1, 1),
+        };
+        self.report(
+            ErrorType::CompilationError,
+            format!("instruction not recognized: {}", instruction).as_str(),
+            line,
+            column,
+        );
+        0
     }
 
-    fn gen_node_bytecode(node: &AstNodeType) -> Vec<u8> {
+    fn report(&mut self, kind: ErrorType, message: &str, line: usize, column: usize) {
+        self.diagnostics
+            .push(Diagnostic::new(kind, message, line, column));
+    }
+
+    // at: position where the node bytecode will be placed. it's
+    //     used to resolve the jumps of break and continue statements
+    fn gen_node_bytecode(&mut self, node: &AstNodeType, at: usize) -> Vec<u8> {
+        if node.line() > 0 {
+            self.line_table
+                .add_entry(at, self.file, node.line(), node.at());
+        }
+
         match node {
-            AstNodeType::AssignamentStatement(node) => {
-                Compiler::compile_assignament_statement(node)
+            AstNodeType::AssignamentStatement(node) => self.compile_assignament_statement(node),
+            AstNodeType::FunctionDeclaration(node) => self.compile_function_declaration(node, at),
+            AstNodeType::IfStatement(node) => self.compile_if_statement(node, at),
+            AstNodeType::Expression(node) => self.compile_expression(node, true),
+            AstNodeType::WhileStatement(node) => self.compile_while_statement(node, at),
+            AstNodeType::BreakStatement(node) => self.compile_break_statement(node, at),
+            AstNodeType::ContinueStatement(node) => self.compile_continue_statement(node, at),
+            AstNodeType::TryStatement(node) => self.compile_try_statement(node, at),
+            AstNodeType::ThrowStatement(node) => self.compile_throw_statement(node),
+            AstNodeType::IndexAssignamentStatement(node) => {
+                self.compile_index_assignament_statement(node)
+            }
+            AstNodeType::PropertyAssignamentStatement(node) => {
+                self.compile_property_assignament_statement(node)
             }
-            AstNodeType::FunctionDeclaration(node) => Compiler::compile_function_declaration(node),
-            AstNodeType::IfStatement(node) => Compiler::compile_if_statement(node),
-            AstNodeType::Expression(node) => Compiler::compile_expression(node, true),
-            AstNodeType::WhileStatement(node) => Compiler::compile_while_statement(node),
-            AstNodeType::ReturnStatement(node) => Compiler::compile_return_statement(node),
-            AstNodeType::Struct(node) => Compiler::compile_struct_declaration(node),
-            AstNodeType::ImportStatement(node) => Compiler::compile_import(node),
-            AstNodeType::ExportStatement(node) => Compiler::compile_export(node),
+            AstNodeType::ReturnStatement(node) => self.compile_return_statement(node),
+            AstNodeType::Struct(node) => self.compile_struct_declaration(node),
+            AstNodeType::ImplDeclaration(node) => self.compile_impl_declaration(node, at),
+            AstNodeType::ImportStatement(node) => self.compile_import(node, at),
+            AstNodeType::ExportStatement(node) => self.compile_export(node),
             _ => {
                 // panic!("unhandled node type")
                 // here we should, in the near future throw an error
@@ -96,50 +238,140 @@ impl Compiler {
         }
     }
 
-    fn compile_assignament_statement(node: &AssignamentNode) -> Vec<u8> {
+    fn compile_assignament_statement(&mut self, node: &AssignamentNode) -> Vec<u8> {
         let mut operation_bytecode = vec![];
         // load value
-        operation_bytecode.extend_from_slice(&Compiler::compile_expression(&node.init, false));
+        operation_bytecode.extend_from_slice(&self.compile_expression(&node.init, false));
 
         // op
-        operation_bytecode.push(get_bytecode("store_var".to_string()));
-
-        // var_type
-        operation_bytecode.push(match node.var_type {
-            VarType::Const => get_bytecode("inmut".to_string()),
-            _ => get_bytecode("mut".to_string()),
-        });
+        let var_type = match node.var_type {
+            VarType::Const => "inmut",
+            VarType::Let => "mut",
+            VarType::None => "reassign",
+        };
+        operation_bytecode.extend_from_slice(&self.compile_store(&node.identifier.name, var_type));
 
-        // identifier raw string
         operation_bytecode
-            .extend_from_slice(&Compiler::compile_raw_string(node.identifier.name.clone()));
+    }
 
-        operation_bytecode
+    // locals are loaded from their slot, the rest by name
+    fn compile_load(&mut self, identifier: &str) -> Vec<u8> {
+        let mut bytecode = vec![];
+        match self.resolver.resolve(identifier) {
+            Variable::Local { depth, slot } => {
+                bytecode.push(self.opcode("load_local"));
+                bytecode.extend_from_slice(&Compiler::compile_offset(depth as i32));
+                bytecode.extend_from_slice(&Compiler::compile_offset(slot as i32));
+            }
+            Variable::Named => bytecode.push(self.opcode("load_var")),
+            Variable::Global => bytecode.push(self.opcode("load_global")),
+        }
+
+        // identifier raw string, kept on locals for errors
+        bytecode.extend_from_slice(&self.compile_raw_string(identifier.to_string()));
+        bytecode
     }
 
-    fn compile_function_declaration(node: &FunctionDeclaration) -> Vec<u8> {
+    // var_type: inmut | mut | reassign, only used on
+    //           the module level, where names are bound
+    fn compile_store(&mut self, identifier: &str, var_type: &str) -> Vec<u8> {
+        let mut bytecode = vec![];
+        match self.resolver.resolve(identifier) {
+            Variable::Local { depth, slot } => {
+                bytecode.push(self.opcode("store_local"));
+                bytecode.extend_from_slice(&Compiler::compile_offset(depth as i32));
+                bytecode.extend_from_slice(&Compiler::compile_offset(slot as i32));
+            }
+            Variable::Named | Variable::Global => {
+                bytecode.push(self.opcode("store_var"));
+                bytecode.push(self.opcode(var_type));
+            }
+        }
+
+        bytecode.extend_from_slice(&self.compile_raw_string(identifier.to_string()));
+        bytecode
+    }
+
+    fn compile_index_assignament_statement(&mut self, node: &IndexAssignamentNode) -> Vec<u8> {
+        let mut bytecode = vec![];
+        // load vector, index and value
+        bytecode.extend_from_slice(&self.compile_expression(&node.target.object, false));
+        bytecode.extend_from_slice(&self.compile_expression(&node.target.index, false));
+        bytecode.extend_from_slice(&self.compile_expression(&node.init, false));
+
+        // op
+        bytecode.push(self.opcode("set_index"));
+        bytecode
+    }
+
+    fn compile_property_assignament_statement(
+        &mut self,
+        node: &PropertyAssignamentNode,
+    ) -> Vec<u8> {
+        let mut bytecode = vec![];
+        // load object (a potential nested member_expression),
+        // property and value
+        let property = &node.target.property;
+        bytecode.extend_from_slice(&self.compile_expression(&node.target.object, false));
+        bytecode.extend_from_slice(&self.compile_expression(
+            &Expression::StringLiteral(StringLiteral::new(
+                property.name.clone(),
+                property.name.clone(),
+                property.at,
+                property.line,
+            )),
+            false,
+        ));
+        bytecode.extend_from_slice(&self.compile_expression(&node.init, false));
+
+        // op
+        bytecode.push(self.opcode("set_property"));
+        bytecode
+    }
+
+    fn compile_function_declaration(&mut self, node: &FunctionDeclaration, at: usize) -> Vec<u8> {
+        if !self.resolver.in_function() {
+            return self.compile_function(node, "function_declaration", at);
+        }
+
+        // nested functions are locals of the enclosing one
+        let mut bytecode = self.compile_function(node, "method_declaration", at);
+        bytecode.extend_from_slice(&self.compile_store(&node.identifier.name, "inmut"));
+        bytecode
+    }
+
+    // opcode: function_declaration stores the function on the current
+    //         scope by name, method_declaration leaves it on the stack
+    fn compile_function(&mut self, node: &FunctionDeclaration, opcode: &str, at: usize) -> Vec<u8> {
         let mut bytecode = vec![];
 
         // load function args num/type/...
-        let parameters: Vec<String> = node
-            .parameters
-            .children
-            .iter()
-            .map(|c| match c {
-                Some(Expression::Identifier(x)) => x.name.clone(),
-                Some(_) => panic!(
-                    "bad param type on '{}' function declaration",
-                    node.identifier.name
+        let mut parameters: Vec<String> = vec![];
+        for child in &node.parameters.children {
+            match child {
+                Some(Expression::Identifier(x)) => parameters.push(x.name.clone()),
+                Some(other) => self.report(
+                    ErrorType::CompilationError,
+                    format!("parameters of '{}' must be names", node.identifier.name).as_str(),
+                    other.line(),
+                    other.at(),
                 ),
-                None => panic!(
-                    "empty parameter in '{}' function declaration",
-                    node.identifier.name
+                None => self.report(
+                    ErrorType::CompilationError,
+                    format!(
+                        "empty parameter in '{}' function declaration",
+                        node.identifier.name
+                    )
+                    .as_str(),
+                    node.parameters.line,
+                    node.parameters.at,
                 ),
-            })
-            .collect();
+            }
+        }
         let params_length = parameters.len();
+        self.resolver.enter_function(&parameters, &node.body);
         for param in parameters {
-            let param_bytecode = Compiler::compile_expression(
+            let param_bytecode = self.compile_expression(
                 &Expression::StringLiteral(StringLiteral {
                     value: param.to_string(),
                     raw_value: param,
@@ -152,21 +384,34 @@ impl Compiler {
         }
 
         // op
-        bytecode.push(get_bytecode("function_declaration".to_string()));
+        bytecode.push(self.opcode(opcode));
 
         // load function name
-        bytecode.extend_from_slice(&Compiler::compile_raw_string(node.identifier.name.clone()));
+        bytecode.extend_from_slice(&self.compile_raw_string(node.identifier.name.clone()));
 
         // // load function parameters_num
         bytecode.extend_from_slice(&Compiler::compile_offset(params_length as i32));
 
-        // load body of the function
-        let body_bytecode = Compiler::compile_block(&node.body);
+        // load body of the function. the body is not part of
+        // the enclosing loops, so break and continue can't
+        // jump out of it
+        let enclosing_loops = std::mem::take(&mut self.loops);
+        let body_at = at + bytecode.len() + 4;
+        let body_bytecode = self.compile_block(&node.body, body_at);
+        self.loops = enclosing_loops;
+        self.resolver.exit_function();
         let body_bytecode_length = if body_bytecode.len() > i32::MAX as usize {
-            panic!(
-                "{} function declaration body is bigger than the limits",
-                node.identifier.name
+            self.report(
+                ErrorType::CompilationError,
+                format!(
+                    "{} function declaration body is bigger than the limits",
+                    node.identifier.name
+                )
+                .as_str(),
+                node.identifier.line,
+                node.identifier.at,
             );
+            0
         } else {
             body_bytecode.len() as i32
         };
@@ -177,30 +422,60 @@ impl Compiler {
         bytecode
     }
 
-    fn compile_struct_declaration(node: &Struct) -> Vec<u8> {
+    fn compile_struct_declaration(&mut self, node: &Struct) -> Vec<u8> {
         let mut bytecode = vec![];
         // op
-        bytecode.push(get_bytecode("struct_declaration".to_string()));
+        bytecode.push(self.opcode("struct_declaration"));
 
         // identifier raw string
-        bytecode.extend_from_slice(&Compiler::compile_raw_string(node.identifier.name.clone()));
+        bytecode.extend_from_slice(&self.compile_raw_string(node.identifier.name.clone()));
 
         // compile object fields number
         bytecode.extend_from_slice(&Compiler::compile_offset(node.fields.fields.len() as i32));
 
         // object type
-        bytecode.extend_from_slice(&Compiler::compile_object_type(&node.fields));
+        bytecode.extend_from_slice(&self.compile_object_type(&node.fields));
+
+        bytecode
+    }
+
+    fn compile_impl_declaration(&mut self, node: &ImplDeclaration, at: usize) -> Vec<u8> {
+        let mut bytecode = vec![];
+
+        // load methods
+        for method in &node.methods {
+            let method_at = at + bytecode.len();
+            self.line_table
+                .add_entry(method_at, self.file, method.line, method.at);
+            bytecode.extend_from_slice(&self.compile_function(
+                method,
+                "method_declaration",
+                method_at,
+            ));
+        }
+
+        // op
+        self.line_table
+            .add_entry(at + bytecode.len(), self.file, node.line, node.at);
+        bytecode.push(self.opcode("impl_declaration"));
+
+        // struct identifier raw string
+        bytecode.extend_from_slice(&self.compile_raw_string(node.identifier.name.clone()));
+
+        // methods number
+        bytecode.extend_from_slice(&Compiler::compile_offset(node.methods.len() as i32));
 
         bytecode
     }
 
-    fn compile_if_statement(node: &IfStatement) -> Vec<u8> {
+    fn compile_if_statement(&mut self, node: &IfStatement, at: usize) -> Vec<u8> {
         let mut bytecode = vec![];
 
-        let condition_bytecode = &Compiler::compile_expression(&node.condition, false);
-        let then_bytecode = Compiler::compile_block(&node.body);
+        let condition_bytecode = &self.compile_expression(&node.condition, false);
+        let then_at = at + condition_bytecode.len() + 1 + 4;
+        let then_bytecode = self.compile_block(&node.body, then_at);
         let else_bytecode = if let Some(else_node) = &node.else_node {
-            Compiler::compile_block(&else_node.body)
+            self.compile_block(&else_node.body, then_at + then_bytecode.len() + 1 + 4)
         } else {
             vec![]
         };
@@ -208,17 +483,17 @@ impl Compiler {
         let offset_skip_else = Compiler::compile_offset((else_bytecode.len() + 1) as i32);
 
         bytecode.extend_from_slice(&condition_bytecode);
-        bytecode.push(get_bytecode("jump_if_false".to_string()));
+        bytecode.push(self.opcode("jump_if_false"));
         bytecode.extend_from_slice(&offset_to_else);
         bytecode.extend_from_slice(&then_bytecode);
-        bytecode.push(get_bytecode("jump".to_string()));
+        bytecode.push(self.opcode("jump"));
         bytecode.extend_from_slice(&offset_skip_else);
         bytecode.extend_from_slice(&else_bytecode);
 
         bytecode
     }
 
-    fn compile_while_statement(node: &WhileStatement) -> Vec<u8> {
+    fn compile_while_statement(&mut self, node: &WhileStatement, at: usize) -> Vec<u8> {
         // body offset and while offset are calculated based on
         // two euristics to handle the circular reference
         // "to calculate body offset you need while offset and
@@ -226,27 +501,139 @@ impl Compiler {
         // 4: offset bytecode size
         // 1: opcode size
         let mut bytecode = vec![];
-        let condition_bytecode = Compiler::compile_expression(&node.condition, false);
-        let body_bytecode = Compiler::compile_block(&node.body);
+        let condition_bytecode = self.compile_expression(&node.condition, false);
+        let body_at = at + condition_bytecode.len() + 1 + 4;
+        self.loops.push(LoopContext {
+            start: at,
+            breaks: vec![],
+            try_depth: self.try_depth,
+        });
+        let mut body_bytecode = self.compile_block(&node.body, body_at);
+        let loop_context = self.loops.pop().unwrap();
+
+        // patch breaks to jump right after the loop
+        let loop_end = body_at + body_bytecode.len() + 1 + 4;
+        for break_at in loop_context.breaks {
+            let offset = Compiler::compile_offset((loop_end - break_at - 4) as i32);
+            let index = break_at - body_at + 1;
+            body_bytecode[index..index + 4].copy_from_slice(&offset);
+        }
+
         let body_offset = Compiler::compile_offset((body_bytecode.len() + 4 + 1) as i32);
         let while_offset = Compiler::compile_offset(
             -((condition_bytecode.len() + body_offset.len() + 1 + body_bytecode.len() + 4) as i32),
         );
 
         bytecode.extend_from_slice(&condition_bytecode);
-        bytecode.push(get_bytecode("jump_if_false".to_string()));
+        bytecode.push(self.opcode("jump_if_false"));
         bytecode.extend_from_slice(&body_offset);
         bytecode.extend_from_slice(&body_bytecode);
-        bytecode.push(get_bytecode("jump".to_string()));
+        bytecode.push(self.opcode("jump"));
         bytecode.extend_from_slice(&while_offset);
         bytecode
     }
 
-    fn compile_return_statement(node: &ReturnStatement) -> Vec<u8> {
+    fn compile_break_statement(&mut self, node: &BreakStatement, at: usize) -> Vec<u8> {
         let mut bytecode = vec![];
+        let try_end = self.opcode("try_end");
+        if let Some(loop_context) = self.loops.last_mut() {
+            for _ in loop_context.try_depth..self.try_depth {
+                bytecode.push(try_end);
+            }
+            // the offset is patched by the enclosing while
+            loop_context.breaks.push(at + bytecode.len());
+        } else {
+            self.diagnostics.push(
+                Diagnostic::new(
+                    ErrorType::CompilationError,
+                    "'break' outside of a loop",
+                    node.line,
+                    node.at,
+                )
+                .with_help("'break' can only be used inside a while loop"),
+            );
+            return bytecode;
+        }
 
-        bytecode.extend_from_slice(&Compiler::compile_expression(&node.value, false));
-        bytecode.push(get_bytecode("return".to_string()));
+        bytecode.push(self.opcode("jump"));
+        bytecode.extend_from_slice(&Compiler::compile_offset(0));
+        bytecode
+    }
+
+    fn compile_continue_statement(&mut self, node: &ContinueStatement, at: usize) -> Vec<u8> {
+        let mut bytecode = vec![];
+        let try_end = self.opcode("try_end");
+        let loop_start = if let Some(loop_context) = self.loops.last() {
+            for _ in loop_context.try_depth..self.try_depth {
+                bytecode.push(try_end);
+            }
+            loop_context.start
+        } else {
+            self.diagnostics.push(
+                Diagnostic::new(
+                    ErrorType::CompilationError,
+                    "'continue' outside of a loop",
+                    node.line,
+                    node.at,
+                )
+                .with_help("'continue' can only be used inside a while loop"),
+            );
+            return bytecode;
+        };
+
+        // jump back to the loop condition
+        let jump_at = at + bytecode.len();
+        bytecode.push(self.opcode("jump"));
+        bytecode.extend_from_slice(&Compiler::compile_offset(
+            loop_start as i32 - (jump_at + 4) as i32,
+        ));
+        bytecode
+    }
+
+    fn compile_try_statement(&mut self, node: &TryStatement, at: usize) -> Vec<u8> {
+        let mut bytecode = vec![];
+
+        // 1: opcode size
+        // 4: offset bytecode size
+        let body_at = at + 1 + 4;
+        self.try_depth += 1;
+        let body_bytecode = self.compile_block(&node.body, body_at);
+        self.try_depth -= 1;
+
+        // the vm pushes the caught error before jumping to the
+        // catch block, so it starts binding it to its identifier
+        let mut catch_bytecode = self.compile_store(&node.error_identifier.name, "mut");
+        let catch_at = body_at + body_bytecode.len() + 1 + 1 + 4;
+        let catch_body_bytecode =
+            self.compile_block(&node.catch_body, catch_at + catch_bytecode.len());
+        catch_bytecode.extend_from_slice(&catch_body_bytecode);
+
+        let offset_to_catch = Compiler::compile_offset((body_bytecode.len() + 1 + 1 + 4) as i32);
+        let offset_skip_catch = Compiler::compile_offset((catch_bytecode.len() + 1) as i32);
+
+        bytecode.push(self.opcode("try_start"));
+        bytecode.extend_from_slice(&offset_to_catch);
+        bytecode.extend_from_slice(&body_bytecode);
+        bytecode.push(self.opcode("try_end"));
+        bytecode.push(self.opcode("jump"));
+        bytecode.extend_from_slice(&offset_skip_catch);
+        bytecode.extend_from_slice(&catch_bytecode);
+
+        bytecode
+    }
+
+    fn compile_throw_statement(&mut self, node: &ThrowStatement) -> Vec<u8> {
+        let mut bytecode = vec![];
+        bytecode.extend_from_slice(&self.compile_expression(&node.value, false));
+        bytecode.push(self.opcode("throw"));
+        bytecode
+    }
+
+    fn compile_return_statement(&mut self, node: &ReturnStatement) -> Vec<u8> {
+        let mut bytecode = vec![];
+
+        bytecode.extend_from_slice(&self.compile_expression(&node.value, false));
+        bytecode.push(self.opcode("return"));
 
         bytecode
     }
@@ -254,17 +641,17 @@ impl Compiler {
     // drop value: if the value must not be persisted like module level declared string
     //             or function calling with no receiver of the return value, the value
     //             must be dropped
-    fn compile_expression(node: &Expression, drop_value: bool) -> Vec<u8> {
-        // all expressions push a load_const opcode
-        // except of identifier which loads a load_var opcode
+    fn compile_expression(&mut self, node: &Expression, drop_value: bool) -> Vec<u8> {
+        // all expressions push a load_const opcode except
+        // of identifiers, loaded from where the resolver says
         match node {
             Expression::CallExpression(v) => {
-                let call_expression_bytecode = match v.get_callee().as_str() {
-                    "print" => handlers::print_as_bytecode(v),
-                    "println" => handlers::print_as_bytecode(v), // both print types can be handled by the same function
-                    "call" => handlers::call_as_bytecode(v),
-                    "ai" => handlers::function_call_as_bytecode(v, drop_value),
-                    _ => handlers::function_call_as_bytecode(v, drop_value),
+                let call_expression_bytecode = match v.get_callee().as_deref() {
+                    Some("print") => handlers::print_as_bytecode(self, v, "print"),
+                    Some("println") => handlers::print_as_bytecode(self, v, "println"), // both print types can be handled by the same function
+                    Some("call") => handlers::call_as_bytecode(self, v),
+                    Some("ai") => handlers::function_call_as_bytecode(self, v, drop_value),
+                    _ => handlers::function_call_as_bytecode(self, v, drop_value),
                 };
 
                 call_expression_bytecode
@@ -273,17 +660,16 @@ impl Compiler {
                 let mut bytecode = vec![];
 
                 // first, load field values onto the stack
-                let (fields_num, object_literal_bytecode) =
-                    &Compiler::compile_object_literal(&v.fields);
+                let (fields_num, object_literal_bytecode) = &self.compile_object_literal(&v.fields);
                 bytecode.extend_from_slice(&object_literal_bytecode);
 
                 // struct type
                 let struct_type_bytecode = match &v.identifier {
-                    StructTypeExpr::MemberExpression(x) => Compiler::compile_expression(
+                    StructTypeExpr::MemberExpression(x) => self.compile_expression(
                         &Expression::MemberExpression(x.as_ref().clone()),
                         false,
                     ),
-                    StructTypeExpr::Identifier(x) => Compiler::compile_expression(
+                    StructTypeExpr::Identifier(x) => self.compile_expression(
                         &Expression::StringLiteral(StringLiteral::new(
                             x.name.clone(),
                             x.name.clone(),
@@ -296,8 +682,8 @@ impl Compiler {
                 bytecode.extend_from_slice(&struct_type_bytecode);
 
                 // compile struct
-                bytecode.push(get_bytecode("load_const".to_string()));
-                bytecode.push(get_bytecode("struct_literal".to_string()));
+                bytecode.push(self.opcode("load_const"));
+                bytecode.push(self.opcode("struct_literal"));
 
                 // compile object fields number
                 bytecode.extend_from_slice(&Compiler::compile_offset(*fields_num as i32));
@@ -309,19 +695,38 @@ impl Compiler {
                 let elements_num = v.children.len();
 
                 for child in &v.children {
-                    bytecode.extend_from_slice(&Compiler::compile_expression(child, false));
+                    bytecode.extend_from_slice(&self.compile_expression(child, false));
                 }
 
                 // compile struct
-                bytecode.push(get_bytecode("load_const".to_string()));
-                bytecode.push(get_bytecode("vector".to_string()));
+                bytecode.push(self.opcode("load_const"));
+                bytecode.push(self.opcode("vector"));
                 bytecode.extend_from_slice(&Compiler::compile_offset(elements_num as i32));
 
                 bytecode
             }
+            Expression::MapLiteral(m) => {
+                let mut bytecode = vec![];
+                let entries_num = m.entries.len();
+
+                // each key is followed by its value
+                for (key, value) in &m.entries {
+                    bytecode.extend_from_slice(
+                        &self.compile_expression(&Expression::StringLiteral(key.clone()), false),
+                    );
+                    bytecode.extend_from_slice(&self.compile_expression(value, false));
+                }
+
+                // compile map
+                bytecode.push(self.opcode("load_const"));
+                bytecode.push(self.opcode("map"));
+                bytecode.extend_from_slice(&Compiler::compile_offset(entries_num as i32));
+
+                bytecode
+            }
             Expression::Number(v) => {
                 let mut bytecode = vec![];
-                bytecode.push(get_bytecode("load_const".to_string()));
+                bytecode.push(self.opcode("load_const"));
 
                 // if v.value.is_sign_negative() {
                 //     panic!("Cannot compile negative numbers on self");
@@ -330,20 +735,31 @@ impl Compiler {
                 let (num_bytecode, num_type_bytecode) = if v.value.fract() != 0.0 {
                     (
                         bytes_from_float(Number::F64(v.value)).to_vec(),
-                        get_bytecode("f64".to_string()),
+                        self.opcode("f64"),
                     )
                 } else if v.value >= i32::MIN as f64 && v.value <= i32::MAX as f64 {
                     (
                         bytes_from_32(Number::I32(v.value as i32)).to_vec(),
-                        get_bytecode("i32".to_string()),
+                        self.opcode("i32"),
                     )
                 } else if v.value >= i64::MIN as f64 && v.value <= i64::MAX as f64 {
                     (
                         bytes_from_64(Number::I64(v.value as i64)).to_vec(),
-                        get_bytecode("i64".to_string()),
+                        self.opcode("i64"),
                     )
                 } else {
-                    panic!("Unsupported number type or out of range");
+                    self.report(
+                        ErrorType::CompilationError,
+                        format!(
+                            "number out of range, integers go from {} to {}",
+                            i64::MIN,
+                            i64::MAX
+                        )
+                        .as_str(),
+                        v.line,
+                        v.at,
+                    );
+                    (vec![], self.opcode("nothing"))
                 };
 
                 // type
@@ -355,22 +771,16 @@ impl Compiler {
             }
             Expression::StringLiteral(v) => {
                 let mut bytecode = vec![];
-                bytecode.push(get_bytecode("load_const".to_string()));
-
-                // todo: handle larger string
-                let string_bytes = v.raw_value.as_bytes();
-                let string_length = string_bytes.len() as u32;
+                bytecode.push(self.opcode("load_const"));
 
-                bytecode.push(get_bytecode("utf8".to_string()));
-                bytecode.push(get_bytecode("u32".to_string()));
-                bytecode.extend_from_slice(&string_length.to_le_bytes());
-                bytecode.extend_from_slice(string_bytes);
+                bytecode.push(self.opcode("utf8"));
+                bytecode.extend_from_slice(&self.compile_raw_string(v.raw_value.clone()));
                 bytecode
             }
             Expression::Bool(v) => {
                 let mut bytecode = vec![];
-                bytecode.push(get_bytecode("load_const".to_string()));
-                bytecode.push(get_bytecode("bool".to_string()));
+                bytecode.push(self.opcode("load_const"));
+                bytecode.push(self.opcode("bool"));
                 if v.value {
                     bytecode.push(0x01);
                 } else {
@@ -379,13 +789,9 @@ impl Compiler {
 
                 bytecode
             }
-            Expression::Identifier(v) => {
-                let mut bytecode = vec![];
-                bytecode.push(get_bytecode("load_var".to_string()));
-
-                let identifier_bytecode = Compiler::compile_raw_string(v.name.clone());
-                bytecode.extend_from_slice(&identifier_bytecode);
-                bytecode
+            Expression::Identifier(v) => self.compile_load(&v.name),
+            Expression::BinaryExpression(v) if v.operator == "&&" || v.operator == "||" => {
+                self.compile_logical_expression(v)
             }
             Expression::BinaryExpression(v) => {
                 let mut bytecode = vec![];
@@ -393,20 +799,46 @@ impl Compiler {
                 // operands
                 let left_operand = *v.left.clone();
                 let right_operand = *v.right.clone();
-                bytecode.extend_from_slice(&Compiler::compile_expression(&left_operand, false));
-                bytecode.extend_from_slice(&Compiler::compile_expression(&right_operand, false));
+                bytecode.extend_from_slice(&self.compile_expression(&left_operand, false));
+                bytecode.extend_from_slice(&self.compile_expression(&right_operand, false));
+
+                // operator
+                match v.operator.as_str() {
+                    "+" => bytecode.push(self.opcode("add")),
+                    "-" => bytecode.push(self.opcode("substract")),
+                    "*" => bytecode.push(self.opcode("multiply")),
+                    "/" => bytecode.push(self.opcode("divide")),
+                    ">" => bytecode.push(self.opcode("greater_than")),
+                    "<" => bytecode.push(self.opcode("less_than")),
+                    ">=" => bytecode.push(self.opcode("greater_than_or_equal")),
+                    "<=" => bytecode.push(self.opcode("less_than_or_equal")),
+                    "==" => bytecode.push(self.opcode("equals")),
+                    "!=" => bytecode.push(self.opcode("not_equals")),
+                    _ => self.report(
+                        ErrorType::CompilationError,
+                        format!("unsupported binary operator '{}'", v.operator).as_str(),
+                        v.line,
+                        v.at,
+                    ),
+                };
+
+                bytecode
+            }
+            Expression::UnaryExpression(v) => {
+                let mut bytecode = vec![];
+
+                // operand
+                bytecode.extend_from_slice(&self.compile_expression(&v.operand, false));
 
                 // operator
                 match v.operator.as_str() {
-                    "+" => bytecode.push(get_bytecode("add".to_string())),
-                    "-" => bytecode.push(get_bytecode("substract".to_string())),
-                    "*" => bytecode.push(get_bytecode("multiply".to_string())),
-                    "/" => bytecode.push(get_bytecode("divide".to_string())),
-                    ">" => bytecode.push(get_bytecode("greater_than".to_string())),
-                    "<" => bytecode.push(get_bytecode("less_than".to_string())),
-                    "==" => bytecode.push(get_bytecode("equals".to_string())),
-                    "!=" => bytecode.push(get_bytecode("not_equals".to_string())),
-                    _ => {}
+                    "!" => bytecode.push(self.opcode("not")),
+                    _ => self.report(
+                        ErrorType::CompilationError,
+                        format!("unsupported unary operator '{}'", v.operator).as_str(),
+                        v.line,
+                        v.at,
+                    ),
                 };
 
                 bytecode
@@ -424,30 +856,107 @@ impl Compiler {
                     property.line,
                 );
                 let property_bytecode =
-                    Compiler::compile_expression(&Expression::StringLiteral(string_literal), false);
+                    self.compile_expression(&Expression::StringLiteral(string_literal), false);
 
                 // compile object (a potential nested object_expression)
-                let object_bytecode = Compiler::compile_expression(&object, false);
+                let object_bytecode = self.compile_expression(&object, false);
 
                 bytecode.extend_from_slice(&object_bytecode);
                 bytecode.extend_from_slice(&property_bytecode);
-                bytecode.push(get_bytecode("get_property".to_string()));
+                bytecode.push(self.opcode("get_property"));
+                bytecode
+            }
+            Expression::IndexExpression(v) => {
+                let mut bytecode = vec![];
+                bytecode.extend_from_slice(&self.compile_expression(&v.object, false));
+                bytecode.extend_from_slice(&self.compile_expression(&v.index, false));
+                bytecode.push(self.opcode("get_index"));
                 bytecode
             }
             Expression::Nothing(_) => {
                 let mut bytecode = vec![];
-                bytecode.push(get_bytecode("load_const".to_string()));
-                bytecode.push(get_bytecode("nothing".to_string()));
+                bytecode.push(self.opcode("load_const"));
+                bytecode.push(self.opcode("nothing"));
 
                 bytecode
             }
-            _ => {
-                panic!("unhandled expression type")
+            Expression::ObjectLiteral(v) => {
+                self.report(
+                    ErrorType::CompilationError,
+                    "an object literal is only valid after a struct name",
+                    v.line,
+                    v.at,
+                );
+                vec![]
             }
         }
     }
 
-    fn compile_import(node: &ImportStatement) -> Vec<u8> {
+    // && and || are lowered to conditional jumps so the right
+    // operand is only evaluated when needed (short-circuit). both
+    // operands go through jump_if_false, which type checks them
+    // as bool, and the result is always a fresh bool constant.
+    //
+    // a && b                     a || b
+    //   <a>                        <a>
+    //   jump_if_false -> false     jump_if_false -> right
+    //   <b>                        load_const true
+    //   jump_if_false -> false     jump -> end
+    //   load_const true          right:
+    //   jump -> end                <b>
+    // false:                       jump_if_false -> false
+    //   load_const false           load_const true
+    // end:                         jump -> end
+    //                            false:
+    //                              load_const false
+    //                            end:
+    //
+    // 4: offset bytecode size
+    // 1: opcode size
+    // 3: load_const bool size
+    fn compile_logical_expression(&mut self, node: &BinaryExpression) -> Vec<u8> {
+        let mut bytecode = vec![];
+        let left_bytecode = self.compile_expression(&node.left, false);
+        let right_bytecode = self.compile_expression(&node.right, false);
+        let load_true = self.compile_expression(
+            &Expression::Bool(Bool::new(true, node.at, node.line)),
+            false,
+        );
+        let load_false = self.compile_expression(
+            &Expression::Bool(Bool::new(false, node.at, node.line)),
+            false,
+        );
+
+        bytecode.extend_from_slice(&left_bytecode);
+        bytecode.push(self.opcode("jump_if_false"));
+        if node.operator == "&&" {
+            // skip: right, its jump, true and the jump to the end
+            bytecode.extend_from_slice(&Compiler::compile_offset(
+                (right_bytecode.len() + 4 + 1 + load_true.len() + 4 + 1) as i32,
+            ));
+        } else {
+            // skip: true and the jump to the end
+            bytecode.extend_from_slice(&Compiler::compile_offset((load_true.len() + 4 + 1) as i32));
+            bytecode.extend_from_slice(&load_true);
+            bytecode.push(self.opcode("jump"));
+            // skip: right, its jump, true, the jump and false
+            bytecode.extend_from_slice(&Compiler::compile_offset(
+                (right_bytecode.len() + 4 + 1 + load_true.len() + 4 + 1 + load_false.len() + 1)
+                    as i32,
+            ));
+        }
+        bytecode.extend_from_slice(&right_bytecode);
+        bytecode.push(self.opcode("jump_if_false"));
+        bytecode.extend_from_slice(&Compiler::compile_offset((load_true.len() + 4 + 1) as i32));
+        bytecode.extend_from_slice(&load_true);
+        bytecode.push(self.opcode("jump"));
+        bytecode.extend_from_slice(&Compiler::compile_offset((load_false.len() + 1) as i32));
+        bytecode.extend_from_slice(&load_false);
+
+        bytecode
+    }
+
+    fn compile_import(&mut self, node: &ImportStatement, at: usize) -> Vec<u8> {
         let mut bytecode = vec![];
 
         match node.module_type {
@@ -455,7 +964,7 @@ impl Compiler {
                 // for the moment let's only enable
                 // one deepth
                 let module = node.module[0].clone();
-                bytecode.extend_from_slice(&Compiler::compile_expression(
+                bytecode.extend_from_slice(&self.compile_expression(
                     &Expression::StringLiteral(StringLiteral::new(
                         module.to_string(),
                         module,
@@ -465,7 +974,7 @@ impl Compiler {
                     false,
                 ));
 
-                bytecode.push(get_bytecode("import".to_string()));
+                bytecode.push(self.opcode("import"));
                 // compile an offset to make compatible with the custom module
                 // execution
                 bytecode.extend_from_slice(&Compiler::compile_offset(0 as i32));
@@ -477,12 +986,31 @@ impl Compiler {
                 // compilation
                 let module_name = node.module[0].clone();
                 let path = format!("{}.ego", module_name);
-                let code =
-                    fs::read_to_string(&path).expect(&format!("Failed to read module '{}'", path));
-                let mod_bytecode = gen_bytecode(module_name.to_string(), code, &vec![]);
+                let Ok(code) = fs::read_to_string(&path) else {
+                    self.report(
+                        ErrorType::IOError,
+                        format!("Failed to read module '{}'", path).as_str(),
+                        node.line,
+                        node.at,
+                    );
+                    return bytecode;
+                };
+                let (mod_bytecode, mod_line_table) =
+                    match gen_bytecode(module_name.to_string(), code, &vec![]) {
+                        Ok(module) => module,
+                        Err(diagnostics) => {
+                            // problems of modules imported by the
+                            // module keep their own file
+                            for mut diagnostic in diagnostics {
+                                diagnostic.file.get_or_insert(path.clone());
+                                self.diagnostics.push(diagnostic);
+                            }
+                            return bytecode;
+                        }
+                    };
 
                 // push module_name to stack
-                bytecode.extend_from_slice(&Compiler::compile_expression(
+                bytecode.extend_from_slice(&self.compile_expression(
                     &Expression::StringLiteral(StringLiteral::new(
                         module_name.to_string(),
                         module_name,
@@ -491,8 +1019,11 @@ impl Compiler {
                     )),
                     false,
                 ));
-                bytecode.push(get_bytecode("import".to_string()));
+                bytecode.push(self.opcode("import"));
                 bytecode.extend_from_slice(&Compiler::compile_offset(mod_bytecode.len() as i32));
+                // the module is inlined, so its positions are
+                // shifted to where its bytecode is placed
+                self.line_table.extend(mod_line_table, at + bytecode.len());
                 bytecode.extend_from_slice(&mod_bytecode);
 
                 bytecode
@@ -500,12 +1031,12 @@ impl Compiler {
         }
     }
 
-    fn compile_export(node: &ExportStatement) -> Vec<u8> {
+    fn compile_export(&mut self, node: &ExportStatement) -> Vec<u8> {
         let mut bytecode = vec![];
         match &node.value {
             Expression::Identifier(n) => {
                 let identifier = n.name.clone();
-                bytecode.extend_from_slice(&Compiler::compile_expression(
+                bytecode.extend_from_slice(&self.compile_expression(
                     &Expression::StringLiteral(StringLiteral {
                         value: identifier.clone(),
                         raw_value: identifier,
@@ -516,34 +1047,35 @@ impl Compiler {
                 ));
             }
             _ => {
-                error::throw(
+                self.report(
                     ErrorType::CompilationError,
                     "exported member must be an <identifier>",
-                    Some(node.line),
+                    node.line,
+                    node.at,
                 );
-                std::process::exit(1);
+                return bytecode;
             }
         }
 
-        bytecode.push(get_bytecode("export".to_string()));
+        bytecode.push(self.opcode("export"));
         bytecode
     }
 
-    fn compile_block(node: &Block) -> Vec<u8> {
+    fn compile_block(&mut self, node: &Block, at: usize) -> Vec<u8> {
         let mut bytecode = vec![];
         for node in &node.children {
-            let node_bytecode = Compiler::gen_node_bytecode(node);
+            let node_bytecode = self.gen_node_bytecode(node, at + bytecode.len());
             bytecode.extend_from_slice(&node_bytecode);
         }
 
         bytecode
     }
 
-    fn compile_group(node: &Group) -> (usize, Vec<u8>) {
+    fn compile_group(&mut self, node: &Group) -> (usize, Vec<u8>) {
         let mut bytecode = vec![];
         for argument in &node.children {
             if let Some(arg) = argument {
-                bytecode.extend_from_slice(&Compiler::compile_expression(&arg, false))
+                bytecode.extend_from_slice(&self.compile_expression(&arg, false))
             } else {
                 // push nothing to bytecode
             }
@@ -552,26 +1084,27 @@ impl Compiler {
         (node.children.len(), bytecode)
     }
 
-    fn compile_object_type(node: &ObjectType) -> Vec<u8> {
+    fn compile_object_type(&mut self, node: &ObjectType) -> Vec<u8> {
         let mut bytecode = vec![];
 
         for field in &node.fields {
-            bytecode.extend_from_slice(&Compiler::compile_raw_string(field.name.to_string()));
+            bytecode.extend_from_slice(&self.compile_raw_string(field.name.to_string()));
             if let Some(annotation) = field.annotation {
                 // tyte it's a funny name for type_byte
                 let tyte = match annotation {
-                    Type::Bool => get_bytecode("bool".to_string()),
-                    Type::String => get_bytecode("utf8".to_string()),
-                    Type::Number => get_bytecode("f64".to_string()),
-                    Type::Nothing => get_bytecode("nothing".to_string()),
+                    Type::Bool => self.opcode("bool"),
+                    Type::String => self.opcode("utf8"),
+                    Type::Number => self.opcode("f64"),
+                    Type::Nothing => self.opcode("nothing"),
                 };
                 bytecode.push(tyte);
             } else {
                 // this code should be unreachable
-                error::throw(
+                self.report(
                     ErrorType::CompilationError,
                     "object type's identifier must be annotated",
-                    Some(node.line),
+                    node.line,
+                    node.at,
                 );
             }
         }
@@ -579,32 +1112,25 @@ impl Compiler {
         bytecode
     }
 
-    fn compile_object_literal(node: &ObjectLiteral) -> (usize, Vec<u8>) {
+    fn compile_object_literal(&mut self, node: &ObjectLiteral) -> (usize, Vec<u8>) {
         let mut bytecode = vec![];
 
         for field in &node.fields {
             // load field_namde
-            bytecode.push(get_bytecode("load_const".to_string()));
-            bytecode.extend_from_slice(&Compiler::compile_raw_string(field.0.name.clone()));
+            bytecode.push(self.opcode("load_const"));
+            bytecode.push(self.opcode("utf8"));
+            bytecode.extend_from_slice(&self.compile_raw_string(field.0.name.clone()));
             // load expression
-            bytecode.extend_from_slice(&Compiler::compile_expression(&field.1, false));
+            bytecode.extend_from_slice(&self.compile_expression(&field.1, false));
         }
 
         (node.fields.len(), bytecode)
     }
 
-    fn compile_raw_string(v: String) -> Vec<u8> {
-        let mut bytecode = vec![];
-
-        // todo: handle larger string
-        let string_bytes = v.as_bytes();
-        let string_length = string_bytes.len() as u32;
-
-        bytecode.push(get_bytecode("utf8".to_string()));
-        bytecode.push(get_bytecode("u32".to_string()));
-        bytecode.extend_from_slice(&string_length.to_le_bytes());
-        bytecode.extend_from_slice(string_bytes);
-        bytecode
+    // strings are referenced by their index on the constants
+    fn compile_raw_string(&mut self, v: String) -> [u8; 4] {
+        let index = self.constants.add(&v);
+        Compiler::compile_offset(index as i32)
     }
 
     fn compile_offset(v: i32) -> [u8; 4] {
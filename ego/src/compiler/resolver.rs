@@ -0,0 +1,183 @@
+/*
+    DECIDES WHERE EACH IDENTIFIER LIVES AT RUNTIME, SO
+    LOCALS ARE READ FROM A NUMERIC SLOT OF THEIR FRAME
+    INSTEAD OF LOOKED UP BY NAME
+*/
+
+use std::{collections::HashSet, path::Path};
+
+use crate::ast::{
+    assignament_statement::VarType,
+    block::Block,
+    import_statement::{ImportStatement, ModuleType},
+    AstNodeType,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Variable {
+    // slot of the frame `depth` functions up from the
+    // function being compiled
+    Local { depth: u32, slot: u32 },
+    // bound by name on a function frame by the vm itself:
+    // structs and modules imported inside functions
+    Named,
+    // module level symbol
+    Global,
+}
+
+// the variables of a function being compiled. slots are
+// assigned in order: parameters first, then every name the
+// body declares, no matter how nested in blocks it is
+struct FunctionScope {
+    slots: Vec<String>,
+    named: HashSet<String>,
+}
+
+pub struct Resolver {
+    globals: HashSet<String>,
+    // innermost function last
+    functions: Vec<FunctionScope>,
+}
+
+impl Resolver {
+    pub fn new(module: &[AstNodeType]) -> Resolver {
+        let mut globals = HashSet::new();
+        let mut declarations = vec![];
+        collect_declarations(module, &mut declarations);
+        for declaration in declarations {
+            globals.insert(declaration.name().to_string());
+        }
+
+        Resolver {
+            globals,
+            functions: vec![],
+        }
+    }
+
+    // true while compiling a function body
+    pub fn in_function(&self) -> bool {
+        !self.functions.is_empty()
+    }
+
+    pub fn enter_function(&mut self, parameters: &[String], body: &Block) {
+        let mut declarations = vec![];
+        collect_declarations(&body.children, &mut declarations);
+
+        let mut scope = FunctionScope {
+            slots: parameters.to_vec(),
+            named: HashSet::new(),
+        };
+        for declaration in declarations {
+            let name = declaration.name();
+            if scope.slots.iter().any(|slot| slot == name) || scope.named.contains(name) {
+                continue;
+            }
+            match declaration {
+                Declaration::Local(name) => scope.slots.push(name),
+                Declaration::Named(name) => {
+                    scope.named.insert(name);
+                }
+                // assigning an undeclared name creates a local, unless
+                // an enclosing function or the module already has it
+                Declaration::Assignment(name) => {
+                    if self.resolve(&name) == Variable::Global && !self.globals.contains(&name) {
+                        scope.slots.push(name);
+                    }
+                }
+            }
+        }
+
+        self.functions.push(scope);
+    }
+
+    pub fn exit_function(&mut self) {
+        self.functions.pop();
+    }
+
+    pub fn resolve(&self, name: &str) -> Variable {
+        for (depth, function) in self.functions.iter().rev().enumerate() {
+            if let Some(slot) = function.slots.iter().position(|slot| slot == name) {
+                return Variable::Local {
+                    depth: depth as u32,
+                    slot: slot as u32,
+                };
+            }
+            if function.named.contains(name) {
+                return Variable::Named;
+            }
+        }
+
+        Variable::Global
+    }
+}
+
+enum Declaration {
+    // let, const, catch variables and nested functions
+    Local(String),
+    Named(String),
+    // reassignment without let or const
+    Assignment(String),
+}
+
+impl Declaration {
+    fn name(&self) -> &str {
+        match self {
+            Declaration::Local(name) | Declaration::Named(name) | Declaration::Assignment(name) => {
+                name
+            }
+        }
+    }
+}
+
+// names declared by the nodes, looking into nested blocks
+// but not into the functions declared on them
+fn collect_declarations(nodes: &[AstNodeType], declarations: &mut Vec<Declaration>) {
+    for node in nodes {
+        match node {
+            AstNodeType::AssignamentStatement(node) => {
+                let name = node.identifier.name.clone();
+                declarations.push(match node.var_type {
+                    VarType::Const | VarType::Let => Declaration::Local(name),
+                    VarType::None => Declaration::Assignment(name),
+                });
+            }
+            AstNodeType::FunctionDeclaration(node) => {
+                declarations.push(Declaration::Local(node.identifier.name.clone()))
+            }
+            AstNodeType::Struct(node) => {
+                declarations.push(Declaration::Named(node.identifier.name.clone()))
+            }
+            AstNodeType::ImportStatement(node) => {
+                declarations.push(Declaration::Named(import_name(node)))
+            }
+            AstNodeType::IfStatement(node) => {
+                collect_declarations(&node.body.children, declarations);
+                if let Some(else_node) = &node.else_node {
+                    collect_declarations(&else_node.body.children, declarations);
+                }
+            }
+            AstNodeType::WhileStatement(node) => {
+                collect_declarations(&node.body.children, declarations);
+            }
+            AstNodeType::TryStatement(node) => {
+                collect_declarations(&node.body.children, declarations);
+                declarations.push(Declaration::Local(node.error_identifier.name.clone()));
+                collect_declarations(&node.catch_body.children, declarations);
+            }
+            _ => {}
+        }
+    }
+}
+
+// the name the vm binds an imported module to
+pub fn import_name(node: &ImportStatement) -> String {
+    let module = node.module[0].clone();
+    match node.module_type {
+        ModuleType::Native => module,
+        ModuleType::Custom => Path::new(&module)
+            .file_name()
+            .and_then(|s| s.to_str())
+            .unwrap_or("unknown")
+            .to_string(),
+    }
+}
@@ -0,0 +1,150 @@
+/*
+    EXPLANATION OF EVERY ERROR CODE OF THE COMPILER. THE CODES
+    OF THE VM ARE EXPLAINED BY self_vm::VM_ERRORS
+*/
+
+use self_vm::Explanation;
+
+// every ErrorType::code needs its entry, in the same order
+pub const ERRORS: &[Explanation] = &[
+    Explanation {
+        code: "E0001",
+        title: "Syntax error",
+        description: "\
+The source has a token where the grammar doesn't allow it, like two operators
+in a row, an unknown character or a block that is never closed. Every syntax
+error of a module is reported at once, fix them from the first one as the
+later ones can be caused by it.",
+        example: "let a = = 3",
+    },
+    Explanation {
+        code: "E0002",
+        title: "Usage error",
+        description: "\
+ego was called without a command. Files are run with 'ego run <file>' or
+just 'ego <file>'.",
+        example: "ego",
+    },
+    Explanation {
+        code: "E0003",
+        title: "Fatal error",
+        description: "\
+ego couldn't read the file it was asked to run, check or compile. Without a
+file it looks for main.ego, or main.b for 'ego exec', in the current
+directory.",
+        example: "ego run missing.ego",
+    },
+    Explanation {
+        code: "E0004",
+        title: "Parsing error",
+        description: "\
+The module ends in the middle of a statement, or a statement has a shape the
+parser can't use, like an 'if' without a block.",
+        example: "let a =",
+    },
+    Explanation {
+        code: "E0005",
+        title: "Interpreting error",
+        description: "\
+Raised by the tree walking interpreter ego used before compiling to self
+bytecode. The compiler doesn't raise it, its runtime errors have V codes.",
+        example: "println(1 / 0)   // the vm raises V0004 now",
+    },
+    Explanation {
+        code: "E0006",
+        title: "Reference error",
+        description: "\
+A name is used but nothing with that name is declared where it's used, or it
+is used before its declaration. Functions see every name of the module, also
+the ones declared after them, but the rest of the code only sees the names
+declared above it.
+
+Imports of native modules that don't exist and members a native module
+doesn't export are reported too.",
+        example: "\
+println(count)
+let count = 1",
+    },
+    Explanation {
+        code: "E0007",
+        title: "Stack underflow error",
+        description: "\
+Raised by the tree walking interpreter ego used before compiling to self
+bytecode when leaving more scopes than it had entered. The vm raises V0009
+for its own stack.",
+        example: "no ego program raises it anymore",
+    },
+    Explanation {
+        code: "E0008",
+        title: "Unknown arithmetic operator error",
+        description: "\
+Raised by the tree walking interpreter ego used before compiling to self
+bytecode for operators it didn't support. The vm raises V0003 instead.",
+        example: "println(true + false)   // the vm raises V0003 now",
+    },
+    Explanation {
+        code: "E0009",
+        title: "Type error",
+        description: "\
+The value assigned to an annotated variable has another type than the
+annotation. Either change the annotation or the value.",
+        example: "let a: number = true",
+    },
+    Explanation {
+        code: "E0010",
+        title: "Missing member error",
+        description: "\
+A vector literal has an empty member, usually two commas in a row or a comma
+before the closing ']'.",
+        example: "let v = [1, , 2]",
+    },
+    Explanation {
+        code: "E0011",
+        title: "Invalid type annotation",
+        description: "\
+The annotation after ':' is not a type. The types are bool, string, number
+and nothing.",
+        example: "let z: 5 = 2",
+    },
+    Explanation {
+        code: "E0012",
+        title: "Compilation error",
+        description: "\
+The statement is valid syntax but can't be compiled where it is, like a
+'break' outside of a loop, an export of something other than a name, an
+empty function parameter or an integer literal too big for 64 bits.",
+        example: "break",
+    },
+    Explanation {
+        code: "E0013",
+        title: "IO error",
+        description: "\
+A module imported by its path couldn't be read. Paths are relative to the
+directory ego runs from and don't include the .ego extension.",
+        example: "import \"./missing\"",
+    },
+    Explanation {
+        code: "E0014",
+        title: "Duplicate declaration",
+        description: "\
+A name is declared again on the block that declares it, or on a block nested
+in it. Blocks don't hide the names of the blocks around them, so the second
+declaration would overwrite the first one. Assign the new value without
+'let', or use another name.
+
+The same name can be declared on sibling blocks, like the two arms of an if.",
+        example: "\
+let total = 1
+if true {
+  let total = 2
+}",
+    },
+];
+
+// looks up the code on the compiler errors and then on the vm ones
+pub fn explain(code: &str) -> Option<&'static Explanation> {
+    ERRORS
+        .iter()
+        .find(|explanation| explanation.code.eq_ignore_ascii_case(code))
+        .or_else(|| self_vm::explain(code))
+}
@@ -0,0 +1,104 @@
+use std::fs;
+
+use self_vm::{Label as ReportLabel, Renderer, Report};
+
+use crate::core::logs::log_error;
+
+use super::ErrorType;
+
+// a problem found on a module source while lexing, parsing or
+// compiling it. lines and columns start at 1
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    pub kind: ErrorType,
+    pub message: String,
+    pub line: usize,
+    pub column: usize,
+    // module the problem was found on, when it is not the one
+    // being compiled but one imported by it
+    pub file: Option<String>,
+    // other positions related to the problem
+    pub labels: Vec<Label>,
+    pub help: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Label {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl Diagnostic {
+    pub fn new(kind: ErrorType, message: &str, line: usize, column: usize) -> Diagnostic {
+        Diagnostic {
+            kind,
+            message: message.to_string(),
+            line,
+            column,
+            file: None,
+            labels: vec![],
+            help: vec![],
+        }
+    }
+
+    pub fn with_label(mut self, line: usize, column: usize, message: &str) -> Diagnostic {
+        self.labels.push(Label {
+            line,
+            column,
+            message: message.to_string(),
+        });
+        self
+    }
+
+    pub fn with_help(mut self, help: &str) -> Diagnostic {
+        self.help.push(help.to_string());
+        self
+    }
+
+    // file and source of the module being compiled, imported
+    // modules are read again from their files
+    pub fn to_report(&self, file: &str, source: &str) -> Report {
+        let imported;
+        let (file, source) = match &self.file {
+            Some(imported_file) => {
+                imported = fs::read_to_string(imported_file).unwrap_or_default();
+                (imported_file.as_str(), imported.as_str())
+            }
+            None => (file, source),
+        };
+        let label = |line: usize, column: usize, message: &str| ReportLabel {
+            line,
+            column,
+            length: 0,
+            source_line: source
+                .lines()
+                .nth(line.saturating_sub(1))
+                .unwrap_or("")
+                .to_string(),
+            message: message.to_string(),
+        };
+
+        Report {
+            code: self.kind.code().to_string(),
+            title: self.kind.label().to_string(),
+            message: self.message.clone(),
+            file: Some(file.to_string()),
+            primary: Some(label(self.line, self.column, "")),
+            secondary: self
+                .labels
+                .iter()
+                .map(|l| label(l.line, l.column, &l.message))
+                .collect(),
+            notes: self.help.clone(),
+        }
+    }
+}
+
+// logs the diagnostics of the module `file` on the error output
+pub fn report(diagnostics: &[Diagnostic], file: &str, source: &str, renderer: &Renderer) {
+    for diagnostic in diagnostics {
+        log_error(&renderer.render(&diagnostic.to_report(file, source)));
+        log_error(""); // space between diagnostics
+    }
+}
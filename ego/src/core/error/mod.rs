@@ -1,5 +1,12 @@
+mod catalogue;
+mod diagnostic;
+
+pub use catalogue::{explain, ERRORS};
+pub use diagnostic::{report, Diagnostic};
+
 use crate::log;
 
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum ErrorType {
     SyntaxError,
     EgoUsageError,
@@ -14,29 +21,57 @@ pub enum ErrorType {
     InvalidTypeAnnotation,
     CompilationError,
     IOError,
+    DuplicateDeclaration,
+}
+
+impl ErrorType {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ErrorType::SyntaxError => "Syntax error",
+            ErrorType::EgoUsageError => "Usage error",
+            ErrorType::FatalError => "Fatal error",
+            ErrorType::ParsingError => "Parsing error",
+            ErrorType::InterpretingError => "Interpreting error",
+            ErrorType::ReferenceError => "Reference error",
+            ErrorType::StackUnderflowError => "Stack underflow error",
+            ErrorType::UnknownArithmeticOperator => "Unknown arithmetic operator error",
+            ErrorType::MissingMemberError => "Missing member error",
+            ErrorType::TypeError => "Type error",
+            ErrorType::InvalidTypeAnnotation => "Invalid type annotation",
+            ErrorType::CompilationError => "Compilation error",
+            ErrorType::IOError => "IO error",
+            ErrorType::DuplicateDeclaration => "Duplicate declaration",
+        }
+    }
+
+    // stable code of the error, in the order of the enum. new
+    // variants get the next free code
+    pub fn code(&self) -> &'static str {
+        match self {
+            ErrorType::SyntaxError => "E0001",
+            ErrorType::EgoUsageError => "E0002",
+            ErrorType::FatalError => "E0003",
+            ErrorType::ParsingError => "E0004",
+            ErrorType::InterpretingError => "E0005",
+            ErrorType::ReferenceError => "E0006",
+            ErrorType::StackUnderflowError => "E0007",
+            ErrorType::UnknownArithmeticOperator => "E0008",
+            ErrorType::TypeError => "E0009",
+            ErrorType::MissingMemberError => "E0010",
+            ErrorType::InvalidTypeAnnotation => "E0011",
+            ErrorType::CompilationError => "E0012",
+            ErrorType::IOError => "E0013",
+            ErrorType::DuplicateDeclaration => "E0014",
+        }
+    }
 }
 
 pub fn throw(error_type: ErrorType, error_message: &str, line: Option<usize>) {
-    let error_string = match error_type {
-        ErrorType::SyntaxError => "Syntax error:",
-        ErrorType::EgoUsageError => "Usage error:",
-        ErrorType::FatalError => "Fatal error:",
-        ErrorType::ParsingError => "Parsing error:",
-        ErrorType::InterpretingError => "Interpreting error:",
-        ErrorType::ReferenceError => "Reference error:",
-        ErrorType::StackUnderflowError => "Stack underflow error:",
-        ErrorType::UnknownArithmeticOperator => "Unknown arithmetic operator error:",
-        ErrorType::MissingMemberError => "Missing member error: ",
-        ErrorType::TypeError => "Type Error: ",
-        ErrorType::InvalidTypeAnnotation => "Invalid type annotation: ",
-        ErrorType::CompilationError => "Compilation error: ",
-        ErrorType::IOError => "IO error: ",
-    };
+    let error_string = error_type.label();
 
-    log!("\n[ego] {error_string} {error_message}");
+    log!("\n[ego] {error_string}: {error_message}");
     if let Some(line) = line {
         log!("      └ on line: {line}");
     }
     log!(""); // space at the end
-    std::process::exit(1);
 }
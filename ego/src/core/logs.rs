@@ -18,6 +18,17 @@ pub fn log(str: &str) {
     LOG_HISTORY.lock().unwrap().push(str.to_string());
 }
 
+// same as log, on the error output
+pub fn log_error(str: &str) {
+    if cfg!(target_arch = "wasm32") {
+        console::error_1(&JsValue::from_str(str));
+    } else {
+        eprintln!("{str}");
+    }
+
+    LOG_HISTORY.lock().unwrap().push(str.to_string());
+}
+
 pub fn get_log_history() -> Vec<String> {
     LOG_HISTORY.lock().unwrap().clone()
 }
@@ -1,10 +1,11 @@
 mod ast;
-mod commands;
+pub mod commands;
 mod compiler;
 mod core;
 mod wasm;
 
 pub use compiler::gen_bytecode;
+pub use core::error::{report, Diagnostic, ErrorType};
 use wasm::run_ego;
 use wasm_bindgen::prelude::*;
 
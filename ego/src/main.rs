@@ -1,11 +1,7 @@
-mod ast;
-mod commands;
-mod compiler;
-mod core;
-
-use commands::Command;
+use ego::commands::Command;
 
 fn main() {
-    let command = Command::parse();
-    command.exec();
+    if let Err(code) = Command::parse().and_then(|command| command.exec()) {
+        std::process::exit(code);
+    }
 }
@@ -1,7 +1,9 @@
+use self_vm::Renderer;
+
 use crate::{
     ast::{lex, Module},
     compiler::Compiler,
-    core::logs::get_log_history,
+    core::{error, logs::get_log_history},
     log,
 };
 
@@ -9,11 +11,17 @@ pub fn run_ego(code: String, vm: bool) -> Vec<String> {
     log!("Executing ego:");
     log!("  '- code: {}", code);
     log!("  '- vm: {}", vm);
-    let tokens = lex(code);
+    let tokens = match lex(code.clone()) {
+        Ok(tokens) => tokens,
+        Err(diagnostics) => return failed(&diagnostics, &code),
+    };
     log!(" > Generated tokens");
 
     let mut module = Module::new("fileless".to_string(), tokens);
-    let ast = module.parse();
+    let ast = match module.parse() {
+        Ok(ast) => ast,
+        Err(diagnostics) => return failed(&diagnostics, &code),
+    };
     log!(" > Generated ast");
     log!(
         " > Running on {}",
@@ -22,8 +30,11 @@ pub fn run_ego(code: String, vm: bool) -> Vec<String> {
     log!("-------------------");
 
     // if vm {
-    let mut compiler = Compiler::new(ast);
-    let bytecode = compiler.gen_bytecode();
+    let mut compiler = Compiler::new(ast, code.clone());
+    let bytecode = match compiler.gen_bytecode() {
+        Ok(bytecode) => bytecode,
+        Err(diagnostics) => return failed(&diagnostics, &code),
+    };
     let mut vm = self_vm::vm::Vm::new(bytecode);
     vm.run(&vec![]);
     vec!["Logs with executions are not implemented yet".to_string()]
@@ -33,3 +44,9 @@ pub fn run_ego(code: String, vm: bool) -> Vec<String> {
     //     get_log_history()
     // }
 }
+
+// the logs hold the code and the reported problems
+fn failed(diagnostics: &[error::Diagnostic], code: &str) -> Vec<String> {
+    error::report(diagnostics, "fileless", code, &Renderer::new(false));
+    get_log_history()
+}
@@ -10,15 +10,26 @@ fn main() {
             return;
         }
     };
-    let bytecode = ego::gen_bytecode("main".to_string(), contents, &args);
-    let mut vm = self_vm::new(bytecode);
+    let renderer = self_vm::Renderer::from_args(&args);
+    let (bytecode, line_table) =
+        match ego::gen_bytecode("main".to_string(), contents.clone(), &args) {
+            Ok(program) => program,
+            Err(diagnostics) => {
+                ego::report(&diagnostics, "main.ego", &contents, &renderer);
+                std::process::exit(1);
+            }
+        };
+    let mut vm = self_vm::new_with_line_table(bytecode, line_table);
     if args.contains(&"-d".to_string()) {
         vm.debug_bytecode();
+        vm.set_debug(true);
         println!("\n--- RUNTIME ----------\n");
     }
     let execution = vm.run(&args);
+    if let Some(traceback) = execution.traceback {
+        eprintln!("{traceback}");
+    }
     if let Some(err) = execution.error {
-        let error_msg = format!("{}: {}", err.message, err.semantic_message);
-        eprintln!("\x1b[31m[ERR] \x1b[0m{error_msg}");
+        eprintln!("{}", renderer.render(&err.report()));
     }
 }
@@ -1,23 +1,21 @@
-use self_vm::{
-    utils::{to_bytes::bytes_from_32, to_bytes::bytes_from_64, Number},
-    vm::Vm,
-};
+use std::{env, fs};
 
+// runs a program compiled with `ego compile`
 fn main() {
-    // print two nums
-    let mut instructions: Vec<u8> = vec![0x01, 0x01];
-    instructions.extend_from_slice(&bytes_from_32(Number::U32(14)));
-    instructions.push(0x01);
-    instructions.push(0x02);
-    instructions.extend_from_slice(&bytes_from_64(Number::U64(20)));
-    instructions.push(0x02);
-    instructions.extend_from_slice(&bytes_from_32(Number::U32(2)));
+    let args: Vec<String> = env::args().skip(1).collect();
+    let path = args.first().cloned().unwrap_or("main.b".to_string());
+    let bytes = fs::read(&path).expect("cannot read the program file");
 
-    // store a variable
-    instructions.extend_from_slice(&[0x04, 0x01]);
-    instructions.extend_from_slice(&bytes_from_32(Number::U32(14)));
-    instructions.push(0x01);
-
-    let mut vm = Vm::new(instructions);
-    vm.run();
+    let mut vm = match self_vm::load(&bytes) {
+        Ok(vm) => vm,
+        Err(err) => {
+            eprintln!("{}: {}", err.message, err.semantic_message);
+            std::process::exit(2);
+        }
+    };
+    let execution = vm.run(&args);
+    if let Some(err) = execution.error {
+        eprintln!("{}: {}", err.message, err.semantic_message);
+        std::process::exit(1);
+    }
 }
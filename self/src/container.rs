@@ -0,0 +1,430 @@
+/*
+    FILE FORMAT OF A COMPILED PROGRAM. THE BYTECODE IS
+    STORED WITH WHAT IS NEEDED TO KNOW IF THIS VM CAN
+    RUN IT AND TO REPORT ERRORS ON ITS SOURCE
+*/
+
+// header (20 bytes)
+//   magic           4B  "SELF"
+//   format version  2B
+//   opcodes version 2B  OPCODES_VERSION of the compiler
+//   flags           4B  SOURCES_FLAG or none
+//   sections        4B  number of sections
+//   checksum        4B  adler-32 of everything after the header
+// section table
+//   [kind 1B][offset 4B][length 4B] for each section, offsets
+//   are counted from the file start
+// sections data
+//
+// the line table stores the name of each file, and also its
+// source when SOURCES_FLAG is set. strings are stored as
+// [u32 length][utf8 bytes] and every number is little endian
+
+use crate::{
+    core::{error::container_errors::ContainerError, line_table::LineTable},
+    opcodes::OPCODES_VERSION,
+    translator::Translator,
+};
+
+pub const MAGIC: [u8; 4] = *b"SELF";
+pub const FORMAT_VERSION: u16 = 2;
+
+// the line table embeds the source of every file
+pub const SOURCES_FLAG: u32 = 0x0000_0001;
+
+const HEADER_LENGTH: usize = 20;
+const SECTION_ENTRY_LENGTH: usize = 9;
+
+const CODE_SECTION: u8 = 0x01;
+const CONSTANTS_SECTION: u8 = 0x02;
+const LINE_TABLE_SECTION: u8 = 0x03;
+const EXPORTS_SECTION: u8 = 0x04;
+const IMPORTS_SECTION: u8 = 0x05;
+
+#[derive(Debug, Clone, Default)]
+pub struct Program {
+    // the main module bytecode, starting with its constants
+    pub bytecode: Vec<u8>,
+    pub line_table: LineTable,
+    // names exported by the main module
+    pub exports: Vec<String>,
+    // modules imported by the main module, as written on
+    // the import statements
+    pub imports: Vec<String>,
+    // store the sources to show them on errors. without them
+    // errors only point to the file, line and column
+    pub embed_sources: bool,
+}
+
+impl Program {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        // the constants of the main module are stored apart
+        // from its instructions
+        let code_start = match Translator::read_constants(&self.bytecode, 0) {
+            Ok((_, code_start)) => code_start,
+            Err(_) => 0,
+        };
+
+        let mut line_table = vec![];
+        write_u32(&mut line_table, self.line_table.files.len() as u32);
+        for file in &self.line_table.files {
+            write_string(&mut line_table, &file.name);
+            if self.embed_sources {
+                write_string(&mut line_table, &file.source);
+            }
+        }
+        write_u32(&mut line_table, self.line_table.entries.len() as u32);
+        for entry in &self.line_table.entries {
+            write_u32(&mut line_table, entry.pc as u32);
+            write_u32(&mut line_table, entry.file as u32);
+            write_u32(&mut line_table, entry.line as u32);
+            write_u32(&mut line_table, entry.column as u32);
+        }
+
+        let sections = vec![
+            (CODE_SECTION, self.bytecode[code_start..].to_vec()),
+            (CONSTANTS_SECTION, self.bytecode[..code_start].to_vec()),
+            (LINE_TABLE_SECTION, line_table),
+            (EXPORTS_SECTION, strings_section(&self.exports)),
+            (IMPORTS_SECTION, strings_section(&self.imports)),
+        ];
+
+        let mut body = vec![];
+        let mut offset = HEADER_LENGTH + sections.len() * SECTION_ENTRY_LENGTH;
+        for (kind, data) in &sections {
+            body.push(*kind);
+            write_u32(&mut body, offset as u32);
+            write_u32(&mut body, data.len() as u32);
+            offset += data.len();
+        }
+        for (_, data) in &sections {
+            body.extend_from_slice(data);
+        }
+
+        let mut bytes = vec![];
+        bytes.extend_from_slice(&MAGIC);
+        bytes.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+        bytes.extend_from_slice(&OPCODES_VERSION.to_le_bytes());
+        let flags = if self.embed_sources { SOURCES_FLAG } else { 0 };
+        write_u32(&mut bytes, flags);
+        write_u32(&mut bytes, sections.len() as u32);
+        write_u32(&mut bytes, adler32(&body));
+        bytes.extend_from_slice(&body);
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Program, ContainerError> {
+        if bytes.len() < HEADER_LENGTH || bytes[0..4] != MAGIC {
+            return Err(ContainerError::NotAProgram);
+        }
+
+        let mut header = Reader::new(bytes, 4, bytes.len(), "header");
+        let version = u16::from_le_bytes([header.u8()?, header.u8()?]);
+        if version != FORMAT_VERSION {
+            return Err(ContainerError::UnsupportedVersion {
+                found: version,
+                supported: FORMAT_VERSION,
+            });
+        }
+        let opcodes_version = u16::from_le_bytes([header.u8()?, header.u8()?]);
+        if opcodes_version != OPCODES_VERSION {
+            return Err(ContainerError::IncompatibleOpcodes {
+                found: opcodes_version,
+                supported: OPCODES_VERSION,
+            });
+        }
+        let flags = header.u32()?;
+        let sections_count = header.u32()? as usize;
+        let checksum = header.u32()?;
+
+        // a cut file fails the checksum too, the section table
+        // tells it apart from a corrupted one
+        let mut sections = vec![];
+        let mut expected =
+            HEADER_LENGTH.saturating_add(sections_count.saturating_mul(SECTION_ENTRY_LENGTH));
+        if expected <= bytes.len() {
+            let mut table = Reader::new(bytes, HEADER_LENGTH, bytes.len(), "section table");
+            for _ in 0..sections_count {
+                let kind = table.u8()?;
+                let offset = table.u32()? as usize;
+                let length = table.u32()? as usize;
+                let end = offset.saturating_add(length);
+                expected = expected.max(end);
+                sections.push((kind, offset, end));
+            }
+        }
+        if expected > bytes.len() {
+            return Err(ContainerError::Truncated {
+                expected,
+                found: bytes.len(),
+            });
+        }
+
+        let found = adler32(&bytes[HEADER_LENGTH..]);
+        if checksum != found {
+            return Err(ContainerError::ChecksumMismatch {
+                expected: checksum,
+                found,
+            });
+        }
+        if flags & !SOURCES_FLAG != 0 {
+            return Err(ContainerError::UnsupportedFlags(flags & !SOURCES_FLAG));
+        }
+
+        let mut code = None;
+        let mut constants = None;
+        let mut program = Program {
+            embed_sources: flags & SOURCES_FLAG != 0,
+            ..Program::default()
+        };
+        for (kind, offset, end) in sections {
+            let name = section_name(kind);
+            if offset < HEADER_LENGTH {
+                return Err(ContainerError::MalformedSection(name.to_string()));
+            }
+
+            let mut section = Reader::new(bytes, offset, end, name);
+            match kind {
+                CODE_SECTION => code = Some(&bytes[offset..end]),
+                CONSTANTS_SECTION => constants = Some(&bytes[offset..end]),
+                LINE_TABLE_SECTION => {
+                    program.line_table = section.line_table(program.embed_sources)?
+                }
+                EXPORTS_SECTION => program.exports = section.strings()?,
+                IMPORTS_SECTION => program.imports = section.strings()?,
+                // sections added by later versions of the format
+                _ => {}
+            }
+        }
+
+        let Some(constants) = constants else {
+            return Err(ContainerError::MissingSection("constants".to_string()));
+        };
+        let Some(code) = code else {
+            return Err(ContainerError::MissingSection("code".to_string()));
+        };
+        program.bytecode = [constants, code].concat();
+        Ok(program)
+    }
+}
+
+fn section_name(kind: u8) -> &'static str {
+    match kind {
+        CODE_SECTION => "code",
+        CONSTANTS_SECTION => "constants",
+        LINE_TABLE_SECTION => "line table",
+        EXPORTS_SECTION => "exports",
+        IMPORTS_SECTION => "imports",
+        _ => "unknown",
+    }
+}
+
+fn strings_section(strings: &[String]) -> Vec<u8> {
+    let mut section = vec![];
+    write_u32(&mut section, strings.len() as u32);
+    for string in strings {
+        write_string(&mut section, string);
+    }
+    section
+}
+
+fn write_u32(bytes: &mut Vec<u8>, value: u32) {
+    bytes.extend_from_slice(&value.to_le_bytes());
+}
+
+fn write_string(bytes: &mut Vec<u8>, value: &str) {
+    write_u32(bytes, value.len() as u32);
+    bytes.extend_from_slice(value.as_bytes());
+}
+
+fn adler32(bytes: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for byte in bytes {
+        a = (a + *byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    (b << 16) | a
+}
+
+// reads the values of a section, any read past its
+// end is reported as a malformed section
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+    end: usize,
+    section: &'a str,
+}
+
+impl<'a> Reader<'a> {
+    fn new(bytes: &'a [u8], start: usize, end: usize, section: &'a str) -> Reader<'a> {
+        Reader {
+            bytes,
+            pos: start,
+            end,
+            section,
+        }
+    }
+
+    fn take(&mut self, length: usize) -> Result<&'a [u8], ContainerError> {
+        match self.pos.checked_add(length) {
+            Some(next) if next <= self.end => {
+                let bytes = &self.bytes[self.pos..next];
+                self.pos = next;
+                Ok(bytes)
+            }
+            _ => Err(ContainerError::MalformedSection(self.section.to_string())),
+        }
+    }
+
+    fn u8(&mut self) -> Result<u8, ContainerError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32, ContainerError> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn string(&mut self) -> Result<String, ContainerError> {
+        let length = self.u32()? as usize;
+        match std::str::from_utf8(self.take(length)?) {
+            Ok(string) => Ok(string.to_string()),
+            Err(_) => Err(ContainerError::MalformedSection(self.section.to_string())),
+        }
+    }
+
+    fn strings(&mut self) -> Result<Vec<String>, ContainerError> {
+        let count = self.u32()?;
+        let mut strings = vec![];
+        for _ in 0..count {
+            strings.push(self.string()?);
+        }
+        Ok(strings)
+    }
+
+    fn line_table(&mut self, with_sources: bool) -> Result<LineTable, ContainerError> {
+        let mut line_table = LineTable::new();
+        let files = self.u32()?;
+        for _ in 0..files {
+            let name = self.string()?;
+            // without a source errors show no snippet
+            let source = if with_sources {
+                self.string()?
+            } else {
+                String::new()
+            };
+            line_table.add_file(name, source);
+        }
+        let entries = self.u32()?;
+        for _ in 0..entries {
+            let pc = self.u32()? as usize;
+            let file = self.u32()? as usize;
+            let line = self.u32()? as usize;
+            let column = self.u32()? as usize;
+            line_table.add_entry(pc, file, line, column);
+        }
+        Ok(line_table)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // one "hi" constant followed by a few code bytes
+    fn program(embed_sources: bool) -> Program {
+        let mut bytecode = vec![];
+        write_u32(&mut bytecode, 1);
+        write_string(&mut bytecode, "hi");
+        bytecode.extend_from_slice(&[0x01, 0x02, 0x03]);
+
+        let mut line_table = LineTable::new();
+        let file = line_table.add_file("main.ego".to_string(), "println(\"hi\")".to_string());
+        line_table.add_entry(0, file, 1, 1);
+
+        Program {
+            bytecode,
+            line_table,
+            exports: vec!["greet".to_string()],
+            imports: vec!["./utils".to_string()],
+            embed_sources,
+        }
+    }
+
+    #[test]
+    fn round_trip_keeps_the_program() {
+        let original = program(true);
+        let loaded = Program::from_bytes(&original.to_bytes()).expect("program not loaded");
+
+        assert_eq!(loaded.bytecode, original.bytecode);
+        assert_eq!(loaded.exports, original.exports);
+        assert_eq!(loaded.imports, original.imports);
+        assert!(loaded.embed_sources);
+        let location = loaded.line_table.lookup(0).expect("no location");
+        assert_eq!(location.file, "main.ego");
+        assert_eq!(location.source_line, "println(\"hi\")");
+    }
+
+    #[test]
+    fn sources_are_only_stored_on_request() {
+        let bytes = program(false).to_bytes();
+        let source = b"println(\"hi\")";
+        assert!(!bytes.windows(source.len()).any(|window| window == source));
+
+        let loaded = Program::from_bytes(&bytes).expect("program not loaded");
+        assert!(!loaded.embed_sources);
+        let location = loaded.line_table.lookup(0).expect("no location");
+        assert_eq!((location.file.as_str(), location.line), ("main.ego", 1));
+        assert_eq!(location.source_line, "");
+    }
+
+    #[test]
+    fn corrupted_program_fails_the_checksum() {
+        let mut bytes = program(false).to_bytes();
+        let last = bytes.len() - 1;
+        bytes[last] ^= 0xff;
+        assert!(matches!(
+            Program::from_bytes(&bytes),
+            Err(ContainerError::ChecksumMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn truncated_program_is_not_reported_as_corrupted() {
+        let bytes = program(false).to_bytes();
+        for length in [HEADER_LENGTH + 3, bytes.len() - 1] {
+            match Program::from_bytes(&bytes[..length]) {
+                Err(ContainerError::Truncated { expected, found }) => {
+                    assert_eq!(found, length);
+                    assert!(expected > length);
+                }
+                other => panic!("cut at {length} gave {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn other_versions_are_rejected() {
+        let mut bytes = program(false).to_bytes();
+        bytes[4..6].copy_from_slice(&(FORMAT_VERSION + 1).to_le_bytes());
+        assert!(matches!(
+            Program::from_bytes(&bytes),
+            Err(ContainerError::UnsupportedVersion { .. })
+        ));
+
+        let mut bytes = program(false).to_bytes();
+        bytes[6..8].copy_from_slice(&(OPCODES_VERSION + 1).to_le_bytes());
+        assert!(matches!(
+            Program::from_bytes(&bytes),
+            Err(ContainerError::IncompatibleOpcodes { .. })
+        ));
+    }
+
+    #[test]
+    fn other_files_are_not_programs() {
+        assert!(matches!(
+            Program::from_bytes(b"println(1)\nprintln(2)\n"),
+            Err(ContainerError::NotAProgram)
+        ));
+    }
+}
@@ -3,3 +3,13 @@ pub enum ActionError {
     InvalidModule(String),
     InvalidMember { module: String, member: String },
 }
+
+impl ActionError {
+    // stable code of the error, see VMErrorType::code
+    pub fn code(&self) -> &'static str {
+        match self {
+            ActionError::InvalidModule(_) => "V0501",
+            ActionError::InvalidMember { .. } => "V0502",
+        }
+    }
+}
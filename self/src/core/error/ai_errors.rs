@@ -4,3 +4,14 @@ pub enum AIError {
     AIEngineNotSet(),
     AIEngineNotImplemented(String),
 }
+
+impl AIError {
+    // stable code of the error, see VMErrorType::code
+    pub fn code(&self) -> &'static str {
+        match self {
+            AIError::AIFetchError(_) => "V0401",
+            AIError::AIEngineNotSet() => "V0402",
+            AIError::AIEngineNotImplemented(_) => "V0403",
+        }
+    }
+}
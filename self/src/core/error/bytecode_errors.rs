@@ -0,0 +1,68 @@
+// bytecode the compiler would never emit. the vm can be fed
+// any bytes, so these are reported instead of trusted
+#[derive(Debug)]
+pub enum BytecodeError {
+    UnknownOpcode {
+        opcode: u8,
+        at: usize,
+    },
+    // the operands of the instruction go past the bytecode end
+    TruncatedInstruction {
+        instruction: String,
+        at: usize,
+    },
+    InvalidOperand {
+        instruction: String,
+        at: usize,
+    },
+    UnsupportedDataType {
+        data_type: String,
+        at: usize,
+    },
+    InvalidUtf8 {
+        at: usize,
+    },
+    // an index past the module constants section
+    UnknownConstant {
+        index: u32,
+        available: usize,
+        at: usize,
+    },
+    // a function or module body longer than the bytecode left
+    BodyOutOfBounds {
+        instruction: String,
+        at: usize,
+        body_length: usize,
+        available: usize,
+    },
+    // jumps must land on the start of an instruction
+    InvalidJumpTarget {
+        instruction: String,
+        at: usize,
+        target: isize,
+    },
+    // found while simulating the operands stack depth
+    StackUnderflow {
+        instruction: String,
+        at: usize,
+        needed: usize,
+        available: usize,
+    },
+}
+
+impl BytecodeError {
+    // stable code of the error, see VMErrorType::code
+    pub fn code(&self) -> &'static str {
+        match self {
+            BytecodeError::UnknownOpcode { .. } => "V0901",
+            BytecodeError::TruncatedInstruction { .. } => "V0902",
+            BytecodeError::InvalidOperand { .. } => "V0903",
+            BytecodeError::UnsupportedDataType { .. } => "V0904",
+            BytecodeError::InvalidUtf8 { .. } => "V0905",
+            BytecodeError::UnknownConstant { .. } => "V0906",
+            BytecodeError::BodyOutOfBounds { .. } => "V0907",
+            BytecodeError::InvalidJumpTarget { .. } => "V0908",
+            BytecodeError::StackUnderflow { .. } => "V0909",
+        }
+    }
+}
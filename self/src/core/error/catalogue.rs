@@ -0,0 +1,590 @@
+/*
+    EXPLANATION OF EVERY ERROR CODE OF THE VM, PRINTED BY
+    `ego explain <code>`. A CODE IS NEVER REMOVED FROM HERE,
+    ALSO WHEN THE ERROR IS NOT RAISED ANYMORE
+*/
+
+// every VMErrorType::code needs its entry, in the same order
+#[derive(Debug)]
+pub struct Explanation {
+    pub code: &'static str,
+    pub title: &'static str,
+    pub description: &'static str,
+    // ego source raising the error, or the steps leading to it
+    // when no ego program can
+    pub example: &'static str,
+}
+
+pub const VM_ERRORS: &[Explanation] = &[
+    Explanation {
+        code: "V0001",
+        title: "Type coercion error",
+        description: "\
+A binary operation mixes a value stored on the heap, like a string, a vector
+or a map, with a raw value, like a number or a bool. Ego never converts one
+into the other implicitly.
+
+To print them together pass them as separate arguments: println(\"total:\", n)",
+        example: "\
+let n = 1
+println(\"total: \" + n)",
+    },
+    Explanation {
+        code: "V0002",
+        title: "Type mismatch error",
+        description: "\
+A value of one type was found where another one was expected. It is raised
+when a struct field is assigned a value of a type other than the one it was
+declared with, and by the native functions receiving arguments of the wrong
+type.",
+        example: "\
+struct Point {
+  x: number
+}
+
+let p = Point { x: 1 }
+p.x = \"one\"",
+    },
+    Explanation {
+        code: "V0003",
+        title: "Invalid binary operation",
+        description: "\
+The operator is not defined for the types of its operands. Strings only
+support '+', and bools can't be used in arithmetic.",
+        example: "println(true + false)",
+    },
+    Explanation {
+        code: "V0004",
+        title: "Invalid division",
+        description: "\
+The right operand of a division is 0. Check the divisor before dividing.",
+        example: "\
+let n = 1
+println(n / 0)",
+    },
+    Explanation {
+        code: "V0005",
+        title: "Undeclared identifier",
+        description: "\
+A name is read but it has no value yet. The compiler rejects the names that
+are never declared with E0006, so at runtime it happens when a function reads
+a name of the module before the module declares it, or a name declared in a
+branch that didn't run.",
+        example: "\
+fn total() {
+  return count
+}
+
+println(total())
+let count = 1",
+    },
+    Explanation {
+        code: "V0006",
+        title: "Not callable member",
+        description: "\
+A value that is not a function or a method was called. It also happens when
+calling a member a struct or a module does not have.",
+        example: "\
+let n = 1
+n()",
+    },
+    Explanation {
+        code: "V0007",
+        title: "Module not found",
+        description: "\
+Reserved for an import naming a module that neither the standard library nor
+the file system provides. The compiler rejects these imports with E0006, so
+the vm does not raise it.",
+        example: "\
+import maths   // rejected by the compiler with E0006",
+    },
+    Explanation {
+        code: "V0008",
+        title: "Export invalid member type",
+        description: "\
+An export instruction received something other than the name of a member,
+like a number. The compiler rejects these exports with E0012, so it only
+comes from bytecode not produced by ego.",
+        example: "\
+ego exec handmade.b",
+    },
+    Explanation {
+        code: "V0009",
+        title: "Stack underflow",
+        description: "\
+An instruction needed more values than the operands stack held. The verifier
+catches it before running as V0909, so it only happens when the bytecode is
+run with --no-verify.",
+        example: "\
+ego exec --no-verify broken.b",
+    },
+    Explanation {
+        code: "V0010",
+        title: "Stale handle",
+        description: "\
+A reference points to an object the garbage collector already freed. The
+slot of a freed object is reused, so the vm stops instead of reading the new
+value. Ego programs can't keep references the collector doesn't see, so this
+points to a bug in the vm or in one of its native functions. Please report it
+with the program that raised it.",
+        example: "\
+import self
+
+self.gc()   // a native function holding a freed object fails after this",
+    },
+    Explanation {
+        code: "V0011",
+        title: "Thrown error",
+        description: "\
+An error raised by a 'throw' statement was not caught. Wrap the call in a
+'try' block to handle it, the caught error has the thrown message.",
+        example: "\
+fn check(age) {
+  if age < 0 {
+    throw \"age can't be negative\"
+  }
+}
+
+check(0 - 1)",
+    },
+    Explanation {
+        code: "V0012",
+        title: "Invalid bytecode",
+        description: "\
+The verifier found problems in the bytecode before running it, and nothing
+was executed. Every problem is listed under the error, each one is explained
+by its own V09xx code. The program was not produced by this version of ego,
+programs modified after compiling them fail the checksum first with V1005.",
+        example: "\
+ego exec handmade.b",
+    },
+    Explanation {
+        code: "V0013",
+        title: "Integer overflow",
+        description: "\
+The result of an arithmetic operation between integers doesn't fit in their
+type. Whole numbers written in ego are 32 bits integers, from -2147483648 to
+2147483647, and only the literals out of that range are 64 bits ones. Dividing
+the smallest value of a type by -1 overflows too.",
+        example: "\
+let big = 2147483647
+println(big + 1)",
+    },
+    Explanation {
+        code: "V0101",
+        title: "Invalid args count",
+        description: "\
+A native function was called with fewer arguments than it needs.",
+        example: "\
+let m = {\"a\": 1}
+m.has()",
+    },
+    Explanation {
+        code: "V0201",
+        title: "File not found",
+        description: "\
+The path given to a function of the 'fs' module does not exist. Relative
+paths start from the directory the program runs from.",
+        example: "\
+import fs
+fs.read_file(\"missing.txt\")",
+    },
+    Explanation {
+        code: "V0202",
+        title: "Not a file",
+        description: "\
+The path given to 'fs.read_file' exists but is not a regular file, like a
+directory.",
+        example: "\
+import fs
+fs.read_file(\".\")",
+    },
+    Explanation {
+        code: "V0203",
+        title: "Read error",
+        description: "\
+The file exists but can't be read, usually because of its permissions or
+because its content is not valid utf8.",
+        example: "\
+import fs
+fs.read_file(\"image.png\")   // the file exists, but holds binary data",
+    },
+    Explanation {
+        code: "V0204",
+        title: "Write error",
+        description: "\
+'fs.write_file' couldn't write the file, usually because its directory does
+not exist or because of the permissions of the file or of its directory.",
+        example: "\
+import fs
+fs.write_file(\"missing/notes.txt\", \"hi\", true)",
+    },
+    Explanation {
+        code: "V0205",
+        title: "Delete error",
+        description: "\
+'fs.delete' couldn't remove the path, usually because of its permissions or
+because it is a directory. Directories are removed passing true as the
+second argument, which deletes all their content.",
+        example: "\
+import fs
+fs.delete(\".\")",
+    },
+    Explanation {
+        code: "V0301",
+        title: "Os error",
+        description: "\
+Reserved for the errors of the 'os' module. None of its functions fail at
+the moment.",
+        example: "\
+import os
+println(os.get_cwd())",
+    },
+    Explanation {
+        code: "V0401",
+        title: "AI fetch error",
+        description: "\
+The request to the AI engine failed, or the engine answered with something
+that is not a chat response. The error has the reason, check that the api key
+of the engine is set (OPENAI_API_KEY or MISTRAL_API_KEY) and the network.",
+        example: "\
+import ai
+ai.infer(\"a name for a cat\", \"\")",
+    },
+    Explanation {
+        code: "V0402",
+        title: "AI engine not set",
+        description: "\
+The 'ai' module needs the SELF_AI_ENGINE environment variable to know which
+engine to use. The supported ones are openai and mistral.",
+        example: "\
+import ai
+ai.infer(\"a name for a cat\", \"\")   // without SELF_AI_ENGINE",
+    },
+    Explanation {
+        code: "V0403",
+        title: "AI engine not implemented",
+        description: "\
+SELF_AI_ENGINE names an engine the 'ai' module does not support. The
+supported ones are openai and mistral.",
+        example: "\
+import ai
+ai.infer(\"a name for a cat\", \"\")   // with SELF_AI_ENGINE=llama",
+    },
+    Explanation {
+        code: "V0501",
+        title: "Invalid module",
+        description: "\
+An action returned by 'ai.do' names a module that does not exist in the
+standard library. The engine answered with something the vm can't run.",
+        example: "\
+import ai
+ai.do(\"send an email\")   // the engine answers with an 'email' module",
+    },
+    Explanation {
+        code: "V0502",
+        title: "Invalid member",
+        description: "\
+An action returned by 'ai.do' names a member its module does not have. The
+engine answered with something the vm can't run.",
+        example: "\
+import ai
+ai.do(\"remove old.txt\")   // the engine answers with 'fs.remove'",
+    },
+    Explanation {
+        code: "V0601",
+        title: "Network connection error",
+        description: "\
+'net.connect' couldn't open a connection to the host. Check the address, the
+port and that something is listening on it.",
+        example: "\
+import net
+net.connect(\"localhost:1\")",
+    },
+    Explanation {
+        code: "V0602",
+        title: "Socket write error",
+        description: "\
+Writing to an open connection failed, usually because the other side closed
+it.",
+        example: "\
+import net
+let conn = net.connect(\"localhost:8080\")
+conn.write(\"hi\")   // after the server closed the connection",
+    },
+    Explanation {
+        code: "V0603",
+        title: "Socket read error",
+        description: "\
+Reading from an open connection failed, usually because the other side closed
+it.",
+        example: "\
+import net
+let conn = net.connect(\"localhost:8080\")
+conn.read()   // after the server closed the connection",
+    },
+    Explanation {
+        code: "V0701",
+        title: "Field not found",
+        description: "\
+The member accessed does not exist on the struct or on the module. Check the
+declaration of the struct or the members the module exports.",
+        example: "\
+struct Point {
+  x: number
+}
+
+let p = Point { x: 1 }
+println(p.y)",
+    },
+    Explanation {
+        code: "V0801",
+        title: "Index out of range",
+        description: "\
+The index is not lower than the length of the vector. Indexes start at 0, so
+the last element is at len - 1.",
+        example: "\
+let v = [1, 2]
+println(v[5])",
+    },
+    Explanation {
+        code: "V0802",
+        title: "Negative index",
+        description: "\
+Vectors are indexed from 0 and negative indexes are not supported to count
+from the end.",
+        example: "\
+let v = [1, 2]
+println(v[0 - 1])",
+    },
+    Explanation {
+        code: "V0803",
+        title: "Invalid index type",
+        description: "\
+Vectors are indexed by integers. Strings are the keys of maps.",
+        example: "\
+let v = [1, 2]
+println(v[\"a\"])",
+    },
+    Explanation {
+        code: "V0804",
+        title: "Not indexable",
+        description: "\
+Only vectors and maps can be indexed with '[...]'. Struct fields are
+accessed with '.'.",
+        example: "\
+let n = 5
+println(n[0])",
+    },
+    Explanation {
+        code: "V0805",
+        title: "Key not found",
+        description: "\
+The map has no value under that key. Check it first with 'has', or assign it
+before reading it.",
+        example: "\
+let m = {\"a\": 1}
+println(m[\"b\"])",
+    },
+    Explanation {
+        code: "V0806",
+        title: "Invalid key type",
+        description: "\
+Maps are indexed by strings.",
+        example: "\
+let m = {\"a\": 1}
+println(m[1])",
+    },
+    Explanation {
+        code: "V0901",
+        title: "Unknown opcode",
+        description: "\
+A byte that should start an instruction is not an opcode of this vm. The
+bytecode was produced by another version of ego or written by hand.",
+        example: "\
+ego exec handmade.b",
+    },
+    Explanation {
+        code: "V0902",
+        title: "Truncated instruction",
+        description: "\
+The bytecode ends in the middle of the operands of an instruction. A program
+file that was cut is reported as V1008 before, so the bytecode was written
+wrong.",
+        example: "\
+ego exec handmade.b",
+    },
+    Explanation {
+        code: "V0903",
+        title: "Invalid operand",
+        description: "\
+An operand holds a value its instruction doesn't accept. The bytecode was
+not produced by this version of ego or was modified.",
+        example: "\
+ego exec handmade.b",
+    },
+    Explanation {
+        code: "V0904",
+        title: "Unsupported data type",
+        description: "\
+A value in the bytecode has a data type the vm can't load. The bytecode was
+not produced by this version of ego or was modified.",
+        example: "\
+ego exec handmade.b",
+    },
+    Explanation {
+        code: "V0905",
+        title: "Invalid utf8",
+        description: "\
+A string stored in the bytecode is not valid utf8, usually because the file
+was modified.",
+        example: "\
+ego exec handmade.b",
+    },
+    Explanation {
+        code: "V0906",
+        title: "Unknown constant",
+        description: "\
+An instruction refers to a constant past the end of the constants of its
+module. The bytecode was not produced by this version of ego or was modified.",
+        example: "\
+ego exec handmade.b",
+    },
+    Explanation {
+        code: "V0907",
+        title: "Body out of bounds",
+        description: "\
+A function or module body is declared longer than the bytecode left after
+it. A program file that was cut is reported as V1008 before, so the bytecode
+was written wrong.",
+        example: "\
+ego exec handmade.b",
+    },
+    Explanation {
+        code: "V0908",
+        title: "Invalid jump target",
+        description: "\
+A jump lands outside of its body or in the middle of an instruction, instead
+of on the start of one.",
+        example: "\
+ego exec handmade.b",
+    },
+    Explanation {
+        code: "V0909",
+        title: "Stack underflow",
+        description: "\
+Simulating the bytecode, the verifier found an instruction popping more
+values than the operands stack would hold. Running it would raise V0009.",
+        example: "\
+ego exec handmade.b",
+    },
+    Explanation {
+        code: "V1001",
+        title: "Not a program",
+        description: "\
+The file doesn't start like a compiled self program. Programs are created
+with 'ego compile', and sources are run with 'ego run' instead.",
+        example: "\
+ego exec main.ego",
+    },
+    Explanation {
+        code: "V1002",
+        title: "Unsupported program version",
+        description: "\
+The program uses a version of the file format this vm doesn't read, because
+it was compiled by a newer or an older ego. Compile it again with this
+version of ego.",
+        example: "\
+ego exec app.b",
+    },
+    Explanation {
+        code: "V1003",
+        title: "Incompatible opcodes",
+        description: "\
+The program was compiled against another opcodes table than the one of this
+vm, so its instructions would be misread. It happens with programs compiled
+by another version of ego, compile it again with this one.",
+        example: "\
+ego exec app.b",
+    },
+    Explanation {
+        code: "V1004",
+        title: "Unsupported flags",
+        description: "\
+The program header sets flags this vm doesn't know, usually for a feature of
+a newer version of ego.",
+        example: "\
+ego exec app.b",
+    },
+    Explanation {
+        code: "V1005",
+        title: "Corrupted program",
+        description: "\
+The checksum stored in the program doesn't match its content, so the file was
+modified or damaged after compiling it. Compile it again.",
+        example: "\
+ego compile main.ego app.b
+printf 'x' | dd of=app.b bs=1 seek=100 conv=notrunc
+ego exec app.b",
+    },
+    Explanation {
+        code: "V1006",
+        title: "Missing section",
+        description: "\
+The program lacks one of the sections every compiled program has, like the
+bytecode or the line table.",
+        example: "\
+ego exec handmade.b",
+    },
+    Explanation {
+        code: "V1007",
+        title: "Malformed section",
+        description: "\
+The content of a section doesn't match its layout, like a line table string
+declared longer than the section holding it. The checksum matched, so the
+file was written wrong instead of damaged later.",
+        example: "\
+ego exec handmade.b",
+    },
+    Explanation {
+        code: "V1008",
+        title: "Truncated program",
+        description: "\
+The file is shorter than its section table says, usually because it was cut
+while copying or downloading it. Copy or compile it again.",
+        example: "\
+ego compile main.ego app.b
+truncate -s -16 app.b
+ego exec app.b",
+    },
+    Explanation {
+        code: "V1101",
+        title: "Missing handler name",
+        description: "\
+'call' runs a foreign handler and needs its name as the first argument.",
+        example: "call()",
+    },
+    Explanation {
+        code: "V1102",
+        title: "Handler not found",
+        description: "\
+The handler passed to 'call' is not registered. Foreign handlers are
+declared in the foreign.toml file of the directory the program runs from.",
+        example: "call(\"resize\", \"photo.png\")",
+    },
+    Explanation {
+        code: "V1103",
+        title: "Cannot spawn foreign handler",
+        description: "\
+The runtime of the foreign handler couldn't be started. Check that the
+runtime declared in foreign.toml is installed and in the PATH.",
+        example: "\
+call(\"resize\", \"photo.png\")   // foreign.toml runs it with a missing binary",
+    },
+];
+
+pub fn explain(code: &str) -> Option<&'static Explanation> {
+    VM_ERRORS
+        .iter()
+        .find(|explanation| explanation.code.eq_ignore_ascii_case(code))
+}
@@ -0,0 +1,31 @@
+// a compiled program file this vm can't run
+#[derive(Debug)]
+pub enum ContainerError {
+    // the file doesn't start with the magic bytes
+    NotAProgram,
+    UnsupportedVersion { found: u16, supported: u16 },
+    // compiled against another opcodes table
+    IncompatibleOpcodes { found: u16, supported: u16 },
+    UnsupportedFlags(u32),
+    ChecksumMismatch { expected: u32, found: u32 },
+    // the file is shorter than its sections say
+    Truncated { expected: usize, found: usize },
+    MissingSection(String),
+    MalformedSection(String),
+}
+
+impl ContainerError {
+    // stable code of the error, see VMErrorType::code
+    pub fn code(&self) -> &'static str {
+        match self {
+            ContainerError::NotAProgram => "V1001",
+            ContainerError::UnsupportedVersion { .. } => "V1002",
+            ContainerError::IncompatibleOpcodes { .. } => "V1003",
+            ContainerError::UnsupportedFlags(_) => "V1004",
+            ContainerError::ChecksumMismatch { .. } => "V1005",
+            ContainerError::MissingSection(_) => "V1006",
+            ContainerError::MalformedSection(_) => "V1007",
+            ContainerError::Truncated { .. } => "V1008",
+        }
+    }
+}
@@ -0,0 +1,17 @@
+#[derive(Debug)]
+pub enum FfiError {
+    MissingHandlerName,
+    HandlerNotFound(String),
+    SpawnError(String),
+}
+
+impl FfiError {
+    // stable code of the error, see VMErrorType::code
+    pub fn code(&self) -> &'static str {
+        match self {
+            FfiError::MissingHandlerName => "V1101",
+            FfiError::HandlerNotFound(_) => "V1102",
+            FfiError::SpawnError(_) => "V1103",
+        }
+    }
+}
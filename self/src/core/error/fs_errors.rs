@@ -6,3 +6,16 @@ pub enum FsError {
     WriteError(String),
     DeleteError(String),
 }
+
+impl FsError {
+    // stable code of the error, see VMErrorType::code
+    pub fn code(&self) -> &'static str {
+        match self {
+            FsError::FileNotFound(_) => "V0201",
+            FsError::NotAFile(_) => "V0202",
+            FsError::ReadError(_) => "V0203",
+            FsError::WriteError(_) => "V0204",
+            FsError::DeleteError(_) => "V0205",
+        }
+    }
+}
@@ -0,0 +1,23 @@
+#[derive(Debug)]
+pub enum IndexError {
+    OutOfRange { index: usize, length: usize },
+    NegativeIndex(i64),
+    InvalidIndexType(String),
+    NotIndexable(String),
+    KeyNotFound(String),
+    InvalidKeyType(String),
+}
+
+impl IndexError {
+    // stable code of the error, see VMErrorType::code
+    pub fn code(&self) -> &'static str {
+        match self {
+            IndexError::OutOfRange { .. } => "V0801",
+            IndexError::NegativeIndex(_) => "V0802",
+            IndexError::InvalidIndexType(_) => "V0803",
+            IndexError::NotIndexable(_) => "V0804",
+            IndexError::KeyNotFound(_) => "V0805",
+            IndexError::InvalidKeyType(_) => "V0806",
+        }
+    }
+}
@@ -1,6 +1,11 @@
 pub mod action_errors;
 pub mod ai_errors;
+pub mod bytecode_errors;
+pub mod catalogue;
+pub mod container_errors;
+pub mod ffi_errors;
 pub mod fs_errors;
+pub mod index_errors;
 pub mod net_errors;
 pub mod os_errors;
 pub mod struct_errors;
@@ -8,14 +13,23 @@ pub mod type_errors;
 
 use crate::{
     core::error::{
-        action_errors::ActionError, ai_errors::AIError, fs_errors::FsError, net_errors::NetErrors,
-        os_errors::OsError, struct_errors::StructError, type_errors::TypeError,
+        action_errors::ActionError, ai_errors::AIError, bytecode_errors::BytecodeError,
+        container_errors::ContainerError, ffi_errors::FfiError, fs_errors::FsError,
+        index_errors::IndexError, net_errors::NetErrors, os_errors::OsError,
+        struct_errors::StructError, type_errors::TypeError,
     },
+    memory::Handle,
     opcodes::DataType,
     stack::OperandsStackValue,
     vm::Vm,
 };
 
+use super::{
+    line_table::SourceLocation,
+    report::{Label, Report},
+    traceback::Traceback,
+};
+
 #[derive(Debug)]
 pub enum VMErrorType {
     TypeCoercionError(OperandsStackValue), // maybe here we should have a more generic value, we'll see with time
@@ -23,6 +37,8 @@ pub enum VMErrorType {
     TypeError(TypeError),
     InvalidBinaryOperation(InvalidBinaryOperation),
     DivisionByZero(OperandsStackValue),
+    // the result doesn't fit in the type of its operands
+    IntegerOverflow(IntegerOverflow),
     UndeclaredIdentifierError(String),
     NotCallableError(String),
     ModuleNotFound(String),
@@ -33,6 +49,103 @@ pub enum VMErrorType {
     Action(ActionError),
     Net(NetErrors),
     Struct(StructError),
+    Index(IndexError),
+    Bytecode(BytecodeError),
+    // every problem found by the verifier before running
+    Verification(Vec<BytecodeError>),
+    // a program file that can't be loaded
+    Container(ContainerError),
+    Ffi(FfiError),
+    StackUnderflow { needed: usize, available: usize },
+    // a handle to an object that was already freed
+    StaleHandle(Handle),
+    // raised by a throw statement
+    Thrown(Box<ThrownError>),
+}
+
+impl VMErrorType {
+    // name exposed as `error_type` to ego code catching the error
+    pub fn name(&self) -> String {
+        match self {
+            VMErrorType::TypeCoercionError(_) => "TypeCoercionError".to_string(),
+            VMErrorType::TypeMismatch { .. } => "TypeMismatch".to_string(),
+            VMErrorType::TypeError(_) => "TypeError".to_string(),
+            VMErrorType::InvalidBinaryOperation(_) => "InvalidBinaryOperation".to_string(),
+            VMErrorType::DivisionByZero(_) => "DivisionByZero".to_string(),
+            VMErrorType::IntegerOverflow(_) => "IntegerOverflow".to_string(),
+            VMErrorType::UndeclaredIdentifierError(_) => "UndeclaredIdentifierError".to_string(),
+            VMErrorType::NotCallableError(_) => "NotCallableError".to_string(),
+            VMErrorType::ModuleNotFound(_) => "ModuleNotFound".to_string(),
+            VMErrorType::ExportInvalidMemberType => "ExportInvalidMemberType".to_string(),
+            VMErrorType::Fs(_) => "FsError".to_string(),
+            VMErrorType::Os(_) => "OsError".to_string(),
+            VMErrorType::AI(_) => "AIError".to_string(),
+            VMErrorType::Action(_) => "ActionError".to_string(),
+            VMErrorType::Net(_) => "NetError".to_string(),
+            VMErrorType::Struct(_) => "StructError".to_string(),
+            VMErrorType::Index(_) => "IndexError".to_string(),
+            VMErrorType::Bytecode(_) => "BytecodeError".to_string(),
+            VMErrorType::Verification(_) => "VerificationError".to_string(),
+            VMErrorType::Container(_) => "ContainerError".to_string(),
+            VMErrorType::Ffi(_) => "FfiError".to_string(),
+            VMErrorType::StackUnderflow { .. } => "StackUnderflow".to_string(),
+            VMErrorType::StaleHandle(_) => "StaleHandle".to_string(),
+            VMErrorType::Thrown(thrown) => thrown.error_type.clone(),
+        }
+    }
+}
+
+impl VMErrorType {
+    // stable code of the error. the errors of the vm itself use
+    // V00xx and each group of errors its own hundred: V01xx for
+    // TypeError, V02xx Fs, V03xx Os, V04xx AI, V05xx Action,
+    // V06xx Net, V07xx Struct, V08xx Index, V09xx Bytecode,
+    // V10xx Container and V11xx Ffi. codes are never reused
+    pub fn code(&self) -> &'static str {
+        match self {
+            VMErrorType::TypeCoercionError(_) => "V0001",
+            VMErrorType::TypeMismatch { .. } => "V0002",
+            VMErrorType::InvalidBinaryOperation(_) => "V0003",
+            VMErrorType::DivisionByZero(_) => "V0004",
+            VMErrorType::UndeclaredIdentifierError(_) => "V0005",
+            VMErrorType::NotCallableError(_) => "V0006",
+            VMErrorType::ModuleNotFound(_) => "V0007",
+            VMErrorType::ExportInvalidMemberType => "V0008",
+            VMErrorType::StackUnderflow { .. } => "V0009",
+            VMErrorType::StaleHandle(_) => "V0010",
+            VMErrorType::Thrown(_) => "V0011",
+            VMErrorType::Verification(_) => "V0012",
+            VMErrorType::IntegerOverflow(_) => "V0013",
+            VMErrorType::TypeError(error) => error.code(),
+            VMErrorType::Fs(error) => error.code(),
+            VMErrorType::Os(error) => error.code(),
+            VMErrorType::AI(error) => error.code(),
+            VMErrorType::Action(error) => error.code(),
+            VMErrorType::Net(error) => error.code(),
+            VMErrorType::Struct(error) => error.code(),
+            VMErrorType::Index(error) => error.code(),
+            VMErrorType::Bytecode(error) => error.code(),
+            VMErrorType::Container(error) => error.code(),
+            VMErrorType::Ffi(error) => error.code(),
+        }
+    }
+
+    // hint shown under the error, for the ones with a usual fix
+    pub fn help(&self) -> Option<&'static str> {
+        match self {
+            VMErrorType::UndeclaredIdentifierError(_) => {
+                Some("declare it with 'let' before using it")
+            }
+            VMErrorType::NotCallableError(_) => Some("only functions and methods can be called"),
+            VMErrorType::ModuleNotFound(_) => {
+                Some("custom modules are imported by their path, like import \"./utils\"")
+            }
+            VMErrorType::Container(ContainerError::IncompatibleOpcodes { .. }) => {
+                Some("compile the program again with this version of ego")
+            }
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -40,6 +153,34 @@ pub struct VMError {
     pub error_type: VMErrorType,
     pub message: String,
     pub semantic_message: String,
+    // ego source of the failing instruction
+    pub location: Option<Box<SourceLocation>>,
+    pub traceback: Box<Traceback>,
+}
+
+impl VMError {
+    pub fn report(&self) -> Report {
+        Report {
+            code: self.error_type.code().to_string(),
+            title: self.message.clone(),
+            message: self.semantic_message.clone(),
+            file: self.location.as_ref().map(|location| location.file.clone()),
+            primary: self.location.as_ref().map(|location| Label {
+                line: location.line,
+                column: location.column,
+                length: 0,
+                source_line: location.source_line.clone(),
+                message: String::new(),
+            }),
+            secondary: vec![],
+            notes: self
+                .error_type
+                .help()
+                .into_iter()
+                .map(String::from)
+                .collect(),
+        }
+    }
 }
 
 pub fn throw(error_type: VMErrorType, vm: &Vm) -> VMError {
@@ -84,6 +225,10 @@ pub fn throw(error_type: VMErrorType, vm: &Vm) -> VMError {
                 format!("Cannot devide {source} by 0",),
             )
         }
+        VMErrorType::IntegerOverflow(v) => (
+            "Integer overflow".to_string(),
+            format!("{} doesn't fit in {}", v.operation, v.data_type.as_str()),
+        ),
         VMErrorType::UndeclaredIdentifierError(v) => {
             ("Undeclared identifier".to_string(), format!("{}", v))
         }
@@ -132,7 +277,7 @@ pub fn throw(error_type: VMErrorType, vm: &Vm) -> VMError {
                 format!("couldn't write to {}", s),
             ),
             NetErrors::ReadError(s) => (
-                "Socket write error".to_string(),
+                "Socket read error".to_string(),
                 format!("couldn't read from {}", s),
             ),
         },
@@ -142,18 +287,216 @@ pub fn throw(error_type: VMErrorType, vm: &Vm) -> VMError {
                 format!("'{}' on {}", field, struct_type),
             ),
         },
+        VMErrorType::Index(index) => match index {
+            IndexError::OutOfRange { index, length } => (
+                "Index out of range".to_string(),
+                format!("index {} on a vector of length {}", index, length),
+            ),
+            IndexError::NegativeIndex(i) => (
+                "Negative index".to_string(),
+                format!("{} cannot be used as an index", i),
+            ),
+            IndexError::InvalidIndexType(t) => (
+                "Invalid index type".to_string(),
+                format!("expected an integer, received {}", t),
+            ),
+            IndexError::NotIndexable(t) => ("Not indexable".to_string(), t.clone()),
+            IndexError::KeyNotFound(k) => (
+                "Key not found".to_string(),
+                format!("\"{}\" is not a key of the map", k),
+            ),
+            IndexError::InvalidKeyType(t) => (
+                "Invalid key type".to_string(),
+                format!("expected a string, received {}", t),
+            ),
+        },
+        VMErrorType::Bytecode(bytecode) => bytecode_error_message(bytecode),
+        VMErrorType::Verification(problems) => {
+            let mut semantic_message = match problems.len() {
+                1 => "1 problem found".to_string(),
+                n => format!("{n} problems found"),
+            };
+            for problem in problems {
+                let (message, semantic) = bytecode_error_message(problem);
+                semantic_message += format!("\n  - {message}: {semantic}").as_str();
+            }
+            ("Invalid bytecode".to_string(), semantic_message)
+        }
+        VMErrorType::Container(container) => match container {
+            ContainerError::NotAProgram => (
+                "Not a program".to_string(),
+                "the file is not a compiled self program".to_string(),
+            ),
+            ContainerError::UnsupportedVersion { found, supported } => (
+                "Unsupported program version".to_string(),
+                format!(
+                    "the program uses the format version {}, this vm supports {}",
+                    found, supported
+                ),
+            ),
+            ContainerError::IncompatibleOpcodes { found, supported } => (
+                "Incompatible opcodes".to_string(),
+                format!(
+                    "the program was compiled for the opcodes version {}, this vm runs {}. compile it again",
+                    found, supported
+                ),
+            ),
+            ContainerError::UnsupportedFlags(flags) => (
+                "Unsupported flags".to_string(),
+                format!("0x{:08x} are not known by this vm", flags),
+            ),
+            ContainerError::ChecksumMismatch { expected, found } => (
+                "Corrupted program".to_string(),
+                format!(
+                    "checksum 0x{:08x} expected, the file has 0x{:08x}",
+                    expected, found
+                ),
+            ),
+            ContainerError::MissingSection(section) => (
+                "Missing section".to_string(),
+                format!("the program has no {} section", section),
+            ),
+            ContainerError::MalformedSection(section) => (
+                "Malformed section".to_string(),
+                format!("the {} section goes past its bounds", section),
+            ),
+            ContainerError::Truncated { expected, found } => (
+                "Truncated program".to_string(),
+                format!(
+                    "the program needs {} bytes, the file has {}",
+                    expected, found
+                ),
+            ),
+        },
+        VMErrorType::Ffi(ffi) => match ffi {
+            FfiError::MissingHandlerName => (
+                "Missing handler name".to_string(),
+                "a foreign call needs the handler name as first argument".to_string(),
+            ),
+            FfiError::HandlerNotFound(s) => (
+                "Handler not found".to_string(),
+                format!("'{}' is not a registered foreign handler", s),
+            ),
+            FfiError::SpawnError(s) => ("Cannot spawn foreign handler".to_string(), s.clone()),
+        },
+        VMErrorType::StackUnderflow { needed, available } => (
+            "Stack underflow".to_string(),
+            format!(
+                "needed {} values on the operands stack, found {}",
+                needed, available
+            ),
+        ),
+        VMErrorType::StaleHandle(handle) => (
+            "Stale handle".to_string(),
+            format!("object {} was already freed", handle.to_string()),
+        ),
+        VMErrorType::Thrown(thrown) => (thrown.message.clone(), thrown.semantic_message.clone()),
+    };
+
+    // nothing has run when the bytecode does not load or verify
+    let (location, traceback) = match &error_type {
+        VMErrorType::Verification(_) | VMErrorType::Container(_) => (None, Traceback::default()),
+        _ => (vm.current_location().map(Box::new), vm.traceback()),
     };
 
     VMError {
         error_type: error_type,
         message: error.0,
         semantic_message: error.1,
+        location,
+        traceback: Box::new(traceback),
+    }
+}
+
+fn bytecode_error_message(error: &BytecodeError) -> (String, String) {
+    match error {
+        BytecodeError::UnknownOpcode { opcode, at } => (
+            "Unknown opcode".to_string(),
+            format!("0x{:02x} at position {}", opcode, at),
+        ),
+        BytecodeError::TruncatedInstruction { instruction, at } => (
+            "Truncated instruction".to_string(),
+            format!("{} at position {} is missing operands", instruction, at),
+        ),
+        BytecodeError::InvalidOperand { instruction, at } => (
+            "Invalid operand".to_string(),
+            format!("unexpected operand for {} at position {}", instruction, at),
+        ),
+        BytecodeError::UnsupportedDataType { data_type, at } => (
+            "Unsupported data type".to_string(),
+            format!("{} cannot be loaded at position {}", data_type, at),
+        ),
+        BytecodeError::InvalidUtf8 { at } => (
+            "Invalid utf8".to_string(),
+            format!("string at position {} is not valid utf8", at),
+        ),
+        BytecodeError::UnknownConstant {
+            index,
+            available,
+            at,
+        } => (
+            "Unknown constant".to_string(),
+            format!(
+                "constant {} referenced at position {}, the module has {}",
+                index, at, available
+            ),
+        ),
+
+        BytecodeError::BodyOutOfBounds {
+            instruction,
+            at,
+            body_length,
+            available,
+        } => (
+            "Body out of bounds".to_string(),
+            format!(
+                "{} at position {} declares a body of {} bytes, only {} left",
+                instruction, at, body_length, available
+            ),
+        ),
+        BytecodeError::InvalidJumpTarget {
+            instruction,
+            at,
+            target,
+        } => (
+            "Invalid jump target".to_string(),
+            format!(
+                "{} at position {} jumps to {}, which is not the start of an instruction",
+                instruction, at, target
+            ),
+        ),
+        BytecodeError::StackUnderflow {
+            instruction,
+            at,
+            needed,
+            available,
+        } => (
+            "Stack underflow".to_string(),
+            format!(
+                "{} at position {} needs {} values on the operands stack, {} available",
+                instruction, at, needed, available
+            ),
+        ),
     }
 }
 
+#[derive(Debug)]
+pub struct ThrownError {
+    pub error_type: String,
+    pub message: String,
+    pub semantic_message: String,
+}
+
 #[derive(Debug)]
 pub struct InvalidBinaryOperation {
     pub left: DataType,
     pub right: DataType,
     pub operator: String,
 }
+
+#[derive(Debug)]
+pub struct IntegerOverflow {
+    pub data_type: DataType,
+    // the operation as written, like 2147483647 + 1
+    pub operation: String,
+}
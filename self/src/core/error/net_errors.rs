@@ -5,3 +5,14 @@ pub enum NetErrors {
     WriteError(String),
     ReadError(String),
 }
+
+impl NetErrors {
+    // stable code of the error, see VMErrorType::code
+    pub fn code(&self) -> &'static str {
+        match self {
+            NetErrors::NetConnectError(_) => "V0601",
+            NetErrors::WriteError(_) => "V0602",
+            NetErrors::ReadError(_) => "V0603",
+        }
+    }
+}
@@ -2,3 +2,12 @@
 pub enum OsError {
     __placeholder(String),
 }
+
+impl OsError {
+    // stable code of the error, see VMErrorType::code
+    pub fn code(&self) -> &'static str {
+        match self {
+            OsError::__placeholder(_) => "V0301",
+        }
+    }
+}
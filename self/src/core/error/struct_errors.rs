@@ -2,3 +2,12 @@
 pub enum StructError {
     FieldNotFound { field: String, struct_type: String },
 }
+
+impl StructError {
+    // stable code of the error, see VMErrorType::code
+    pub fn code(&self) -> &'static str {
+        match self {
+            StructError::FieldNotFound { .. } => "V0701",
+        }
+    }
+}
@@ -2,3 +2,12 @@
 pub enum TypeError {
     InvalidArgsCount { expected: u32, received: u32 },
 }
+
+impl TypeError {
+    // stable code of the error, see VMErrorType::code
+    pub fn code(&self) -> &'static str {
+        match self {
+            TypeError::InvalidArgsCount { .. } => "V0101",
+        }
+    }
+}
@@ -1,13 +1,17 @@
 use crate::{types::Value, vm::Vm};
 
-use super::error::{throw, VMError, VMErrorType};
+use super::{
+    error::{throw, VMError, VMErrorType},
+    traceback::Traceback,
+};
 
 pub struct VMExecutionResult {
     pub error: Option<VMError>,
     pub result: Option<Value>,
+    // call stack when the error was raised
+    pub traceback: Option<Traceback>,
     //pub exports: Vec<Value>,
     // eventually here we could implement things like:
-    // traceback
     // execution time
     // ...
 }
@@ -17,12 +21,24 @@ impl VMExecutionResult {
         VMExecutionResult {
             error: None,
             result: result,
+            traceback: None,
         }
     }
 
     pub fn terminate_with_errors(error_type: VMErrorType, vm: &Vm) -> VMExecutionResult {
+        VMExecutionResult::terminate_with_error(throw(error_type, vm))
+    }
+
+    pub fn terminate_with_error(error: VMError) -> VMExecutionResult {
+        // errors raised before running have no frames to show
+        let traceback = if error.traceback.frames.is_empty() {
+            None
+        } else {
+            Some(*error.traceback.clone())
+        };
         VMExecutionResult {
-            error: Some(throw(error_type, vm)),
+            traceback,
+            error: Some(error),
             result: None,
         }
     }
@@ -1,26 +1,31 @@
 use std::process::Command;
 
+use crate::core::error::{ffi_errors::FfiError, VMErrorType};
+
 use super::foreign_handlers::ForeignHandlers;
 
-pub fn call_handler(foreign_handlers: &ForeignHandlers, args: Vec<String>) {
-    if args.len() < 1 {
-        panic!("Call handler requires at least 1 arg");
+pub fn call_handler(
+    foreign_handlers: &ForeignHandlers,
+    args: Vec<String>,
+) -> Result<(), VMErrorType> {
+    if args.is_empty() {
+        return Err(VMErrorType::Ffi(FfiError::MissingHandlerName));
     }
 
     let handler = foreign_handlers.handlers.get(&args[0]);
     let handler = match handler {
         Some(val) => val,
-        None => panic!("Calling an unset handler"),
+        None => return Err(VMErrorType::Ffi(FfiError::HandlerNotFound(args[0].clone()))),
     };
 
-    spawn_process(&handler.runtime, &handler.script, args[1..].to_vec());
+    spawn_process(&handler.runtime, &handler.script, args[1..].to_vec())
 }
 
-fn spawn_process(binary: &String, script: &String, args: Vec<String>) {
+fn spawn_process(binary: &String, script: &String, args: Vec<String>) -> Result<(), VMErrorType> {
     let output = Command::new(binary).arg(script).args(args).output();
     let output = match output {
         Ok(val) => val,
-        Err(_) => panic!("Cannot spawn a foreign handler"),
+        Err(err) => return Err(VMErrorType::Ffi(FfiError::SpawnError(err.to_string()))),
     };
 
     if output.status.success() {
@@ -30,4 +35,5 @@ fn spawn_process(binary: &String, script: &String, args: Vec<String>) {
         let stderr = String::from_utf8_lossy(&output.stderr);
         eprintln!("Error executing foreign handler:\n{}", stderr);
     }
+    Ok(())
 }
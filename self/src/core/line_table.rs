@@ -0,0 +1,131 @@
+use std::fmt;
+
+// maps bytecode positions to the ego source that generated
+// them. entries are sorted by pc and each one covers the
+// bytecode until the next entry starts
+#[derive(Debug, Clone, Default)]
+pub struct LineTable {
+    pub files: Vec<SourceFile>,
+    pub entries: Vec<LineEntry>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SourceFile {
+    pub name: String,
+    pub source: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct LineEntry {
+    pub pc: usize,
+    pub file: usize, // index on LineTable.files
+    pub line: usize,
+    pub column: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct SourceLocation {
+    pub file: String,
+    pub line: usize,
+    pub column: usize,
+    pub source_line: String,
+}
+
+impl LineTable {
+    pub fn new() -> LineTable {
+        LineTable {
+            files: vec![],
+            entries: vec![],
+        }
+    }
+
+    pub fn add_file(&mut self, name: String, source: String) -> usize {
+        self.files.push(SourceFile { name, source });
+        self.files.len() - 1
+    }
+
+    pub fn add_entry(&mut self, pc: usize, file: usize, line: usize, column: usize) {
+        // nested nodes can start at the same pc than its parent,
+        // the innermost one is the most accurate
+        if let Some(last) = self.entries.last_mut() {
+            if last.pc == pc {
+                *last = LineEntry {
+                    pc,
+                    file,
+                    line,
+                    column,
+                };
+                return;
+            }
+        }
+        self.entries.push(LineEntry {
+            pc,
+            file,
+            line,
+            column,
+        });
+    }
+
+    // merges the table of a module placed at `offset` on
+    // this table bytecode, like the inlined imports
+    pub fn extend(&mut self, other: LineTable, offset: usize) {
+        let files_offset = self.files.len();
+        self.files.extend(other.files);
+        for entry in other.entries {
+            self.add_entry(
+                entry.pc + offset,
+                entry.file + files_offset,
+                entry.line,
+                entry.column,
+            );
+        }
+        self.entries.sort_by_key(|entry| entry.pc);
+    }
+
+    pub fn lookup(&self, pc: usize) -> Option<SourceLocation> {
+        let index = self.entries.partition_point(|entry| entry.pc <= pc);
+        if index == 0 {
+            return None;
+        }
+
+        let entry = &self.entries[index - 1];
+        let file = self.files.get(entry.file)?;
+        let source_line = file
+            .source
+            .lines()
+            .nth(entry.line.saturating_sub(1))
+            .unwrap_or("")
+            .to_string();
+
+        Some(SourceLocation {
+            file: file.name.clone(),
+            line: entry.line,
+            column: entry.column,
+            source_line,
+        })
+    }
+}
+
+// main.ego:12:5
+impl fmt::Display for SourceLocation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}:{}", self.file, self.line, self.column)
+    }
+}
+
+impl SourceLocation {
+    //   --> main.ego:12:5
+    //    |
+    // 12 |     println(v[5])
+    //    |     ^
+    pub fn snippet(&self) -> String {
+        let line_number = self.line.to_string();
+        let padding = " ".repeat(line_number.len());
+        format!(
+            "{padding}--> {}\n{padding} |\n{line_number} | {}\n{padding} | {}^",
+            self,
+            self.source_line,
+            " ".repeat(self.column.saturating_sub(1)),
+        )
+    }
+}
@@ -1,3 +1,6 @@
 pub mod error;
 pub mod execution;
 pub mod handlers;
+pub mod line_table;
+pub mod report;
+pub mod traceback;
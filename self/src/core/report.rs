@@ -0,0 +1,200 @@
+/*
+    RENDERS THE ERRORS OF THE EGO COMPILER AND THE VM THE
+    SAME WAY: CODE, TITLE, LOCATION AND THE SOURCE LINES
+    THEY POINT TO
+*/
+
+// error[V0805]: Key not found: "age" is not a key of the map
+//  --> main.ego:4:9
+//   |
+// 2 | let m = {"name": "ego"}
+//   |         - map created here
+// ...
+// 4 | println(m["age"])
+//   |         ^
+//   |
+//   = help: ...
+
+use std::io::{self, IsTerminal};
+
+// a position on the source. length 0 underlines the word
+// found at the column
+#[derive(Debug, Clone)]
+pub struct Label {
+    pub line: usize,
+    pub column: usize,
+    pub length: usize,
+    pub source_line: String,
+    // printed after the underline, can be empty
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Report {
+    pub code: String,
+    pub title: String,
+    pub message: String,
+    pub file: Option<String>,
+    // where the error is, underlined with '^'
+    pub primary: Option<Label>,
+    // related positions, underlined with '-'
+    pub secondary: Vec<Label>,
+    // help notes printed after the source
+    pub notes: Vec<String>,
+}
+
+const RED: &str = "\x1b[1;31m";
+const BLUE: &str = "\x1b[1;34m";
+const BOLD: &str = "\x1b[1m";
+const RESET: &str = "\x1b[0m";
+
+#[derive(Debug, Clone, Copy)]
+pub struct Renderer {
+    color: bool,
+}
+
+impl Renderer {
+    pub fn new(color: bool) -> Renderer {
+        Renderer { color }
+    }
+
+    // --color=always, --color=never or --color=auto, the default,
+    // which only colors the output of a terminal
+    pub fn from_args(args: &[String]) -> Renderer {
+        let choice = args.iter().find_map(|arg| arg.strip_prefix("--color="));
+        let color = match choice {
+            Some("always") => true,
+            Some("never") => false,
+            _ => io::stderr().is_terminal(),
+        };
+        Renderer { color }
+    }
+
+    fn paint(&self, color: &str, text: &str) -> String {
+        if self.color {
+            format!("{color}{text}{RESET}")
+        } else {
+            text.to_string()
+        }
+    }
+
+    pub fn render(&self, report: &Report) -> String {
+        let mut out = self.paint(RED, format!("error[{}]", report.code).as_str());
+        let header = if report.message.is_empty() {
+            format!(": {}", report.title)
+        } else {
+            format!(": {}: {}", report.title, report.message)
+        };
+        out += self.paint(BOLD, &header).as_str();
+
+        let mut labels: Vec<(&Label, bool)> = report.secondary.iter().map(|l| (l, false)).collect();
+        if let Some(primary) = &report.primary {
+            labels.push((primary, true));
+        }
+        // programs compiled without their sources only have
+        // the position, there is no line to underline
+        labels.retain(|(label, _)| !label.source_line.is_empty());
+        labels.sort_by_key(|(label, _)| (label.line, label.column));
+
+        let width = labels
+            .iter()
+            .map(|(label, _)| label.line.to_string().len())
+            .max()
+            .unwrap_or(0);
+        let gutter = " ".repeat(width);
+
+        if let Some(primary) = &report.primary {
+            let file = report.file.as_deref().unwrap_or("<source>");
+            out += format!(
+                "\n{gutter}{} {}:{}:{}",
+                self.paint(BLUE, "-->"),
+                file,
+                primary.line,
+                primary.column
+            )
+            .as_str();
+        }
+
+        if !labels.is_empty() {
+            out += format!("\n{gutter} {}", self.paint(BLUE, "|")).as_str();
+        }
+        let mut last_line = None;
+        for (label, primary) in &labels {
+            if last_line != Some(label.line) {
+                if last_line.is_some_and(|line| label.line > line + 1) {
+                    out += format!("\n{}", self.paint(BLUE, "...")).as_str();
+                }
+                out += format!(
+                    "\n{} {}",
+                    self.paint(BLUE, format!("{:>width$} |", label.line).as_str()),
+                    label.source_line
+                )
+                .as_str();
+                last_line = Some(label.line);
+            }
+
+            let (mark, color) = if *primary { ('^', RED) } else { ('-', BLUE) };
+            let mut underline = mark.to_string().repeat(underline_length(label));
+            if !label.message.is_empty() {
+                underline += format!(" {}", label.message).as_str();
+            }
+            out += format!(
+                "\n{gutter} {} {}{}",
+                self.paint(BLUE, "|"),
+                padding(&label.source_line, label.column),
+                self.paint(color, &underline)
+            )
+            .as_str();
+        }
+
+        if !report.notes.is_empty() && !labels.is_empty() {
+            out += format!("\n{gutter} {}", self.paint(BLUE, "|")).as_str();
+        }
+        for note in &report.notes {
+            out += format!(
+                "\n{gutter} {} {}: {}",
+                self.paint(BLUE, "="),
+                self.paint(BOLD, "help"),
+                note
+            )
+            .as_str();
+        }
+
+        out
+    }
+}
+
+// whitespace up to the column. tabs are kept so the underline
+// lines up with the source
+fn padding(line: &str, column: usize) -> String {
+    let mut padding: String = line
+        .chars()
+        .take(column.saturating_sub(1))
+        .map(|c| if c == '\t' { '\t' } else { ' ' })
+        .collect();
+    // columns past the line end, like an early end of module
+    let missing = column.saturating_sub(1 + padding.chars().count());
+    padding += " ".repeat(missing).as_str();
+    padding
+}
+
+fn underline_length(label: &Label) -> usize {
+    if label.length > 0 {
+        return label.length;
+    }
+
+    let rest: Vec<char> = label
+        .source_line
+        .chars()
+        .skip(label.column.saturating_sub(1))
+        .collect();
+    let is_word = |c: &char| c.is_alphanumeric() || *c == '_';
+    match rest.first() {
+        Some(c) if is_word(c) => rest.iter().take_while(|c| is_word(c)).count(),
+        Some('"') => match rest.iter().skip(1).position(|c| *c == '"') {
+            Some(end) => end + 2,
+            None => rest.len(),
+        },
+        _ => 1,
+    }
+}
@@ -0,0 +1,51 @@
+use std::fmt;
+
+use super::line_table::SourceLocation;
+
+// a call stack frame at the moment an error was raised
+#[derive(Debug, Clone)]
+pub struct TraceFrame {
+    pub function: String,
+    pub module: String,
+    // where the frame was executing: the call site of the
+    // next frame, or the failing instruction for the last one
+    pub location: Option<SourceLocation>,
+}
+
+// frames are ordered from the outermost (main module) to
+// the one that raised the error
+#[derive(Debug, Clone, Default)]
+pub struct Traceback {
+    pub frames: Vec<TraceFrame>,
+}
+
+// Traceback (most recent call last):
+//   File "main.ego", line 9, in <module>
+//     boom(v)
+//   File "main.ego", line 4, in boom
+//     println(v[5])
+impl fmt::Display for Traceback {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Traceback (most recent call last):")?;
+        for frame in &self.frames {
+            match &frame.location {
+                Some(location) => {
+                    write!(
+                        f,
+                        "\n  File \"{}\", line {}, in {}",
+                        location.file, location.line, frame.function
+                    )?;
+                    if !location.source_line.trim().is_empty() {
+                        write!(f, "\n    {}", location.source_line.trim())?;
+                    }
+                }
+                None => write!(
+                    f,
+                    "\n  File \"{}.ego\", in {}",
+                    frame.module, frame.function
+                )?,
+            }
+        }
+        Ok(())
+    }
+}
@@ -1,38 +1,109 @@
+use std::rc::Rc;
+
 use crate::opcodes::DataType;
 
+// a bytecode stream decoded once before running it. jumps are
+// resolved to instruction indexes and the bodies of functions,
+// methods and imported modules are decoded as codes of their own
+#[derive(Debug, Default)]
+pub struct Code {
+    pub instructions: Vec<Instruction>,
+    // position of each instruction on the program bytecode,
+    // used to look up its source position
+    pub positions: Vec<usize>,
+    // position right after the last instruction
+    pub end: usize,
+    // instruction each jump lands on, the instructions length
+    // when it finishes the stream. None for other instructions
+    pub targets: Vec<Option<usize>>,
+    // body of each declaration, None for other instructions
+    pub bodies: Vec<Option<Rc<Code>>>,
+}
+
+impl Code {
+    // the stream end for an index past the last instruction
+    pub fn position(&self, index: usize) -> usize {
+        match self.positions.get(index) {
+            Some(position) => *position,
+            None => self.end,
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub enum Instruction {
-    Zero,
     LoadConst {
         data_type: DataType,
         value: Vec<u8>,
     },
     LoadVar {
-        data_type: DataType,
-        identifier: Vec<u8>,
+        identifier: String,
     },
     StoreVar {
         identifier: String,
         mutable: bool,
+        reassign: bool,
+    },
+    // locals live on numeric slots of the frame `depth`
+    // functions up from the current one. the identifier
+    // is only kept for errors and debugging
+    LoadLocal {
+        depth: u32,
+        slot: u32,
+        identifier: String,
+    },
+    StoreLocal {
+        depth: u32,
+        slot: u32,
+        identifier: String,
+    },
+    LoadGlobal {
+        identifier: String,
+    },
+    JumpIfFalse {
+        offset: i32,
+    },
+    Jump {
+        offset: i32,
     },
-    JumpIfFalse,
-    Jump,
     Add,
     Substract,
     Multiply,
     Divide,
     GreaterThan,
     LessThan,
+    GreaterThanOrEqual,
+    LessThanOrEqual,
     Equals,
     NotEquals,
+    Not,
     FuncDec {
         identifier: String,
+        parameters_num: u32,
+        body_length: u32,
+    },
+    MethodDec {
+        identifier: String,
+        parameters_num: u32,
+        body_length: u32,
+    },
+    ImplDec {
+        identifier: String,
+        methods_num: u32,
     },
     StructDec {
         identifier: String,
-        fields: Vec<String>,
+        fields: Vec<(String, DataType)>,
     },
     GetProperty,
+    SetProperty,
+    GetIndex,
+    SetIndex,
+    TryStart {
+        offset: i32,
+    },
+    TryEnd,
+    Throw,
     Print {
         number_of_args: u32,
     },
@@ -42,47 +113,66 @@ pub enum Instruction {
     FFI_Call {
         number_of_args: u32,
     },
-    Call,
-    Unknown,
+    Call {
+        number_of_args: u32,
+    },
+    // native modules have an empty body
+    Import {
+        body_length: u32,
+    },
+    Export,
+    Return,
 }
 
 impl Instruction {
     pub fn get_type(&self) -> String {
         match self {
-            Instruction::Zero => "Zero".to_string(),
             Instruction::LoadConst {
                 data_type: _,
                 value: _,
             } => "LoadConst".to_string(),
-            Instruction::LoadVar {
-                data_type: _,
-                identifier: _,
-            } => "LoadVar".to_string(),
-            Instruction::StoreVar {
-                identifier: _,
-                mutable: _,
-            } => "StoreVar".to_string(),
-            Instruction::JumpIfFalse => "JumpIfFalse".to_string(),
-            Instruction::Jump => "Jump".to_string(),
+            Instruction::LoadVar { identifier: _ } => "LoadVar".to_string(),
+            Instruction::StoreVar { .. } => "StoreVar".to_string(),
+            Instruction::LoadLocal { .. } => "LoadLocal".to_string(),
+            Instruction::StoreLocal { .. } => "StoreLocal".to_string(),
+            Instruction::LoadGlobal { .. } => "LoadGlobal".to_string(),
+            Instruction::JumpIfFalse { offset: _ } => "JumpIfFalse".to_string(),
+            Instruction::Jump { offset: _ } => "Jump".to_string(),
             Instruction::Add => "Add".to_string(),
             Instruction::Substract => "Substract".to_string(),
             Instruction::Multiply => "Multiply".to_string(),
             Instruction::Divide => "Divide".to_string(),
             Instruction::GreaterThan => "GreaterThan".to_string(),
             Instruction::LessThan => "LessThan".to_string(),
+            Instruction::GreaterThanOrEqual => "GreaterThanOrEqual".to_string(),
+            Instruction::LessThanOrEqual => "LessThanOrEqual".to_string(),
             Instruction::Equals => "Equals".to_string(),
             Instruction::NotEquals => "NotEquals".to_string(),
-            Instruction::FuncDec { identifier: _ } => "FuncDec".to_string(),
+            Instruction::Not => "Not".to_string(),
+            Instruction::FuncDec { .. } => "FuncDec".to_string(),
+            Instruction::MethodDec { .. } => "MethodDec".to_string(),
+            Instruction::ImplDec {
+                identifier: _,
+                methods_num: _,
+            } => "ImplDec".to_string(),
             Instruction::StructDec {
                 identifier: _,
                 fields: _,
             } => "StructDec".to_string(),
             Instruction::GetProperty => "GetProperty".to_string(),
+            Instruction::SetProperty => "SetProperty".to_string(),
+            Instruction::GetIndex => "GetIndex".to_string(),
+            Instruction::SetIndex => "SetIndex".to_string(),
+            Instruction::TryStart { offset: _ } => "TryStart".to_string(),
+            Instruction::TryEnd => "TryEnd".to_string(),
+            Instruction::Throw => "Throw".to_string(),
             Instruction::Print { number_of_args: _ } => "Print".to_string(),
             Instruction::Println { number_of_args: _ } => "Println".to_string(),
-            Instruction::Call => "Call".to_string(),
+            Instruction::Call { number_of_args: _ } => "Call".to_string(),
             Instruction::FFI_Call { number_of_args: _ } => "FFI_Call".to_string(),
-            Instruction::Unknown => "Unknown".to_string(),
+            Instruction::Import { body_length: _ } => "Import".to_string(),
+            Instruction::Export => "Export".to_string(),
+            Instruction::Return => "Return".to_string(),
         }
     }
 }
@@ -1,3 +1,4 @@
+mod container;
 mod core;
 mod heap;
 mod instructions;
@@ -7,11 +8,51 @@ mod stack;
 mod std;
 mod translator;
 mod types;
+mod verifier;
 
 pub mod utils;
 pub mod vm;
+pub use container::Program;
+pub use core::error::bytecode_errors::BytecodeError;
+pub use core::error::catalogue::{explain, Explanation, VM_ERRORS};
+pub use core::error::VMError;
+pub use core::line_table::{LineTable, SourceLocation};
+pub use core::report::{Label, Renderer, Report};
+pub use core::traceback::{TraceFrame, Traceback};
 pub use opcodes::get_codes_map;
 
 pub fn new(bytecode: Vec<u8>) -> vm::Vm {
     vm::Vm::new(bytecode)
 }
+
+pub fn new_with_line_table(bytecode: Vec<u8>, line_table: LineTable) -> vm::Vm {
+    vm::Vm::new_with_line_table(bytecode, line_table)
+}
+
+// a vm for a compiled program file. files of another format
+// or opcodes version are rejected instead of misread
+pub fn load(bytes: &[u8]) -> Result<vm::Vm, VMError> {
+    match Program::from_bytes(bytes) {
+        Ok(program) => Ok(vm::Vm::new_with_line_table(
+            program.bytecode,
+            program.line_table,
+        )),
+        Err(err) => Err(core::error::throw(
+            core::error::VMErrorType::Container(err),
+            &vm::Vm::new(vec![]),
+        )),
+    }
+}
+
+// names a native module exports, None if there is no native
+// module with that name
+pub fn native_module_members(module: &str) -> Option<Vec<String>> {
+    let module = crate::std::get_native_module_type(module)?;
+    let (_, members) = crate::std::generate_native_module(module);
+    Some(members.into_iter().map(|(name, _)| name).collect())
+}
+
+// checks a bytecode without running it. Vm::run does it too
+pub fn verify(bytecode: &[u8]) -> Result<(), Vec<BytecodeError>> {
+    verifier::verify(bytecode)
+}
@@ -1,142 +1,209 @@
-use std::collections::HashMap;
+use std::{collections::HashSet, rc::Rc};
 
 use crate::{
-    core::error::{self, VMError},
-    heap::{Heap, HeapRef},
-    types::object::{
-        func::Function,
-        native_struct::NativeStruct,
-        structs::{StructDeclaration, StructLiteral},
-        vector::Vector,
+    core::error::{self, VMError, VMErrorType},
+    stack::EnvRef,
+    types::{
+        object::{
+            func::Function,
+            map::Map,
+            native_struct::NativeStruct,
+            structs::{StructDeclaration, StructLiteral},
+            vector::Vector,
+        },
+        Value,
     },
     vm::Vm,
 };
 
+// live objects needed before the first collection. after each
+// collection the threshold is set to twice the surviving objects
+const MIN_GC_THRESHOLD: usize = 1024;
+
+#[derive(Debug, Clone, Default)]
+pub struct GcStats {
+    pub collections: usize,
+    // objects allocated since the vm started
+    pub allocated: usize,
+    // objects freed by the collector since the vm started
+    pub freed: usize,
+    pub live: usize,
+    // live objects that trigger the next collection
+    pub threshold: usize,
+}
+
+// objects live on a slab. freed slots are reused, and their
+// generation is bumped so handles to the previous object
+// are detected as stale
+struct Slot {
+    generation: u32,
+    object: Option<MemObject>,
+}
+
 pub struct MemoryManager {
-    heap: Heap,
-    table: HashMap<u32, PointerType>,
-    next_pointer: u32,
+    slots: Vec<Slot>,
+    free_slots: Vec<u32>,
+    live: usize,
+    stats: GcStats,
 }
 
 impl MemoryManager {
     pub fn new() -> MemoryManager {
         MemoryManager {
-            heap: Heap::new(),
-            table: HashMap::new(),
-            next_pointer: 0,
+            slots: vec![],
+            free_slots: vec![],
+            live: 0,
+            stats: GcStats {
+                threshold: MIN_GC_THRESHOLD,
+                ..GcStats::default()
+            },
         }
     }
 
     pub fn alloc(&mut self, obj: MemObject) -> Handle {
-        match obj {
-            MemObject::String(_)
-            | MemObject::Function(_)
-            | MemObject::NativeStruct(_)
-            | MemObject::StructDeclaration(_)
-            | MemObject::StructLiteral(_)
-            | MemObject::Vector(_) => {
-                let heap_ref = self.heap.allocate(obj);
-                self.gen_handle(PointerType::HeapPointer(heap_ref))
+        self.stats.allocated += 1;
+        self.live += 1;
+        match self.free_slots.pop() {
+            Some(index) => {
+                let slot = &mut self.slots[index as usize];
+                slot.object = Some(obj);
+                Handle::new(index, slot.generation)
+            }
+            None => {
+                self.slots.push(Slot {
+                    generation: 0,
+                    object: Some(obj),
+                });
+                Handle::new((self.slots.len() - 1) as u32, 0)
             }
         }
     }
 
-    pub fn free(&mut self, handle: Handle) -> MemObject {
-        let mem_obj = self.resolve(&handle);
-        match mem_obj {
-            // heap objects
-            MemObject::String(_)
-            | MemObject::Function(_)
-            | MemObject::NativeStruct(_)
-            | MemObject::StructDeclaration(_)
-            | MemObject::StructLiteral(_)
-            | MemObject::Vector(_) => {
-                // free handle from table
-                let heap_ref = self.free_handle(&handle).1.as_heap_pointer();
-                // free heap
-                let mem_obj = self.heap.free(heap_ref);
-                if mem_obj.is_none() {
-                    panic!("handle pointer does not exist in memory table")
-                }
-                mem_obj.unwrap()
+    pub fn free(&mut self, handle: Handle) -> Result<MemObject, VMErrorType> {
+        match self.slots.get_mut(handle.index as usize) {
+            Some(slot) if slot.generation == handle.generation && slot.object.is_some() => {
+                slot.generation = slot.generation.wrapping_add(1);
+                self.free_slots.push(handle.index);
+                self.live -= 1;
+                Ok(slot.object.take().unwrap())
             }
+            _ => Err(VMErrorType::StaleHandle(handle)),
         }
     }
 
-    pub fn resolve(&self, handle: &Handle) -> &MemObject {
-        let real_pointer = self.table.get(&handle.pointer);
-        if let Some(rp) = real_pointer {
-            match rp {
-                PointerType::HeapPointer(p) => match self.heap.get(p.clone()) {
-                    Some(v) => v,
-                    None => panic!("handle pointer does not exist in memory table"),
-                },
-            }
-        } else {
-            panic!("handle pointer does not exist in memory table")
+    pub fn resolve(&self, handle: &Handle) -> Result<&MemObject, VMErrorType> {
+        match self.slots.get(handle.index as usize) {
+            Some(Slot {
+                generation,
+                object: Some(object),
+            }) if *generation == handle.generation => Ok(object),
+            _ => Err(VMErrorType::StaleHandle(handle.clone())),
         }
     }
 
-    pub fn resolve_mut(&mut self, handle: &Handle) -> &mut MemObject {
-        let real_pointer = self.table.get(&handle.pointer);
-        if let Some(rp) = real_pointer {
-            match rp {
-                PointerType::HeapPointer(p) => match self.heap.get_mut(p.clone()) {
-                    Some(v) => v,
-                    None => panic!("handle pointer does not exist in memory table"),
-                },
-            }
-        } else {
-            panic!("handle pointer does not exist in memory table")
+    pub fn resolve_mut(&mut self, handle: &Handle) -> Result<&mut MemObject, VMErrorType> {
+        match self.slots.get_mut(handle.index as usize) {
+            Some(Slot {
+                generation,
+                object: Some(object),
+            }) if *generation == handle.generation => Ok(object),
+            _ => Err(VMErrorType::StaleHandle(handle.clone())),
         }
     }
 
-    fn gen_handle(&mut self, pointer: PointerType) -> Handle {
-        let generated_pointer = self.next_pointer;
-        self.next_pointer += 1;
-        let handle = Handle::new(generated_pointer);
-        self.table.insert(generated_pointer, pointer);
-        handle
+    pub fn should_collect(&self) -> bool {
+        self.live >= self.stats.threshold
+    }
+
+    // the next check collects regardless of the live objects
+    pub fn request_collection(&mut self) {
+        self.stats.threshold = 0;
+    }
+
+    pub fn stats(&self) -> GcStats {
+        GcStats {
+            live: self.live,
+            ..self.stats.clone()
+        }
     }
 
-    fn free_handle(&mut self, handle: &Handle) -> (u32, PointerType) {
-        let val = self.table.remove(&handle.pointer);
-        if val.is_none() {
-            panic!("unset pointer exception")
+    // mark and sweep. every object reachable from the root values
+    // and environments survives, the rest is freed. returns the
+    // number of freed objects
+    pub fn collect(&mut self, roots: Vec<Value>, root_envs: Vec<EnvRef>) -> usize {
+        let mut marked = vec![false; self.slots.len()];
+        // environments are shared and closures make them cyclic
+        let mut visited_envs = HashSet::new();
+        let mut pending = roots;
+        let mut pending_envs = root_envs;
+
+        loop {
+            if let Some(env) = pending_envs.pop() {
+                if visited_envs.insert(Rc::as_ptr(&env)) {
+                    let env = env.borrow();
+                    pending.extend(env.values());
+                    if let Some(parent) = env.parent() {
+                        pending_envs.push(parent);
+                    }
+                }
+                continue;
+            }
+
+            let Some(value) = pending.pop() else {
+                break;
+            };
+            match value {
+                Value::Handle(handle) => {
+                    // stale handles are skipped
+                    if let Ok(object) = self.resolve(&handle) {
+                        let index = handle.index as usize;
+                        if !marked[index] {
+                            marked[index] = true;
+                            object.trace(&mut pending, &mut pending_envs);
+                        }
+                    }
+                }
+                Value::BoundAccess(bound) => {
+                    pending.push(Value::Handle(bound.object));
+                    pending.push(*bound.property);
+                }
+                Value::RawValue(_) | Value::HeapRef(_) => {}
+            }
         }
 
-        (handle.pointer, val.unwrap().clone())
+        let mut freed = 0;
+        for (index, slot) in self.slots.iter_mut().enumerate() {
+            if slot.object.is_some() && !marked[index] {
+                slot.object = None;
+                slot.generation = slot.generation.wrapping_add(1);
+                self.free_slots.push(index as u32);
+                freed += 1;
+            }
+        }
+        self.live -= freed;
+
+        self.stats.collections += 1;
+        self.stats.freed += freed;
+        self.stats.threshold = MIN_GC_THRESHOLD.max(self.live * 2);
+        freed
     }
 }
 
-#[derive(Debug, Clone)]
+// index of the object slot and the generation of the slot
+// when the object was allocated
+#[derive(Debug, Clone, PartialEq)]
 pub struct Handle {
-    pub pointer: u32,
+    pub index: u32,
+    pub generation: u32,
 }
 
 impl Handle {
-    pub fn new(handle_pointer: u32) -> Handle {
-        Handle {
-            pointer: handle_pointer,
-        }
+    pub fn new(index: u32, generation: u32) -> Handle {
+        Handle { index, generation }
     }
 
     pub fn to_string(&self) -> String {
-        self.pointer.to_string()
-    }
-}
-
-#[derive(Clone)]
-pub enum PointerType {
-    HeapPointer(HeapRef),
-}
-
-impl PointerType {
-    pub fn as_heap_pointer(&self) -> HeapRef {
-        match self {
-            PointerType::HeapPointer(v) => v.clone(),
-            _ => panic!("invalid parse on PointerType as_heap_pointer method"),
-        }
+        format!("{}#{}", self.index, self.generation)
     }
 }
 
@@ -148,6 +215,7 @@ pub enum MemObject {
     StructLiteral(StructLiteral),
     NativeStruct(NativeStruct),
     Vector(Vector),
+    Map(Map),
 }
 
 impl MemObject {
@@ -159,6 +227,7 @@ impl MemObject {
             MemObject::StructLiteral(x) => x.struct_type.to_string(),
             MemObject::NativeStruct(x) => x.to_string(),
             MemObject::Vector(x) => x.to_string(vm),
+            MemObject::Map(x) => x.to_string(vm),
         }
     }
 
@@ -170,6 +239,46 @@ impl MemObject {
             MemObject::StructLiteral(_) => "struct_literal".to_string(),
             MemObject::NativeStruct(_) => "native_struct".to_string(),
             MemObject::Vector(_) => "vector".to_string(),
+            MemObject::Map(_) => "map".to_string(),
+        }
+    }
+
+    // pushes the values and environments the object keeps alive
+    fn trace(&self, values: &mut Vec<Value>, envs: &mut Vec<EnvRef>) {
+        match self {
+            MemObject::String(_) => {}
+            // captured environment
+            MemObject::Function(x) => {
+                if let Some(env) = &x.env {
+                    envs.push(env.clone());
+                }
+            }
+            MemObject::StructDeclaration(x) => {
+                values.extend(x.methods.values().cloned().map(Value::Handle));
+            }
+            MemObject::StructLiteral(x) => {
+                values.extend(x.fields.values().cloned());
+                if let Some(declaration) = &x.declaration {
+                    values.push(Value::Handle(declaration.clone()));
+                }
+            }
+            MemObject::NativeStruct(x) => match x {
+                NativeStruct::NetStream(stream) => {
+                    values.extend(stream.shape.fields.values().cloned());
+                }
+                NativeStruct::Action(action) => {
+                    values.push(Value::Handle(action.exec.clone()));
+                    values.extend(action.args.iter().cloned());
+                }
+            },
+            MemObject::Vector(x) => {
+                values.extend(x.elements.iter().cloned());
+                values.extend(x.members.values().cloned());
+            }
+            MemObject::Map(x) => {
+                values.extend(x.entries.values().cloned());
+                values.extend(x.members.values().cloned());
+            }
         }
     }
 
@@ -186,3 +295,55 @@ impl MemObject {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn string(memory: &mut MemoryManager, value: &str) -> Handle {
+        memory.alloc(MemObject::String(value.to_string()))
+    }
+
+    #[test]
+    fn collect_frees_garbage_and_keeps_reachable_values() {
+        let mut memory = MemoryManager::new();
+        let garbage = string(&mut memory, "garbage");
+        let root = string(&mut memory, "root");
+        // only reachable through the vector
+        let element = string(&mut memory, "element");
+        let vector = memory.alloc(MemObject::Vector(Vector::new(vec![Value::Handle(
+            element.clone(),
+        )])));
+
+        let freed = memory.collect(
+            vec![Value::Handle(root.clone()), Value::Handle(vector.clone())],
+            vec![],
+        );
+
+        assert_eq!(freed, 1);
+        assert!(memory.resolve(&garbage).is_err());
+        assert!(matches!(memory.resolve(&root), Ok(MemObject::String(s)) if s == "root"));
+        assert!(matches!(memory.resolve(&element), Ok(MemObject::String(s)) if s == "element"));
+        assert!(memory.resolve(&vector).is_ok());
+
+        let stats = memory.stats();
+        assert_eq!(stats.collections, 1);
+        assert_eq!(stats.freed, 1);
+        assert_eq!(stats.live, 3);
+    }
+
+    #[test]
+    fn reused_slots_keep_old_handles_stale() {
+        let mut memory = MemoryManager::new();
+        let garbage = string(&mut memory, "garbage");
+        memory.collect(vec![], vec![]);
+
+        let reused = string(&mut memory, "new");
+        assert_eq!(reused.index, garbage.index);
+        assert!(matches!(
+            memory.resolve(&garbage),
+            Err(VMErrorType::StaleHandle(_))
+        ));
+        assert!(memory.resolve(&reused).is_ok());
+    }
+}
@@ -1,5 +1,9 @@
 use std::collections::HashMap;
 
+// version of the opcodes table, stored on compiled programs.
+// it must change with any opcode or operands encoding change
+pub const OPCODES_VERSION: u16 = 1;
+
 pub fn get_codes_map() -> HashMap<String, u8> {
     let mut m = HashMap::new();
     // bytecode is generated using opcodes
@@ -8,7 +12,7 @@ pub fn get_codes_map() -> HashMap<String, u8> {
     // bytecode interpretation. Opcode can be repeated
     // if they are on different levels.
 
-    // last used opcode: 0x17
+    // last used opcode: 0x25
     // instructions opcodes - level: 0
     m.insert("zero".to_string(), 0x00);
     m.insert("load_const".to_string(), 0x01);
@@ -21,15 +25,29 @@ pub fn get_codes_map() -> HashMap<String, u8> {
     m.insert("divide".to_string(), 0x0b);
     m.insert("greater_than".to_string(), 0x0e);
     m.insert("less_than".to_string(), 0x0f);
+    m.insert("greater_than_or_equal".to_string(), 0x19);
+    m.insert("less_than_or_equal".to_string(), 0x1a);
     m.insert("equals".to_string(), 0x10);
     m.insert("not_equals".to_string(), 0x11);
+    m.insert("not".to_string(), 0x18);
     m.insert("store_var".to_string(), 0x04);
     m.insert("function_declaration".to_string(), 0x12);
     m.insert("struct_declaration".to_string(), 0x13);
+    m.insert("method_declaration".to_string(), 0x1e);
+    m.insert("impl_declaration".to_string(), 0x1f);
     m.insert("get_property".to_string(), 0x14);
+    m.insert("set_property".to_string(), 0x1d);
+    m.insert("get_index".to_string(), 0x1b);
+    m.insert("set_index".to_string(), 0x1c);
     m.insert("import".to_string(), 0x15);
     m.insert("export".to_string(), 0x16);
     m.insert("return".to_string(), 0x17);
+    m.insert("try_start".to_string(), 0x20);
+    m.insert("try_end".to_string(), 0x21);
+    m.insert("throw".to_string(), 0x22);
+    m.insert("load_local".to_string(), 0x23);
+    m.insert("store_local".to_string(), 0x24);
+    m.insert("load_global".to_string(), 0x25);
 
     // builtin functions opcode - level: 0
     m.insert("print".to_string(), 0x02);
@@ -40,6 +58,7 @@ pub fn get_codes_map() -> HashMap<String, u8> {
     // params - level 1
     m.insert("inmut".to_string(), 0x00);
     m.insert("mut".to_string(), 0x01);
+    m.insert("reassign".to_string(), 0x02);
 
     // typecodes - level 2
     m.insert("nothing".to_string(), 0x00);
@@ -52,6 +71,7 @@ pub fn get_codes_map() -> HashMap<String, u8> {
     m.insert("f64".to_string(), 0x07);
     m.insert("struct_literal".to_string(), 0x08);
     m.insert("vector".to_string(), 0x09);
+    m.insert("map".to_string(), 0x0a);
     m
 }
 
@@ -75,12 +95,26 @@ pub enum Opcode {
     Divide,
     GreaterThan,
     LessThan,
+    GreaterThanOrEqual,
+    LessThanOrEqual,
     Equals,
     NotEquals,
+    Not,
     StoreVar,
     FuncDec,
     StructDec,
+    MethodDec,
+    ImplDec,
     GetProperty,
+    SetProperty,
+    GetIndex,
+    SetIndex,
+    TryStart,
+    TryEnd,
+    Throw,
+    LoadLocal,
+    StoreLocal,
+    LoadGlobal,
     Unknown,
 }
 
@@ -111,6 +145,20 @@ impl Opcode {
             0x15 => Opcode::Import,
             0x16 => Opcode::Export,
             0x17 => Opcode::Return,
+            0x18 => Opcode::Not,
+            0x19 => Opcode::GreaterThanOrEqual,
+            0x1A => Opcode::LessThanOrEqual,
+            0x1B => Opcode::GetIndex,
+            0x1C => Opcode::SetIndex,
+            0x1D => Opcode::SetProperty,
+            0x1E => Opcode::MethodDec,
+            0x1F => Opcode::ImplDec,
+            0x20 => Opcode::TryStart,
+            0x21 => Opcode::TryEnd,
+            0x22 => Opcode::Throw,
+            0x23 => Opcode::LoadLocal,
+            0x24 => Opcode::StoreLocal,
+            0x25 => Opcode::LoadGlobal,
             _ => Opcode::Unknown,
         }
     }
@@ -126,6 +174,7 @@ pub enum DataType {
     Utf8,
     Nothing,
     Vector,
+    Map,
     Bool,
     StructLiteral,
     Unknown,
@@ -144,6 +193,7 @@ impl DataType {
             0x07 => DataType::F64,
             0x08 => DataType::StructLiteral,
             0x09 => DataType::Vector,
+            0x0a => DataType::Map,
             _ => DataType::Unknown,
         }
     }
@@ -160,6 +210,7 @@ impl DataType {
             DataType::StructLiteral => "struct_literal",
             DataType::Nothing => "nothing",
             DataType::Vector => "vector",
+            DataType::Map => "map",
             DataType::Unknown => "unknown",
         }
     }
@@ -177,6 +228,7 @@ impl PartialEq for DataType {
             (DataType::Bool, DataType::Bool) => true,
             (DataType::Nothing, DataType::Nothing) => true,
             (DataType::Vector, DataType::Vector) => true,
+            (DataType::Map, DataType::Map) => true,
             _ => false,
         }
     }
@@ -1,5 +1,114 @@
 use crate::types::Value;
-use std::collections::HashMap;
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+// ENVIRONMENT
+// an environment is the set of symbols visible from a
+// frame. it is shared (Rc) because functions capture the
+// environment where they are declared, so it can outlive
+// the frame that created it.
+pub type EnvRef = Rc<RefCell<Environment>>;
+
+#[derive(Debug)]
+pub struct Environment {
+    symbols: HashMap<String, Value>,
+    // locals resolved by the compiler to a numeric slot.
+    // None until the local is assigned for the first time
+    slots: Vec<Option<Value>>,
+    parent: Option<EnvRef>,
+}
+
+impl Environment {
+    pub fn new(parent: Option<EnvRef>) -> EnvRef {
+        Rc::new(RefCell::new(Environment {
+            symbols: HashMap::new(),
+            slots: vec![],
+            parent,
+        }))
+    }
+
+    pub fn put(&mut self, key: String, value: Value) -> Option<Value> {
+        self.symbols.insert(key, value)
+    }
+
+    pub fn get(&self, key: &str) -> Option<Value> {
+        self.symbols.get(key).cloned()
+    }
+
+    pub fn resolve(&self, key: &str) -> Option<Value> {
+        if let Some(var) = self.symbols.get(key) {
+            return Some(var.clone());
+        }
+
+        match &self.parent {
+            Some(parent) => parent.borrow().resolve(key),
+            None => None,
+        }
+    }
+
+    // depth is the number of environments to go up from this one
+    pub fn get_slot(&self, depth: u32, slot: u32) -> Option<Value> {
+        if depth == 0 {
+            return self.slots.get(slot as usize).cloned().flatten();
+        }
+
+        match &self.parent {
+            Some(parent) => parent.borrow().get_slot(depth - 1, slot),
+            None => None,
+        }
+    }
+
+    pub fn set_slot(&mut self, depth: u32, slot: u32, value: Value) -> bool {
+        if depth == 0 {
+            let slot = slot as usize;
+            if slot >= self.slots.len() {
+                self.slots.resize(slot + 1, None);
+            }
+            self.slots[slot] = Some(value);
+            return true;
+        }
+
+        match &self.parent {
+            Some(parent) => parent.borrow_mut().set_slot(depth - 1, slot, value),
+            None => false,
+        }
+    }
+
+    // module level symbols live on the root of the chain
+    pub fn global(&self, key: &str) -> Option<Value> {
+        match &self.parent {
+            Some(parent) => parent.borrow().global(key),
+            None => self.get(key),
+        }
+    }
+
+    // values bound on this environment, without the parents
+    pub fn values(&self) -> Vec<Value> {
+        self.symbols
+            .values()
+            .cloned()
+            .chain(self.slots.iter().flatten().cloned())
+            .collect()
+    }
+
+    pub fn parent(&self) -> Option<EnvRef> {
+        self.parent.clone()
+    }
+
+    // updates the closest environment that already defines
+    // the key. returns false if the key is not defined on
+    // the environment chain
+    pub fn assign(&mut self, key: &str, value: Value) -> bool {
+        if let Some(var) = self.symbols.get_mut(key) {
+            *var = value;
+            return true;
+        }
+
+        match &self.parent {
+            Some(parent) => parent.borrow_mut().assign(key, value),
+            None => false,
+        }
+    }
+}
 
 // CALL STACK
 #[derive(Debug)]
@@ -10,12 +119,27 @@ pub struct CallStack {
 impl CallStack {
     pub fn new() -> CallStack {
         CallStack {
-            stack: vec![StackFrame::new(0)],
+            stack: vec![StackFrame::new(
+                "<module>".to_string(),
+                "main".to_string(),
+                0,
+                None,
+            )],
         }
     }
-    pub fn push(&mut self) {
-        // we (maybe) should save here the return pc
-        self.stack.push(StackFrame::new(0));
+    // parent is the environment the new frame resolves
+    // free identifiers against (the captured environment
+    // for functions, none for modules). call_pc is the
+    // program position of the instruction that entered it
+    pub fn push(
+        &mut self,
+        function: String,
+        module: String,
+        call_pc: usize,
+        parent: Option<EnvRef>,
+    ) {
+        self.stack
+            .push(StackFrame::new(function, module, call_pc, parent));
     }
     pub fn pop(&mut self) -> Option<StackFrame> {
         self.stack.pop()
@@ -24,51 +148,89 @@ impl CallStack {
         let last = self.stack.len() - 1;
         self.stack[last].put(key, value);
     }
-    pub fn resolve(&self, key: &str) -> Option<Value> {
-        for frame in self.stack.iter().rev() {
-            if let Some(var) = frame.get(key) {
-                return Some(var.clone());
-            }
+    // reassignment: mutate the binding where it was declared
+    // and fallback to a new binding on the current frame
+    pub fn assign(&mut self, key: String, value: Value) {
+        let last = self.stack.len() - 1;
+        let env = self.stack[last].env.clone();
+        if !env.borrow_mut().assign(&key, value.clone()) {
+            self.stack[last].put(key, value);
         }
-
-        None
+    }
+    pub fn resolve(&self, key: &str) -> Option<Value> {
+        // only the current frame is looked up, outer scopes
+        // are reached through its environment chain
+        let last = self.stack.len() - 1;
+        self.stack[last].env.borrow().resolve(key)
+    }
+    pub fn resolve_local(&self, depth: u32, slot: u32) -> Option<Value> {
+        let last = self.stack.len() - 1;
+        self.stack[last].env.borrow().get_slot(depth, slot)
+    }
+    pub fn assign_local(&mut self, depth: u32, slot: u32, value: Value) -> bool {
+        let last = self.stack.len() - 1;
+        self.stack[last]
+            .env
+            .borrow_mut()
+            .set_slot(depth, slot, value)
+    }
+    pub fn resolve_global(&self, key: &str) -> Option<Value> {
+        let last = self.stack.len() - 1;
+        self.stack[last].env.borrow().global(key)
+    }
+    pub fn current_env(&self) -> EnvRef {
+        let last = self.stack.len() - 1;
+        self.stack[last].env.clone()
     }
     pub fn add_export(&mut self, key: String) {
         let last = self.stack.len() - 1;
         self.stack[last].add_export(key);
     }
+    // outermost frame first
+    pub fn frames(&self) -> &[StackFrame] {
+        &self.stack
+    }
 }
 
 #[derive(Debug)]
 pub struct StackFrame {
-    return_pc: usize,
-    symbols: HashMap<String, Value>,
+    pub function: String, // "<module>" for module bodies
+    pub module: String,
+    pub call_pc: usize,
+    env: EnvRef,
     exports: Vec<String>,
 }
 
 impl StackFrame {
-    pub fn new(return_pc: usize) -> StackFrame {
+    pub fn new(
+        function: String,
+        module: String,
+        call_pc: usize,
+        parent: Option<EnvRef>,
+    ) -> StackFrame {
         StackFrame {
-            return_pc: return_pc,
-            symbols: HashMap::new(),
+            function,
+            module,
+            call_pc,
+            env: Environment::new(parent),
             exports: vec![],
         }
     }
 
     pub fn put(&mut self, key: String, value: Value) -> Option<Value> {
-        self.symbols.insert(key, value)
+        self.env.borrow_mut().put(key, value)
     }
 
     pub fn add_export(&mut self, key: String) {
         self.exports.push(key);
     }
 
-    pub fn get(&self, key: &str) -> Option<Value> {
-        if let Some(var) = self.symbols.get(key) {
-            return Some(var.clone());
-        }
+    pub fn env(&self) -> EnvRef {
+        self.env.clone()
+    }
 
-        None
+    pub fn get(&self, key: &str) -> Option<Value> {
+        self.env.borrow().get(key)
     }
 
     pub fn get_exports(&mut self) -> HashMap<String, Value> {
@@ -17,7 +17,7 @@ use crate::{
     std::{
         ai::{
             prompts::{do_prompt, infer_prompt},
-            providers::{fetch_ai, ChatResponse},
+            providers::{answer, fetch_ai},
             types::Action,
         },
         gen_native_modules_defs, generate_native_module, get_native_module_type,
@@ -114,14 +114,16 @@ pub fn infer(
         ));
     }
 
-    let response: ChatResponse = res.json().expect("AI: Failed to parse response");
-    let answer = &response.choices[0].message.content;
+    let answer = match answer(res) {
+        Ok(answer) => answer,
+        Err(vm_err) => return Err(error::throw(vm_err, vm)),
+    };
 
     if debug {
         println!("AI -> {}", answer);
     }
 
-    let parsed_answer = ai_response_parser(answer);
+    let parsed_answer = ai_response_parser(&answer);
     if let Some(v) = parsed_answer {
         return Ok(v);
     } else {
@@ -166,14 +168,16 @@ pub fn do_fn(
         ));
     }
 
-    let response: ChatResponse = res.json().expect("AI.DO: Failed to parse response");
-    let answer = &response.choices[0].message.content;
+    let answer = match answer(res) {
+        Ok(answer) => answer,
+        Err(vm_err) => return Err(error::throw(vm_err, vm)),
+    };
 
     if debug {
         println!("AI -> {}", answer);
     }
 
-    let cleaned = get_response_json(answer);
+    let cleaned = get_response_json(&answer);
     let instructions: Vec<AIAction> = if let Ok(val) = serde_json::from_str(cleaned.as_str()) {
         val
     } else {
@@ -240,7 +244,7 @@ pub fn exec(
 ) -> Result<Value, VMError> {
     // resolve 'self'
     let (_self, _self_ref) = if let Some(_this) = _self {
-        if let MemObject::NativeStruct(NativeStruct::Action(ns)) = vm.memory.resolve(&_this) {
+        if let Ok(MemObject::NativeStruct(NativeStruct::Action(ns))) = vm.memory.resolve(&_this) {
             (ns, _this)
         } else {
             unreachable!()
@@ -285,12 +289,12 @@ pub fn exec(
             }
             return Ok(Value::RawValue(RawValue::Nothing));
         }
-        _ => {
-            // TODO: use self-vm errors system
-            // in principle this should not happen since
-            // to the AI should arrive only valid callable
-            // members from the stdlib modules
-            panic!("error, member is not callable");
-        }
+        // in principle this should not happen since
+        // to the AI should arrive only valid callable
+        // members from the stdlib modules
+        _ => Err(error::throw(
+            VMErrorType::NotCallableError(format!("{}.{}", _self.module, _self.member)),
+            vm,
+        )),
     }
 }
@@ -25,10 +25,10 @@ pub fn generate_struct() -> (String, Vec<(String, MemObject)>) {
         vec![], // TODO: load params to native functions
         Engine::Native(do_fn),
     ));
-    let engine_ref = MemObject::StructDeclaration(StructDeclaration {
-        identifier: "Engine".to_string(),
-        fields: vec![("name".to_string(), DataType::Utf8)],
-    });
+    let engine_ref = MemObject::StructDeclaration(StructDeclaration::new(
+        "Engine".to_string(),
+        vec![("name".to_string(), DataType::Utf8)],
+    ));
 
     fields.push(("infer".to_string(), infer_ref));
     fields.push(("do".to_string(), do_ref));
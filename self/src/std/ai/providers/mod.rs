@@ -44,8 +44,23 @@ pub fn fetch_ai(prompt: String) -> Result<Response, VMErrorType> {
     };
 
     match ai_engine.as_str() {
-        "openai" => Ok(openai::fetch(prompt)),
-        "mistral" => Ok(mistral::fetch(prompt)),
+        "openai" => openai::fetch(prompt),
+        "mistral" => mistral::fetch(prompt),
         _ => Err(VMErrorType::AI(AIError::AIEngineNotImplemented(ai_engine))),
     }
 }
+
+// text of the first choice the engine answered with. a body that is
+// not a chat response is reported as a failed request
+pub fn answer(res: Response) -> Result<String, VMErrorType> {
+    let invalid = |reason: String| {
+        VMErrorType::AI(AIError::AIFetchError(format!(
+            "the engine answer is not valid: {reason}"
+        )))
+    };
+    let response: ChatResponse = res.json().map_err(|err| invalid(err.to_string()))?;
+    match response.choices.into_iter().next() {
+        Some(choice) => Ok(choice.message.content),
+        None => Err(invalid("it has no choices".to_string())),
+    }
+}
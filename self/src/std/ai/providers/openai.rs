@@ -2,10 +2,17 @@ use std::{env, vec};
 
 use reqwest::{blocking::Client, blocking::Response};
 
-use crate::std::ai::providers::{ChatRequest, Message};
+use crate::{
+    core::error::{ai_errors::AIError, VMErrorType},
+    std::ai::providers::{ChatRequest, Message},
+};
 
-pub fn fetch(prompt: String) -> Response {
-    let api_key = env::var("OPENAI_API_KEY").expect("OPENAI_API_KEY not set");
+pub fn fetch(prompt: String) -> Result<Response, VMErrorType> {
+    let Ok(api_key) = env::var("OPENAI_API_KEY") else {
+        return Err(VMErrorType::AI(AIError::AIFetchError(
+            "OPENAI_API_KEY is not set".to_string(),
+        )));
+    };
 
     let client = Client::new();
     let request_body = ChatRequest {
@@ -16,12 +23,10 @@ pub fn fetch(prompt: String) -> Response {
         }],
     };
 
-    let res = client
+    client
         .post("https://api.openai.com/v1/chat/completions")
         .bearer_auth(api_key)
         .json(&request_body)
         .send()
-        .expect("AI: Failed to send request");
-
-    res
+        .map_err(|err| VMErrorType::AI(AIError::AIFetchError(err.to_string())))
 }
@@ -0,0 +1,153 @@
+use crate::{
+    core::error::VMError,
+    memory::{Handle, MemObject},
+    std::vector,
+    types::{
+        object::{
+            func::{Engine, Function},
+            map::Map,
+            vector::Vector,
+        },
+        raw::{bool::Bool, u32::U32, RawValue},
+        Value,
+    },
+    vm::Vm,
+};
+
+// resolve 'self'
+fn resolve_map(vm: &Vm, _self: Option<Handle>) -> &Map {
+    if let Some(_this) = _self {
+        if let Ok(MemObject::Map(map)) = vm.memory.resolve(&_this) {
+            map
+        } else {
+            unreachable!()
+        }
+    } else {
+        unreachable!()
+    }
+}
+
+// keys
+pub fn keys_obj() -> MemObject {
+    MemObject::Function(Function::new(
+        "keys".to_string(),
+        vec![],
+        Engine::Native(keys),
+    ))
+}
+
+fn keys(
+    vm: &mut Vm,
+    _self: Option<Handle>,
+    params: Vec<Value>,
+    debug: bool,
+) -> Result<Value, VMError> {
+    let keys = resolve_map(vm, _self).keys.clone();
+    let elements = keys
+        .into_iter()
+        .map(|key| Value::Handle(vm.memory.alloc(MemObject::String(key))))
+        .collect();
+
+    let mut keys_vector = Vector::new(elements);
+    vector::init_vector_members(&mut keys_vector, vm);
+    Ok(Value::Handle(
+        vm.memory.alloc(MemObject::Vector(keys_vector)),
+    ))
+}
+
+// values
+pub fn values_obj() -> MemObject {
+    MemObject::Function(Function::new(
+        "values".to_string(),
+        vec![],
+        Engine::Native(values),
+    ))
+}
+
+fn values(
+    vm: &mut Vm,
+    _self: Option<Handle>,
+    params: Vec<Value>,
+    debug: bool,
+) -> Result<Value, VMError> {
+    let elements = resolve_map(vm, _self).values();
+
+    let mut values_vector = Vector::new(elements);
+    vector::init_vector_members(&mut values_vector, vm);
+    Ok(Value::Handle(
+        vm.memory.alloc(MemObject::Vector(values_vector)),
+    ))
+}
+
+// has
+pub fn has_obj() -> MemObject {
+    MemObject::Function(Function::new(
+        "has".to_string(),
+        vec!["key".to_string()],
+        Engine::Native(has),
+    ))
+}
+
+fn has(
+    vm: &mut Vm,
+    _self: Option<Handle>,
+    params: Vec<Value>,
+    debug: bool,
+) -> Result<Value, VMError> {
+    let key = params[0].as_string_obj(vm)?;
+    let found = resolve_map(vm, _self).has(&key);
+
+    Ok(Value::RawValue(RawValue::Bool(Bool::new(found))))
+}
+
+// remove
+pub fn remove_obj() -> MemObject {
+    MemObject::Function(Function::new(
+        "remove".to_string(),
+        vec!["key".to_string()],
+        Engine::Native(remove),
+    ))
+}
+
+fn remove(
+    vm: &mut Vm,
+    _self: Option<Handle>,
+    params: Vec<Value>,
+    debug: bool,
+) -> Result<Value, VMError> {
+    let key = params[0].as_string_obj(vm)?;
+    let removed = if let Some(_this) = _self {
+        if let Ok(MemObject::Map(map)) = vm.memory.resolve_mut(&_this) {
+            map.remove(&key)
+        } else {
+            unreachable!()
+        }
+    } else {
+        unreachable!()
+    };
+
+    // removing a missing key is not an error, it returns nothing
+    Ok(removed.unwrap_or(Value::RawValue(RawValue::Nothing)))
+}
+
+// len
+pub fn len_obj() -> MemObject {
+    MemObject::Function(Function::new(
+        "len".to_string(),
+        vec![],
+        Engine::Native(len),
+    ))
+}
+
+fn len(
+    vm: &mut Vm,
+    _self: Option<Handle>,
+    params: Vec<Value>,
+    debug: bool,
+) -> Result<Value, VMError> {
+    let _self = resolve_map(vm, _self);
+
+    Ok(Value::RawValue(RawValue::U32(U32::new(
+        _self.keys.len() as u32
+    ))))
+}
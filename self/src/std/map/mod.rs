@@ -0,0 +1,31 @@
+use std::collections::HashMap;
+
+use crate::{
+    memory::MemObject,
+    types::{object::map::Map, Value},
+    vm::Vm,
+};
+mod members;
+
+pub fn init_lib() -> Vec<(String, MemObject)> {
+    let mut fields = vec![];
+
+    fields.push(("map.keys".to_string(), members::keys_obj()));
+    fields.push(("map.values".to_string(), members::values_obj()));
+    fields.push(("map.has".to_string(), members::has_obj()));
+    fields.push(("map.remove".to_string(), members::remove_obj()));
+    fields.push(("map.len".to_string(), members::len_obj()));
+
+    fields
+}
+
+pub fn init_map_members(map: &mut Map, vm: &Vm) {
+    let mut members = HashMap::new();
+    for member in ["keys", "values", "has", "remove", "len"] {
+        if let Some(mem) = vm.get_handler(&format!("map.{}", member)) {
+            members.insert(member.to_string(), Value::Handle(mem));
+        }
+    }
+
+    map.init_map_members(members);
+}
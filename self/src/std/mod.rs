@@ -4,6 +4,7 @@ pub mod ai;
 pub mod env;
 pub mod fs;
 pub mod heap_utils;
+pub mod map;
 pub mod net;
 pub mod os;
 pub mod selfmod;
@@ -93,6 +94,7 @@ pub fn generate_native_module(
 pub fn bootstrap_default_lib() -> Vec<(String, MemObject)> {
     let mut default_lib = vec![];
     default_lib.extend(vector::init_lib());
+    default_lib.extend(map::init_lib());
     default_lib
 }
 
@@ -30,7 +30,8 @@ fn write(
     let data = params[0].as_string_obj(vm)?;
     // resolve 'self'
     let _self = if let Some(_this) = _self {
-        if let MemObject::NativeStruct(NativeStruct::NetStream(ns)) = vm.memory.resolve_mut(&_this)
+        if let Ok(MemObject::NativeStruct(NativeStruct::NetStream(ns))) =
+            vm.memory.resolve_mut(&_this)
         {
             ns
         } else {
@@ -59,7 +60,8 @@ fn read(
 ) -> Result<Value, VMError> {
     // resolve 'self'
     let _self = if let Some(_this) = _self {
-        if let MemObject::NativeStruct(NativeStruct::NetStream(ns)) = vm.memory.resolve_mut(&_this)
+        if let Ok(MemObject::NativeStruct(NativeStruct::NetStream(ns))) =
+            vm.memory.resolve_mut(&_this)
         {
             ns
         } else {
@@ -2,8 +2,10 @@ use crate::core::error::os_errors::OsError;
 use crate::core::error::{self, VMError, VMErrorType};
 use crate::memory::{Handle, MemObject};
 use crate::std::heap_utils::put_string;
+use crate::std::vector;
 use crate::std::{NativeMember, NativeModuleDef};
 use crate::types::object::func::{Engine, Function};
+use crate::types::object::vector::Vector;
 use crate::types::Value;
 use crate::vm::Vm;
 
@@ -34,27 +36,67 @@ fn get_cwd(
     }
 }
 
+// arguments the program was run with, the program first
+fn args(
+    vm: &mut Vm,
+    _self: Option<Handle>,
+    _params: Vec<Value>,
+    debug: bool,
+) -> Result<Value, VMError> {
+    let args = vm.args().to_vec();
+    if debug {
+        println!("OS.ARGS -> {:?}", args);
+    }
+    let elements = args
+        .into_iter()
+        .map(|arg| Value::Handle(put_string(vm, arg)))
+        .collect();
+
+    let mut args_vector = Vector::new(elements);
+    vector::init_vector_members(&mut args_vector, vm);
+    Ok(Value::Handle(
+        vm.memory.alloc(MemObject::Vector(args_vector)),
+    ))
+}
+
 pub fn generate_struct() -> (String, Vec<(String, MemObject)>) {
     (
         "os".to_string(),
-        vec![(
-            "get_cwd".to_string(),
-            MemObject::Function(Function::new(
+        vec![
+            (
                 "get_cwd".to_string(),
-                vec![],
-                Engine::Native(get_cwd),
-            )),
-        )],
+                MemObject::Function(Function::new(
+                    "get_cwd".to_string(),
+                    vec![],
+                    Engine::Native(get_cwd),
+                )),
+            ),
+            (
+                "args".to_string(),
+                MemObject::Function(Function::new(
+                    "args".to_string(),
+                    vec![],
+                    Engine::Native(args),
+                )),
+            ),
+        ],
     )
 }
 
 pub fn generate_mod_def() -> NativeModuleDef {
     NativeModuleDef {
         module: "os".to_string(),
-        members: vec![NativeMember {
-            name: "get_cwd".to_string(),
-            description: "get the current working directory".to_string(),
-            params: None,
-        }],
+        members: vec![
+            NativeMember {
+                name: "get_cwd".to_string(),
+                description: "get the current working directory".to_string(),
+                params: None,
+            },
+            NativeMember {
+                name: "args".to_string(),
+                description: "get the arguments the program was run with".to_string(),
+                params: None,
+            },
+        ],
     }
 }
@@ -9,9 +9,13 @@ PROVIDER.
 use crate::{
     core::error::VMError,
     memory::{Handle, MemObject},
+    std::map,
     types::{
-        object::func::{Engine, Function},
-        raw::RawValue,
+        object::{
+            func::{Engine, Function},
+            map::Map,
+        },
+        raw::{i32::I32, RawValue},
         Value,
     },
     vm::Vm,
@@ -33,3 +37,57 @@ pub fn get_stack_fn_ref() -> MemObject {
         Engine::Native(get_stack),
     ))
 }
+
+// gc_stats
+pub fn gc_stats(
+    vm: &mut Vm,
+    _self: Option<Handle>,
+    params: Vec<Value>,
+    debug: bool,
+) -> Result<Value, VMError> {
+    let stats = vm.gc_stats();
+    let entries = [
+        ("collections", stats.collections),
+        ("allocated", stats.allocated),
+        ("freed", stats.freed),
+        ("live", stats.live),
+        ("threshold", stats.threshold),
+    ]
+    .into_iter()
+    // i32, like number literals, so they can be compared
+    .map(|(key, value)| {
+        let value = value.min(i32::MAX as usize) as i32;
+        (
+            key.to_string(),
+            Value::RawValue(RawValue::I32(I32::new(value))),
+        )
+    })
+    .collect();
+
+    let mut stats_map = Map::new(entries);
+    map::init_map_members(&mut stats_map, vm);
+    Ok(Value::Handle(vm.memory.alloc(MemObject::Map(stats_map))))
+}
+
+pub fn gc_stats_fn_ref() -> MemObject {
+    MemObject::Function(Function::new(
+        "gc_stats".to_string(),
+        vec![],
+        Engine::Native(gc_stats),
+    ))
+}
+
+// gc. the collection runs before the next instruction
+pub fn gc(
+    vm: &mut Vm,
+    _self: Option<Handle>,
+    params: Vec<Value>,
+    debug: bool,
+) -> Result<Value, VMError> {
+    vm.memory.request_collection();
+    Ok(Value::RawValue(RawValue::Nothing))
+}
+
+pub fn gc_fn_ref() -> MemObject {
+    MemObject::Function(Function::new("gc".to_string(), vec![], Engine::Native(gc)))
+}
@@ -1,11 +1,16 @@
 mod members;
 
-use crate::{memory::MemObject, std::selfmod::members::get_stack_fn_ref};
+use crate::{
+    memory::MemObject,
+    std::selfmod::members::{gc_fn_ref, gc_stats_fn_ref, get_stack_fn_ref},
+};
 
 pub fn generate_struct() -> (String, Vec<(String, MemObject)>) {
     let mut fields = vec![];
 
     fields.push(("get_stack".to_string(), get_stack_fn_ref()));
+    fields.push(("gc".to_string(), gc_fn_ref()));
+    fields.push(("gc_stats".to_string(), gc_stats_fn_ref()));
 
     ("self".to_string(), fields)
 }
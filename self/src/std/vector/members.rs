@@ -25,7 +25,7 @@ fn len(
 ) -> Result<Value, VMError> {
     // resolve 'self'
     let _self = if let Some(_this) = _self {
-        if let MemObject::Vector(vec) = vm.memory.resolve_mut(&_this) {
+        if let Ok(MemObject::Vector(vec)) = vm.memory.resolve_mut(&_this) {
             vec
         } else {
             unreachable!()
@@ -56,7 +56,7 @@ fn map(
 ) -> Result<Value, VMError> {
     // resolve 'self'
     let _self = if let Some(_this) = _self {
-        if let MemObject::Vector(vec) = vm.memory.resolve(&_this) {
+        if let Ok(MemObject::Vector(vec)) = vm.memory.resolve(&_this) {
             vec.clone()
         } else {
             unreachable!()
@@ -1,299 +1,367 @@
 /*
-    THIS FILE ONLY RUNS ON DEBUGGING MODE
-    TO ALLOW ENUMERATION OF EACH INSTRUCTION
-    AND EACH DATA
+    DECODES THE BYTECODE INTO INSTRUCTIONS WITHOUT
+    EXECUTING IT. USED BY THE VERIFIER AND BY THE VM,
+    THAT RUNS THE DECODED INSTRUCTIONS
 */
 
+use std::{collections::HashMap, rc::Rc};
+
 use crate::{
-    instructions::Instruction,
+    core::error::bytecode_errors::BytecodeError,
+    instructions::{Code, Instruction},
     opcodes::{DataType, Opcode},
-    types::{raw::RawValue, Value},
-    utils::from_bytes::bytes_to_data,
-    vm::Vm,
 };
 
-pub struct Translator {
-    bytecode: Vec<u8>,
+pub struct Translator<'a> {
+    bytecode: &'a [u8],
+    // constants section of the module being decoded
+    constants: &'a [String],
+    // start of the instruction being decoded
+    start: usize,
+    // next byte to read
     pc: usize,
 }
 
-impl Translator {
-    pub fn new(bytecode: Vec<u8>) -> Translator {
-        Translator { bytecode, pc: 0 }
-    }
-
-    fn new_with_pc(bytecode: Vec<u8>, pc: usize) -> Translator {
-        Translator { bytecode, pc: pc }
+impl<'a> Translator<'a> {
+    fn new_with_pc(bytecode: &'a [u8], constants: &'a [String], pc: usize) -> Translator<'a> {
+        Translator {
+            bytecode,
+            constants,
+            start: pc,
+            pc,
+        }
     }
 
-    pub fn get_instruction(pc: usize, bytecode: &Vec<u8>) -> (Instruction, usize) {
-        let mut t = Translator::new_with_pc(bytecode.clone(), pc);
-
-        match Opcode::to_opcode(t.bytecode[t.pc]) {
-            Opcode::Zero => (Instruction::Zero, 1),
-            Opcode::LoadConst => {
-                if t.pc + 1 >= t.bytecode.len() {
-                    panic!("Invalid LOAD_CONST instruction at position {}", t.pc);
-                }
-
-                t.pc += 1;
-                let (data_type, value_bytes) = t.get_value_length();
-
-                (
-                    Instruction::LoadConst {
-                        data_type,
-                        value: value_bytes,
-                    },
-                    pc.abs_diff(t.pc),
-                )
-            }
-            Opcode::LoadVar => {
-                if t.pc + 1 >= t.bytecode.len() {
-                    panic!("Invalid LOAD_VAR instruction at position {}", t.pc);
-                }
-
-                t.pc += 1;
-                let (data_type, value_bytes) = t.get_value_length();
-
-                (
-                    Instruction::LoadVar {
-                        data_type,
-                        identifier: value_bytes,
-                    },
-                    pc.abs_diff(t.pc),
-                )
-            }
-            Opcode::JumpIfFalse => {
-                t.pc += 4;
-                (Instruction::JumpIfFalse, pc.abs_diff(t.pc))
-            }
-            Opcode::Jump => {
-                t.pc += 4;
-                (Instruction::Jump, pc.abs_diff(t.pc))
+    // decodes the instruction starting at pc and returns it with its
+    // length in bytes. function and module bodies are part of the
+    // instruction declaring them, so the length skips over them
+    pub fn get_instruction(
+        pc: usize,
+        bytecode: &'a [u8],
+        constants: &'a [String],
+    ) -> Result<(Instruction, usize), BytecodeError> {
+        let mut t = Translator::new_with_pc(bytecode, constants, pc);
+
+        let opcode = t.read_byte("instruction")?;
+        let instruction = match Opcode::to_opcode(opcode) {
+            Opcode::Zero | Opcode::Unknown => {
+                return Err(BytecodeError::UnknownOpcode { opcode, at: pc })
             }
-            Opcode::Print => {
-                // get u32 value. 4 bytes based on the type plus the current
-                let value_length = 4;
-                if t.pc + value_length >= t.bytecode.len() {
-                    panic!("Invalid print instruction at position {}", t.pc);
-                }
-
-                let value_bytes = &t.bytecode[t.pc + 1..t.pc + 5];
-                let number_of_args = u32::from_le_bytes(
-                    value_bytes.try_into().expect("Provided value is incorrect"),
-                );
-                t.pc += 4;
-                (Instruction::Print { number_of_args }, pc.abs_diff(t.pc))
+            Opcode::LoadConst => {
+                let (data_type, value) = t.read_value("load_const")?;
+                Instruction::LoadConst { data_type, value }
             }
-            Opcode::Println => {
-                // get u32 value. 4 bytes based on the type plus the current
-                let value_length = 4;
-                if t.pc + value_length >= t.bytecode.len() {
-                    panic!("Invalid print instruction at position {}", t.pc);
+            Opcode::LoadVar => Instruction::LoadVar {
+                identifier: t.read_identifier("load_var")?,
+            },
+            Opcode::StoreVar => {
+                // 0x00 inmutable | 0x01 mutable | 0x02 reassignment
+                let at = t.pc;
+                let (mutable, reassign) = match t.read_byte("store_var")? {
+                    0x00 => (false, false),
+                    0x01 => (true, false),
+                    0x02 => (true, true),
+                    _ => {
+                        return Err(BytecodeError::InvalidOperand {
+                            instruction: "store_var".to_string(),
+                            at,
+                        })
+                    }
+                };
+                let identifier = t.read_identifier("store_var")?;
+                Instruction::StoreVar {
+                    identifier,
+                    mutable,
+                    reassign,
                 }
-
-                let value_bytes = &t.bytecode[t.pc + 1..t.pc + 5];
-                let number_of_args = u32::from_le_bytes(
-                    value_bytes.try_into().expect("Provided value is incorrect"),
-                );
-                t.pc += 4;
-                (Instruction::Println { number_of_args }, pc.abs_diff(t.pc))
             }
-            Opcode::Add => (Instruction::Add, 0),
-            Opcode::Substract => (Instruction::Substract, 0),
-            Opcode::Multiply => (Instruction::Multiply, 0),
-            Opcode::Divide => (Instruction::Divide, 0),
-            Opcode::GreaterThan => (Instruction::GreaterThan, 0),
-            Opcode::LessThan => (Instruction::LessThan, 0),
-            Opcode::Equals => (Instruction::Equals, 0),
-            Opcode::NotEquals => (Instruction::NotEquals, 0),
+            Opcode::LoadLocal => Instruction::LoadLocal {
+                depth: t.read_u32("load_local")?,
+                slot: t.read_u32("load_local")?,
+                identifier: t.read_identifier("load_local")?,
+            },
+            Opcode::StoreLocal => Instruction::StoreLocal {
+                depth: t.read_u32("store_local")?,
+                slot: t.read_u32("store_local")?,
+                identifier: t.read_identifier("store_local")?,
+            },
+            Opcode::LoadGlobal => Instruction::LoadGlobal {
+                identifier: t.read_identifier("load_global")?,
+            },
+            Opcode::JumpIfFalse => Instruction::JumpIfFalse {
+                offset: i32::from_le_bytes(t.read_word("jump_if_false")?),
+            },
+            Opcode::Jump => Instruction::Jump {
+                offset: i32::from_le_bytes(t.read_word("jump")?),
+            },
+            Opcode::TryStart => Instruction::TryStart {
+                offset: i32::from_le_bytes(t.read_word("try_start")?),
+            },
+            Opcode::Print => Instruction::Print {
+                number_of_args: t.read_u32("print")?,
+            },
+            Opcode::Println => Instruction::Println {
+                number_of_args: t.read_u32("println")?,
+            },
+            Opcode::Call => Instruction::Call {
+                number_of_args: t.read_u32("call")?,
+            },
+            Opcode::FFI_Call => Instruction::FFI_Call {
+                number_of_args: t.read_u32("ffi_call")?,
+            },
+            Opcode::Add => Instruction::Add,
+            Opcode::Substract => Instruction::Substract,
+            Opcode::Multiply => Instruction::Multiply,
+            Opcode::Divide => Instruction::Divide,
+            Opcode::GreaterThan => Instruction::GreaterThan,
+            Opcode::LessThan => Instruction::LessThan,
+            Opcode::GreaterThanOrEqual => Instruction::GreaterThanOrEqual,
+            Opcode::LessThanOrEqual => Instruction::LessThanOrEqual,
+            Opcode::Equals => Instruction::Equals,
+            Opcode::NotEquals => Instruction::NotEquals,
+            Opcode::Not => Instruction::Not,
             Opcode::StructDec => {
-                // skip StructDec opcode
-                t.pc += 1;
-
-                // identifier
-                let (identifier_data_type, identifier_bytes) = t.get_value_length();
-                if identifier_data_type != DataType::Utf8 {
-                    // TODO: use self-vm errors
-                    panic!("Identifier type should be a string encoded as utf8")
-                }
-
-                // TODO: use self-vm errors
-                let identifier_name = String::from_utf8(identifier_bytes)
-                    .expect("Identifier bytes should be valid UTF-8");
-
-                // read fields number
-                t.pc += 1;
-                let fields_num = Vm::read_offset(&bytecode[t.pc..t.pc + 4]);
-                t.pc += 4;
+                let identifier = t.read_identifier("struct_declaration")?;
+                let fields_num = t.read_u32("struct_declaration")?;
 
                 // struct fields [raw_string][type][raw_string][type]
                 //               (x)B        1B    (x)B        1B
-                let mut counter = 0;
                 let mut fields = vec![];
-                while counter < fields_num {
-                    // field
-                    let (field_data_type, field_bytes) = t.get_value_length();
-                    if field_data_type != DataType::Utf8 {
-                        // TODO: use self-vm errors
-                        panic!("Identifier type should be a string encoded as utf8")
-                    }
-                    let field_name =
-                        String::from_utf8(field_bytes).expect("Field bytes should be valid UTF-8"); // TODO: use self-vm errors
-                    t.pc += 1;
-
-                    // annotation
-                    let annotation = DataType::to_opcode(bytecode[t.pc]);
-                    t.pc += 1;
-
-                    fields.push(format!("{}: {}", field_name, annotation.as_str()));
-                    counter += 1;
+                for _ in 0..fields_num {
+                    let field_name = t.read_identifier("struct_declaration")?;
+                    let annotation = DataType::to_opcode(t.read_byte("struct_declaration")?);
+                    fields.push((field_name, annotation));
                 }
 
-                t.pc += 1;
-
-                (
-                    Instruction::StructDec {
-                        identifier: identifier_name.to_string(),
-                        fields,
-                    },
-                    pc.abs_diff(t.pc),
-                )
+                Instruction::StructDec { identifier, fields }
             }
-            Opcode::FuncDec => {
-                // identifier
-                if t.pc + 1 >= t.bytecode.len() {
-                    panic!("Invalid FUNC_DEC instruction at position {}", t.pc);
+            opcode @ (Opcode::FuncDec | Opcode::MethodDec) => {
+                let name = match opcode {
+                    Opcode::MethodDec => "method_declaration",
+                    _ => "function_declaration",
+                };
+                let identifier = t.read_identifier(name)?;
+                let parameters_num = t.read_u32(name)?;
+                let body_length = t.read_u32(name)?;
+                t.read_body(name, body_length)?;
+
+                match opcode {
+                    Opcode::MethodDec => Instruction::MethodDec {
+                        identifier,
+                        parameters_num,
+                        body_length,
+                    },
+                    _ => Instruction::FuncDec {
+                        identifier,
+                        parameters_num,
+                        body_length,
+                    },
                 }
+            }
+            Opcode::ImplDec => Instruction::ImplDec {
+                identifier: t.read_identifier("impl_declaration")?,
+                methods_num: t.read_u32("impl_declaration")?,
+            },
+            Opcode::Import => {
+                let body_length = t.read_u32("import")?;
+                t.read_body("import", body_length)?;
+                Instruction::Import { body_length }
+            }
+            Opcode::Export => Instruction::Export,
+            Opcode::Return => Instruction::Return,
+            Opcode::GetProperty => Instruction::GetProperty,
+            Opcode::SetProperty => Instruction::SetProperty,
+            Opcode::GetIndex => Instruction::GetIndex,
+            Opcode::SetIndex => Instruction::SetIndex,
+            Opcode::TryEnd => Instruction::TryEnd,
+            Opcode::Throw => Instruction::Throw,
+        };
 
-                t.pc += 1;
-                let (_, value_bytes) = t.get_value_length(); // t's pc gets modified by get_value_length
-                let identifier_name =
-                    String::from_utf8(value_bytes).expect("Identifier bytes should be valid UTF-8");
-
-                // function body length
-                if t.pc + 4 >= t.bytecode.len() {
-                    panic!("Invalid FUNC_DEC instruction at position {}", t.pc);
-                }
-                t.pc += 4;
+        Ok((instruction, t.pc - pc))
+    }
 
-                let value_bytes = &t.bytecode[t.pc + 1..t.pc + 5];
-                let body_length = u32::from_le_bytes(
-                    value_bytes.try_into().expect("Provided value is incorrect"),
-                );
-                t.pc += 4 + body_length as usize;
+    // decodes a whole bytecode to run it. the bytecode is expected
+    // to be verified, the first problem found is returned
+    pub fn translate(bytecode: &'a [u8]) -> Result<Code, BytecodeError> {
+        Translator::translate_module(bytecode, 0, bytecode.len())
+    }
 
-                (
-                    Instruction::FuncDec {
-                        identifier: identifier_name,
-                    },
-                    pc.abs_diff(t.pc),
-                )
-            }
-            Opcode::StoreVar => {
-                if t.pc + 1 >= t.bytecode.len() {
-                    panic!("Invalid STORE_VAR instruction at position {}.", t.pc);
-                } else {
-                    t.pc += 1;
-                }
+    // modules start with their constants section, shared
+    // by the functions declared on them
+    fn translate_module(
+        bytecode: &'a [u8],
+        start: usize,
+        end: usize,
+    ) -> Result<Code, BytecodeError> {
+        let (constants, code_start) = Translator::read_constants(&bytecode[..end], start)?;
+        Translator::translate_stream(bytecode, &constants, code_start, end)
+    }
 
-                // 0x00 inmutable | 0x00 mutable
-                let mutable = match t.bytecode[t.pc] {
-                    0x00 => false,
-                    0x01 => true,
-                    _ => {
-                        panic!("Invalid STORE_VAR instruction at position {}. Needed mutability property.", t.pc);
-                    }
-                };
-                t.pc += 1;
+    fn translate_stream(
+        bytecode: &'a [u8],
+        constants: &[String],
+        start: usize,
+        end: usize,
+    ) -> Result<Code, BytecodeError> {
+        let stream = &bytecode[..end];
+        let mut code = Code {
+            end,
+            ..Code::default()
+        };
 
-                // identifier
-                let (identifier_data_type, identifier_bytes) = t.get_value_length();
-                if identifier_data_type != DataType::Utf8 {
-                    panic!("Identifier type should be a string encoded as utf8")
+        let mut pc = start;
+        while pc < end {
+            let (instruction, length) = Translator::get_instruction(pc, stream, constants)?;
+            let body_length = match &instruction {
+                Instruction::FuncDec { body_length, .. }
+                | Instruction::MethodDec { body_length, .. }
+                | Instruction::Import { body_length } => Some(*body_length as usize),
+                _ => None,
+            };
+            let body = match body_length {
+                Some(body_length) => {
+                    let body_end = pc + length;
+                    let body_start = body_end - body_length;
+                    let body = match instruction {
+                        // native modules have an empty body
+                        Instruction::Import { .. } if body_length > 0 => {
+                            Translator::translate_module(bytecode, body_start, body_end)?
+                        }
+                        _ => {
+                            Translator::translate_stream(bytecode, constants, body_start, body_end)?
+                        }
+                    };
+                    Some(Rc::new(body))
                 }
+                None => None,
+            };
 
-                let identifier_name = String::from_utf8(identifier_bytes)
-                    .expect("Identifier bytes should be valid UTF-8");
+            code.instructions.push(instruction);
+            code.positions.push(pc);
+            code.bodies.push(body);
+            pc += length;
+        }
 
-                (
-                    Instruction::StoreVar {
-                        identifier: identifier_name,
-                        mutable,
-                    },
-                    pc.abs_diff(t.pc),
-                )
-            }
-            Opcode::Call => {
-                // get u32 value. 4 bytes based on the type plus the current
-                let value_length = 4;
-                if t.pc + value_length >= t.bytecode.len() {
-                    panic!("Invalid print instruction at position {}", t.pc);
+        // jumps are stored as byte offsets, the vm moves
+        // between instructions
+        let indexes: HashMap<usize, usize> = code
+            .positions
+            .iter()
+            .enumerate()
+            .map(|(index, position)| (*position, index))
+            .collect();
+        for (index, instruction) in code.instructions.iter().enumerate() {
+            let at = code.positions[index];
+            let target = match instruction {
+                Instruction::Jump { offset } => at as isize + 4 + *offset as isize,
+                Instruction::JumpIfFalse { offset } | Instruction::TryStart { offset } => {
+                    at as isize + 5 + *offset as isize
+                }
+                _ => {
+                    code.targets.push(None);
+                    continue;
                 }
+            };
+            let target_index = if target == end as isize {
+                Some(code.instructions.len())
+            } else if target >= 0 {
+                indexes.get(&(target as usize)).copied()
+            } else {
+                None
+            };
+            match target_index {
+                Some(target_index) => code.targets.push(Some(target_index)),
+                None => {
+                    return Err(BytecodeError::InvalidJumpTarget {
+                        instruction: instruction.get_type(),
+                        at,
+                        target,
+                    })
+                }
+            }
+        }
 
-                let value_bytes = &t.bytecode[t.pc + 1..t.pc + 5];
-                let _ = u32::from_le_bytes(
-                    value_bytes.try_into().expect("Provided value is incorrect"),
-                );
-                t.pc += 4;
+        Ok(code)
+    }
 
-                // identifier
-                t.pc += 1;
-                let (_, _) = t.get_value_length();
+    fn truncated(&self, instruction: &str) -> BytecodeError {
+        BytecodeError::TruncatedInstruction {
+            instruction: instruction.to_string(),
+            at: self.start,
+        }
+    }
 
-                (Instruction::Call, pc.abs_diff(t.pc))
+    fn read_byte(&mut self, instruction: &str) -> Result<u8, BytecodeError> {
+        match self.bytecode.get(self.pc) {
+            Some(byte) => {
+                self.pc += 1;
+                Ok(*byte)
             }
-            Opcode::FFI_Call => {
-                // get u32 value. 4 bytes based on the type plus the current
-                let value_length = 4;
-                if t.pc + value_length >= t.bytecode.len() {
-                    panic!("Invalid print instruction at position {}", t.pc);
-                }
+            None => Err(self.truncated(instruction)),
+        }
+    }
 
-                let value_bytes = &t.bytecode[t.pc + 1..t.pc + 5];
-                let number_of_args = u32::from_le_bytes(
-                    value_bytes.try_into().expect("Provided value is incorrect"),
-                );
-                t.pc += 4;
-                (Instruction::FFI_Call { number_of_args }, pc.abs_diff(t.pc))
+    fn read_bytes(&mut self, length: usize, instruction: &str) -> Result<&'a [u8], BytecodeError> {
+        match self.bytecode.get(self.pc..self.pc + length) {
+            Some(bytes) => {
+                self.pc += length;
+                Ok(bytes)
             }
-            Opcode::GetProperty => (Instruction::GetProperty, 1),
-            Opcode::Unknown => (Instruction::Unknown, 1),
-            _ => (Instruction::Unknown, 1),
+            None => Err(self.truncated(instruction)),
         }
     }
 
-    pub fn get_instruction_info(instruction: &Instruction) -> String {
-        match instruction {
-            Instruction::LoadConst {
-                data_type,
-                value: _,
-            } => data_type.as_str().to_string(),
-            Instruction::LoadVar {
-                data_type,
-                identifier: _,
-            } => data_type.as_str().to_string(),
-            Instruction::StoreVar {
-                identifier,
-                mutable: _,
-            } => identifier.to_string(),
-            Instruction::Print { number_of_args } => number_of_args.to_string(),
-            Instruction::Println { number_of_args } => number_of_args.to_string(),
-            Instruction::FFI_Call { number_of_args } => number_of_args.to_string(),
-            Instruction::FuncDec { identifier } => identifier.to_string(),
-            Instruction::StructDec { identifier, fields } => {
-                let mut mem = identifier.to_string();
-                for field in fields {
-                    mem += format!("\n * {field}").as_str()
-                }
-                mem
+    // offsets and counts are stored as 4 bytes little endian
+    fn read_word(&mut self, instruction: &str) -> Result<[u8; 4], BytecodeError> {
+        let bytes = self.read_bytes(4, instruction)?;
+        Ok([bytes[0], bytes[1], bytes[2], bytes[3]])
+    }
+
+    fn read_u32(&mut self, instruction: &str) -> Result<u32, BytecodeError> {
+        Ok(u32::from_le_bytes(self.read_word(instruction)?))
+    }
+
+    // nested bodies are only skipped here, the verifier
+    // decodes them as a bytecode of their own
+    fn read_body(&mut self, instruction: &str, body_length: u32) -> Result<(), BytecodeError> {
+        let available = self.bytecode.len() - self.pc;
+        if body_length as usize > available {
+            return Err(BytecodeError::BodyOutOfBounds {
+                instruction: instruction.to_string(),
+                at: self.start,
+                body_length: body_length as usize,
+                available,
+            });
+        }
+        self.pc += body_length as usize;
+        Ok(())
+    }
+
+    // [u32 count] followed by each string as [u32 length][bytes].
+    // returns the constants and the position right after them
+    pub fn read_constants(
+        bytecode: &'a [u8],
+        start: usize,
+    ) -> Result<(Vec<String>, usize), BytecodeError> {
+        let mut t = Translator::new_with_pc(bytecode, &[], start);
+        let count = t.read_u32("constants")?;
+        let mut constants = vec![];
+        for _ in 0..count {
+            let at = t.pc;
+            let length = t.read_u32("constants")? as usize;
+            match std::str::from_utf8(t.read_bytes(length, "constants")?) {
+                Ok(constant) => constants.push(constant.to_string()),
+                Err(_) => return Err(BytecodeError::InvalidUtf8 { at }),
             }
-            _ => "".to_string(),
         }
+
+        Ok((constants, t.pc))
     }
 
-    fn get_value_length(&mut self) -> (DataType, Vec<u8>) {
-        let data_type = DataType::to_opcode(self.bytecode[self.pc]);
+    // [type][value] where utf8 values are [utf8][u32 constant index]
+    fn read_value(&mut self, instruction: &str) -> Result<(DataType, Vec<u8>), BytecodeError> {
+        let at = self.pc;
+        let data_type = DataType::to_opcode(self.read_byte(instruction)?);
         let value_length = match data_type {
             DataType::I32 => 4,
             DataType::I64 => 8,
@@ -303,31 +371,39 @@ impl Translator {
             DataType::Nothing => 0,
             DataType::Bool => 1,
             DataType::Utf8 => {
-                self.pc += 1;
-                let (data_type, value) = self.get_value_length();
-                if data_type != DataType::U32 {
-                    panic!("bad utf8 value length")
-                }
-
-                let (string_length, _) = bytes_to_data(&DataType::U32, &value);
-                if let Value::RawValue(RawValue::U32(val)) = string_length {
-                    val.value as usize
-                } else {
-                    panic!("Unexpected value type for string length");
-                }
-            } // hardcoded for the moment
+                let constant = self.read_constant(instruction)?;
+                return Ok((data_type, constant.as_bytes().to_vec()));
+            }
+            DataType::StructLiteral => 4, // fields count
+            DataType::Vector => 4,        // elements count
+            DataType::Map => 4,           // entries count
             _ => {
-                panic!("Unsupported datatype")
+                return Err(BytecodeError::UnsupportedDataType {
+                    data_type: data_type.as_str().to_string(),
+                    at,
+                })
             }
         };
 
-        if (self.pc + value_length) >= self.bytecode.len() {
-            panic!("Invalid value size at position {}", self.pc + 1);
-        };
+        let value = self.read_bytes(value_length, instruction)?.to_vec();
+        Ok((data_type, value))
+    }
 
-        let value_bytes = self.bytecode[self.pc + 1..self.pc + 1 + value_length].to_vec();
-        self.pc += value_length;
+    // identifiers are stored as an index on the constants
+    fn read_identifier(&mut self, instruction: &str) -> Result<String, BytecodeError> {
+        Ok(self.read_constant(instruction)?.to_string())
+    }
 
-        (data_type, value_bytes)
+    fn read_constant(&mut self, instruction: &str) -> Result<&'a str, BytecodeError> {
+        let at = self.pc;
+        let index = self.read_u32(instruction)?;
+        match self.constants.get(index as usize) {
+            Some(constant) => Ok(constant),
+            None => Err(BytecodeError::UnknownConstant {
+                index,
+                available: self.constants.len(),
+                at,
+            }),
+        }
     }
 }
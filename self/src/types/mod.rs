@@ -32,7 +32,7 @@ impl Value {
                 }
             }
             Value::BoundAccess(x) => x.property.to_string(vm),
-            Value::Handle(x) => vm.memory.resolve(x).to_string(vm),
+            Value::Handle(_) => object::value_to_string(self, vm, &mut vec![]),
             _ => "unkown_value_type".to_string(),
         }
     }
@@ -49,20 +49,23 @@ impl Value {
 
     pub fn as_mem_obj<'vm>(&self, vm: &'vm Vm) -> Result<&'vm MemObject, VMError> {
         match self {
-            Value::Handle(v) => Ok(vm.memory.resolve(&v)),
+            Value::Handle(v) => vm.memory.resolve(v).map_err(|err| error::throw(err, vm)),
             // assuming that every BoundAccess is created type checking the property, we only need to get the property unwrapped value
             Value::BoundAccess(v) => Ok(v.property.as_mem_obj(vm)?),
-            _ => {
-                // TODO: use self-vm errors system
-                panic!("invalid type to use as_mem_obj struct type")
-            }
+            _ => Err(error::throw(
+                VMErrorType::TypeMismatch {
+                    expected: "handle".to_string(),
+                    received: self.get_type(),
+                },
+                vm,
+            )),
         }
     }
 
     pub fn as_string_obj(&self, vm: &Vm) -> Result<String, VMError> {
         match self {
             Value::Handle(r) => {
-                let heap_obj = vm.memory.resolve(&r);
+                let heap_obj = vm.memory.resolve(r).map_err(|err| error::throw(err, vm))?;
                 let request = match heap_obj {
                     MemObject::String(s) => s,
                     _ => {
@@ -122,7 +125,7 @@ impl Value {
     pub fn as_function_obj(&self, vm: &Vm) -> Result<Function, VMError> {
         match self {
             Value::Handle(r) => {
-                let heap_obj = vm.memory.resolve(&r);
+                let heap_obj = vm.memory.resolve(r).map_err(|err| error::throw(err, vm))?;
                 let request = match heap_obj {
                     MemObject::Function(f) => f.clone(),
                     _ => {
@@ -183,7 +186,7 @@ impl Value {
                 _ => {
                     return Err(error::throw(
                         VMErrorType::TypeMismatch {
-                            expected: "string".to_string(),
+                            expected: "bool".to_string(),
                             received: r.get_type_string(),
                         },
                         vm,
@@ -193,7 +196,7 @@ impl Value {
             Value::HeapRef(r) => {
                 return Err(error::throw(
                     VMErrorType::TypeMismatch {
-                        expected: "string".to_string(),
+                        expected: "bool".to_string(),
                         received: "heap_ref".to_string(),
                     },
                     vm,
@@ -202,17 +205,21 @@ impl Value {
             Value::BoundAccess(_) => {
                 return Err(error::throw(
                     VMErrorType::TypeMismatch {
-                        expected: "string".to_string(),
+                        expected: "bool".to_string(),
                         received: "bound_access".to_string(),
                     },
                     vm,
                 ));
             }
-            Value::Handle(_) => {
+            Value::Handle(r) => {
+                let received = match vm.memory.resolve(r) {
+                    Ok(obj) => obj.get_type(),
+                    Err(err) => return Err(error::throw(err, vm)),
+                };
                 return Err(error::throw(
                     VMErrorType::TypeMismatch {
-                        expected: "string".to_string(),
-                        received: "handle".to_string(),
+                        expected: "bool".to_string(),
+                        received,
                     },
                     vm,
                 ));
@@ -220,7 +227,7 @@ impl Value {
             _ => {
                 return Err(error::throw(
                     VMErrorType::TypeMismatch {
-                        expected: "string".to_string(),
+                        expected: "bool".to_string(),
                         received: "unknown_type".to_string(),
                     },
                     vm,
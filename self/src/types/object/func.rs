@@ -1,8 +1,14 @@
-use crate::{core::error::VMError, memory::Handle, types::Value, vm::Vm};
+use std::rc::Rc;
+
+use crate::{
+    core::error::VMError, instructions::Code, memory::Handle, stack::EnvRef, types::Value, vm::Vm,
+};
 
 #[derive(Debug, Clone)]
 pub enum Engine {
-    Bytecode(Vec<u8>),
+    // the body is shared by every function created from
+    // the same declaration
+    Bytecode(Rc<Code>),
     Native(fn(&mut Vm, Option<Handle>, Vec<Value>, bool) -> Result<Value, VMError>),
 }
 
@@ -11,6 +17,12 @@ pub struct Function {
     pub identifier: String,
     pub parameters: Vec<String>,
     pub engine: Engine,
+    // environment where the function was declared. bytecode
+    // functions resolve their free identifiers against it
+    pub env: Option<EnvRef>,
+    // module where the function was declared, empty for
+    // native functions
+    pub module: String,
 }
 
 impl Function {
@@ -19,6 +31,23 @@ impl Function {
             identifier,
             parameters,
             engine,
+            env: None,
+            module: String::new(),
+        }
+    }
+    pub fn new_closure(
+        identifier: String,
+        parameters: Vec<String>,
+        engine: Engine,
+        env: EnvRef,
+        module: String,
+    ) -> Function {
+        Function {
+            identifier,
+            parameters,
+            engine,
+            env: Some(env),
+            module,
         }
     }
     pub fn to_string(&self) -> String {
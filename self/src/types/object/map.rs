@@ -0,0 +1,81 @@
+use std::collections::HashMap;
+
+use crate::{memory::Handle, types::Value, vm::Vm};
+
+use super::value_to_string;
+
+#[derive(Debug, Clone)]
+pub struct Map {
+    pub entries: HashMap<String, Value>,
+    // keys in insertion order, so keys() and printing are stable
+    pub keys: Vec<String>,
+    pub members: HashMap<String, Value>,
+}
+
+impl Map {
+    pub fn new(entries: Vec<(String, Value)>) -> Map {
+        let mut map = Map {
+            entries: HashMap::new(),
+            keys: vec![],
+            members: HashMap::new(),
+        };
+        for (key, value) in entries {
+            map.insert(key, value);
+        }
+        map
+    }
+
+    pub fn init_map_members(&mut self, members: HashMap<String, Value>) {
+        self.members = members
+    }
+
+    pub fn get(&self, key: &str) -> Option<Value> {
+        self.entries.get(key).cloned()
+    }
+
+    pub fn insert(&mut self, key: String, value: Value) {
+        if self.entries.insert(key.clone(), value).is_none() {
+            self.keys.push(key);
+        }
+    }
+
+    pub fn remove(&mut self, key: &str) -> Option<Value> {
+        let removed = self.entries.remove(key);
+        if removed.is_some() {
+            self.keys.retain(|k| k != key);
+        }
+        removed
+    }
+
+    pub fn has(&self, key: &str) -> bool {
+        self.entries.contains_key(key)
+    }
+
+    pub fn values(&self) -> Vec<Value> {
+        self.keys
+            .iter()
+            .filter_map(|key| self.entries.get(key).cloned())
+            .collect()
+    }
+
+    pub fn to_string(&self, vm: &Vm) -> String {
+        self.to_string_visiting(vm, &mut vec![])
+    }
+
+    pub fn to_string_visiting(&self, vm: &Vm, visiting: &mut Vec<Handle>) -> String {
+        let entries: Vec<String> = self
+            .keys
+            .iter()
+            .filter_map(|key| {
+                self.entries
+                    .get(key)
+                    .map(|value| format!("\"{}\": {}", key, value_to_string(value, vm, visiting)))
+            })
+            .collect();
+        format!("{{{}}}", entries.join(", "))
+    }
+
+    pub fn property_access(&self, property: &str) -> Option<Value> {
+        self.members.get(property).cloned()
+    }
+}
@@ -1,6 +1,11 @@
-use crate::{memory::Handle, types::Value};
+use crate::{
+    memory::{Handle, MemObject},
+    types::Value,
+    vm::Vm,
+};
 
 pub mod func;
+pub mod map;
 pub mod native_struct;
 pub mod structs;
 pub mod vector;
@@ -17,6 +22,55 @@ impl BoundAccess {
     }
 
     pub fn to_string(&self) -> String {
-        format!("property access of struct({})", self.object.pointer)
+        format!("property access of struct({})", self.object.to_string())
+    }
+}
+
+// visiting: handles of the vectors and maps being printed. a
+// container holding itself is printed as [...] or {...} instead
+// of recursing until the stack overflows
+pub fn value_to_string(value: &Value, vm: &Vm, visiting: &mut Vec<Handle>) -> String {
+    let Value::Handle(handle) = value else {
+        return value.to_string(vm);
+    };
+    let Ok(object) = vm.memory.resolve(handle) else {
+        return "freed_object".to_string();
+    };
+    if visiting.contains(handle) {
+        return match object {
+            MemObject::Map(_) => "{...}".to_string(),
+            _ => "[...]".to_string(),
+        };
+    }
+
+    visiting.push(handle.clone());
+    let string = match object {
+        MemObject::Vector(x) => x.to_string_visiting(vm, visiting),
+        MemObject::Map(x) => x.to_string_visiting(vm, visiting),
+        object => object.to_string(vm),
+    };
+    visiting.pop();
+    string
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::object::{map::Map, vector::Vector};
+
+    #[test]
+    fn containers_holding_themselves_are_printed() {
+        let mut vm = Vm::new(vec![]);
+        let vector = vm.memory.alloc(MemObject::Vector(Vector::new(vec![])));
+        if let Ok(MemObject::Vector(x)) = vm.memory.resolve_mut(&vector) {
+            x.elements.push(Value::Handle(vector.clone()));
+        }
+        let map = vm.memory.alloc(MemObject::Map(Map::new(vec![])));
+        if let Ok(MemObject::Map(x)) = vm.memory.resolve_mut(&map) {
+            x.insert("self".to_string(), Value::Handle(map.clone()));
+        }
+
+        assert!(Value::Handle(vector).to_string(&vm).contains("[...]"));
+        assert_eq!(Value::Handle(map).to_string(&vm), "{\"self\": {...}}");
     }
 }
@@ -1,26 +1,48 @@
 use std::collections::HashMap;
 
-use crate::{opcodes::DataType, types::Value};
+use crate::{memory::Handle, opcodes::DataType, types::Value};
 
 #[derive(Debug, Clone)]
 pub struct StructDeclaration {
     pub identifier: String,
     pub fields: Vec<(String, DataType)>,
+    // functions declared on impl blocks
+    pub methods: HashMap<String, Handle>,
 }
 
 impl StructDeclaration {
     pub fn new(identifier: String, fields: Vec<(String, DataType)>) -> StructDeclaration {
-        StructDeclaration { identifier, fields }
+        StructDeclaration {
+            identifier,
+            fields,
+            methods: HashMap::new(),
+        }
+    }
+    pub fn add_method(&mut self, identifier: String, method: Handle) {
+        self.methods.insert(identifier, method);
+    }
+    pub fn method(&self, identifier: &str) -> Option<Handle> {
+        self.methods.get(identifier).cloned()
     }
     pub fn to_string(&self) -> String {
         self.identifier.clone()
     }
+    pub fn field_type(&self, field: &str) -> Option<&DataType> {
+        self.fields
+            .iter()
+            .find(|(name, _)| name == field)
+            .map(|(_, annotation)| annotation)
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct StructLiteral {
     pub struct_type: String,
     pub fields: HashMap<String, Value>,
+    // handle of the struct declaration, used to type check
+    // field updates and to resolve methods. it's none for
+    // structs created by the vm (like modules)
+    pub declaration: Option<Handle>,
 }
 
 impl StructLiteral {
@@ -28,6 +50,19 @@ impl StructLiteral {
         StructLiteral {
             struct_type,
             fields,
+            declaration: None,
+        }
+    }
+
+    pub fn new_with_declaration(
+        struct_type: String,
+        declaration: Handle,
+        fields: HashMap<String, Value>,
+    ) -> StructLiteral {
+        StructLiteral {
+            struct_type,
+            fields,
+            declaration: Some(declaration),
         }
     }
 
@@ -35,6 +70,16 @@ impl StructLiteral {
         self.fields.get(property).cloned()
     }
 
+    // returns false if the field does not exist
+    pub fn set_property(&mut self, property: &str, value: Value) -> bool {
+        if let Some(field) = self.fields.get_mut(property) {
+            *field = value;
+            true
+        } else {
+            false
+        }
+    }
+
     pub fn to_string(&self) -> String {
         format!("[instance] {}", self.struct_type)
     }
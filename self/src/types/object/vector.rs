@@ -1,6 +1,8 @@
 use std::collections::HashMap;
 
-use crate::{types::Value, vm::Vm};
+use crate::{memory::Handle, types::Value, vm::Vm};
+
+use super::value_to_string;
 
 #[derive(Debug, Clone)]
 pub struct Vector {
@@ -21,7 +23,15 @@ impl Vector {
     }
 
     pub fn to_string(&self, vm: &Vm) -> String {
-        let elements: Vec<String> = self.elements.iter().map(|ele| ele.to_string(vm)).collect();
+        self.to_string_visiting(vm, &mut vec![])
+    }
+
+    pub fn to_string_visiting(&self, vm: &Vm, visiting: &mut Vec<Handle>) -> String {
+        let elements: Vec<String> = self
+            .elements
+            .iter()
+            .map(|ele| value_to_string(ele, vm, visiting))
+            .collect();
         format!("{:#?}", elements)
     }
 
@@ -0,0 +1,318 @@
+/*
+    CHECKS A BYTECODE BEFORE A SINGLE INSTRUCTION
+    OF IT IS EXECUTED. EVERY PROBLEM FOUND IS
+    REPORTED, NOT ONLY THE FIRST ONE
+*/
+
+use std::collections::HashMap;
+
+use crate::{
+    core::error::bytecode_errors::BytecodeError, instructions::Instruction, opcodes::DataType,
+    translator::Translator,
+};
+
+pub fn verify(bytecode: &[u8]) -> Result<(), Vec<BytecodeError>> {
+    let mut errors = vec![];
+    verify_module(bytecode, 0, bytecode.len(), &mut errors);
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+// a decoded instruction: (position, instruction, length in bytes)
+type Decoded = (usize, Instruction, usize);
+
+// modules start with the constants section their
+// instructions and functions reference
+fn verify_module(bytecode: &[u8], start: usize, end: usize, errors: &mut Vec<BytecodeError>) {
+    match Translator::read_constants(&bytecode[..end], start) {
+        Ok((constants, code_start)) => verify_stream(bytecode, &constants, code_start, end, errors),
+        Err(err) => errors.push(err),
+    }
+}
+
+// function and module bodies run as a bytecode of their own, so
+// each of them is checked as a separate stream. positions are
+// kept relative to the whole bytecode to report them
+fn verify_stream(
+    bytecode: &[u8],
+    constants: &[String],
+    start: usize,
+    end: usize,
+    errors: &mut Vec<BytecodeError>,
+) {
+    let code = &bytecode[..end];
+
+    let mut instructions: Vec<Decoded> = vec![];
+    let mut pc = start;
+    let mut decoded = true;
+    while pc < end {
+        match Translator::get_instruction(pc, code, constants) {
+            Ok((instruction, length)) => {
+                instructions.push((pc, instruction, length));
+                pc += length;
+            }
+            Err(err) => {
+                // the length of a broken instruction is unknown,
+                // so the rest of the stream cannot be decoded
+                errors.push(err);
+                decoded = false;
+                break;
+            }
+        }
+    }
+
+    for (pc, instruction, length) in &instructions {
+        let body_end = pc + length;
+        match instruction {
+            Instruction::FuncDec { body_length, .. }
+            | Instruction::MethodDec { body_length, .. } => {
+                let body_start = body_end - *body_length as usize;
+                verify_stream(bytecode, constants, body_start, body_end, errors);
+            }
+            // native modules have an empty body
+            Instruction::Import { body_length } if *body_length > 0 => {
+                verify_module(bytecode, body_end - *body_length as usize, body_end, errors);
+            }
+            _ => continue,
+        };
+    }
+
+    let starts: HashMap<usize, usize> = instructions
+        .iter()
+        .enumerate()
+        .map(|(index, (pc, _, _))| (*pc, index))
+        .collect();
+
+    // jumping to the stream end finishes the execution
+    let mut jumps_valid = true;
+    for (at, instruction, _) in &instructions {
+        let Some(target) = jump_target(*at, instruction) else {
+            continue;
+        };
+        // past a decoding error nothing is known about the stream
+        if !decoded && target >= pc as isize {
+            continue;
+        }
+        let on_boundary = target >= 0
+            && (starts.contains_key(&(target as usize)) || (decoded && target as usize == end));
+        if !on_boundary {
+            errors.push(BytecodeError::InvalidJumpTarget {
+                instruction: instruction.get_type(),
+                at: *at,
+                target,
+            });
+            jumps_valid = false;
+        }
+    }
+
+    if decoded && jumps_valid {
+        check_stack_depth(&instructions, &starts, errors);
+    }
+}
+
+// mirrors how the vm moves the pc on each jump
+fn jump_target(pc: usize, instruction: &Instruction) -> Option<isize> {
+    match instruction {
+        Instruction::Jump { offset } => Some(pc as isize + 4 + *offset as isize),
+        Instruction::JumpIfFalse { offset } => Some(pc as isize + 5 + *offset as isize),
+        Instruction::TryStart { offset } => Some(pc as isize + 5 + *offset as isize),
+        _ => None,
+    }
+}
+
+// walks every path of the stream keeping the lowest operands stack
+// depth each instruction can be reached with. expression statements
+// leave their value on the stack, so depths are not required to be
+// equal when paths join, only to be enough for what is popped
+fn check_stack_depth(
+    instructions: &[Decoded],
+    starts: &HashMap<usize, usize>,
+    errors: &mut Vec<BytecodeError>,
+) {
+    let end = instructions.len();
+    let index_of = |target: isize| match starts.get(&(target as usize)) {
+        Some(index) => *index,
+        None => end,
+    };
+
+    let mut depths: Vec<Option<usize>> = vec![None; end];
+    let mut pending = vec![(0, 0)];
+    while let Some((index, depth)) = pending.pop() {
+        if index >= end {
+            continue;
+        }
+        match depths[index] {
+            Some(known) if known <= depth => continue,
+            _ => depths[index] = Some(depth),
+        }
+
+        let (pc, instruction, _) = &instructions[index];
+        let (pops, pushes) = stack_effect(instruction);
+        let next_depth = depth.saturating_sub(pops) + pushes;
+        match instruction {
+            Instruction::Return | Instruction::Throw => {}
+            Instruction::Jump { .. } => {
+                if let Some(target) = jump_target(*pc, instruction) {
+                    pending.push((index_of(target), next_depth));
+                }
+            }
+            // on error the stack is restored to the try start
+            // depth and the error is pushed for the catch block
+            Instruction::TryStart { .. } => {
+                if let Some(target) = jump_target(*pc, instruction) {
+                    pending.push((index_of(target), depth + 1));
+                }
+                pending.push((index + 1, next_depth));
+            }
+            Instruction::JumpIfFalse { .. } => {
+                if let Some(target) = jump_target(*pc, instruction) {
+                    pending.push((index_of(target), next_depth));
+                }
+                pending.push((index + 1, next_depth));
+            }
+            _ => pending.push((index + 1, next_depth)),
+        }
+    }
+
+    for (index, depth) in depths.iter().enumerate() {
+        let (pc, instruction, _) = &instructions[index];
+        let needed = stack_effect(instruction).0;
+        if let Some(depth) = depth {
+            if *depth < needed {
+                errors.push(BytecodeError::StackUnderflow {
+                    instruction: instruction.get_type(),
+                    at: *pc,
+                    needed,
+                    available: *depth,
+                });
+            }
+        }
+    }
+}
+
+// (popped, pushed) operands stack values. calls are counted as
+// pushing their result even if the function returns nothing
+fn stack_effect(instruction: &Instruction) -> (usize, usize) {
+    match instruction {
+        Instruction::LoadConst { data_type, value } => {
+            let count = match value.as_slice() {
+                [a, b, c, d] => u32::from_le_bytes([*a, *b, *c, *d]) as usize,
+                _ => 0,
+            };
+            match data_type {
+                DataType::Vector => (count, 1),
+                // key and value of each entry
+                DataType::Map => (count * 2, 1),
+                // struct type, and name and value of each field
+                DataType::StructLiteral => (1 + count * 2, 1),
+                _ => (0, 1),
+            }
+        }
+        Instruction::LoadVar { .. }
+        | Instruction::LoadLocal { .. }
+        | Instruction::LoadGlobal { .. } => (0, 1),
+        Instruction::StoreVar { .. } | Instruction::StoreLocal { .. } => (1, 0),
+        Instruction::JumpIfFalse { .. } => (1, 0),
+        Instruction::Add
+        | Instruction::Substract
+        | Instruction::Multiply
+        | Instruction::Divide
+        | Instruction::GreaterThan
+        | Instruction::LessThan
+        | Instruction::GreaterThanOrEqual
+        | Instruction::LessThanOrEqual
+        | Instruction::Equals
+        | Instruction::NotEquals => (2, 1),
+        Instruction::Not => (1, 1),
+        // parameters names
+        Instruction::FuncDec { parameters_num, .. } => (*parameters_num as usize, 0),
+        // methods are left on the stack for the impl declaration
+        Instruction::MethodDec { parameters_num, .. } => (*parameters_num as usize, 1),
+        Instruction::ImplDec { methods_num, .. } => (*methods_num as usize, 0),
+        Instruction::GetProperty | Instruction::GetIndex => (2, 1),
+        Instruction::SetProperty | Instruction::SetIndex => (3, 0),
+        Instruction::Print { number_of_args }
+        | Instruction::Println { number_of_args }
+        | Instruction::FFI_Call { number_of_args } => (*number_of_args as usize, 0),
+        // arguments and the callee
+        Instruction::Call { number_of_args } => (*number_of_args as usize + 1, 1),
+        // module name
+        Instruction::Import { .. } => (1, 0),
+        Instruction::Export | Instruction::Return | Instruction::Throw => (1, 0),
+        Instruction::Jump { .. }
+        | Instruction::TryStart { .. }
+        | Instruction::TryEnd
+        | Instruction::StructDec { .. } => (0, 0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // empty constants section
+    const NO_CONSTANTS: [u8; 4] = [0, 0, 0, 0];
+
+    fn load_i32(value: i32) -> Vec<u8> {
+        let mut bytes = vec![0x01, 0x01];
+        bytes.extend(value.to_le_bytes());
+        bytes
+    }
+
+    fn program(instructions: &[Vec<u8>]) -> Vec<u8> {
+        let mut bytecode = NO_CONSTANTS.to_vec();
+        for instruction in instructions {
+            bytecode.extend(instruction);
+        }
+        bytecode
+    }
+
+    #[test]
+    fn popping_more_than_the_stack_holds_is_rejected() {
+        let bytecode = program(&[load_i32(1), vec![0x03]]);
+        let errors = verify(&bytecode).expect_err("add pops two values");
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(
+            errors[0],
+            BytecodeError::StackUnderflow {
+                at: 10,
+                needed: 2,
+                available: 1,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn enough_values_on_the_stack_pass() {
+        let bytecode = program(&[load_i32(1), load_i32(2), vec![0x03]]);
+        assert!(verify(&bytecode).is_ok());
+    }
+
+    #[test]
+    fn every_path_needs_enough_values() {
+        // the jump skips the first load, so add finds a single value
+        // when the condition is false
+        let mut jump_if_false = vec![0x0c];
+        jump_if_false.extend(6i32.to_le_bytes());
+        let bytecode = program(&[
+            vec![0x01, 0x06, 0x01],
+            jump_if_false,
+            load_i32(1),
+            load_i32(2),
+            vec![0x03],
+        ]);
+        let errors = verify(&bytecode).expect_err("one path underflows");
+        assert!(matches!(
+            errors[..],
+            [BytecodeError::StackUnderflow {
+                at: 24,
+                available: 1,
+                ..
+            }]
+        ));
+    }
+}
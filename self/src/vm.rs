@@ -1,21 +1,33 @@
+use crate::core::error::bytecode_errors::BytecodeError;
+use crate::core::error::index_errors::IndexError;
 use crate::core::error::struct_errors::StructError;
+use crate::core::error::type_errors::TypeError;
+use crate::core::error::IntegerOverflow;
 use crate::core::error::InvalidBinaryOperation;
+use crate::core::error::ThrownError;
+use crate::core::error::VMError;
 use crate::core::error::VMErrorType;
 use crate::core::execution::VMExecutionResult;
 use crate::core::handlers::call_handler::call_handler;
 use crate::core::handlers::foreign_handlers::ForeignHandlers;
 use crate::core::handlers::print_handler::print_handler;
+use crate::core::line_table::{LineTable, SourceLocation};
+use crate::core::traceback::{TraceFrame, Traceback};
 use crate::heap::Heap;
+use crate::instructions::{Code, Instruction};
+use crate::memory::GcStats;
 use crate::memory::Handle;
 use crate::memory::MemObject;
 use crate::memory::MemoryManager;
 use crate::opcodes::DataType;
-use crate::opcodes::Opcode;
 use crate::std::bootstrap_default_lib;
+use crate::std::map;
 use crate::std::vector;
 use crate::std::{generate_native_module, get_native_module_type};
+use crate::translator::Translator;
 use crate::types::object::func::Engine;
 use crate::types::object::func::Function;
+use crate::types::object::map::Map;
 use crate::types::object::structs::StructDeclaration;
 use crate::types::object::structs::StructLiteral;
 use crate::types::object::vector::Vector;
@@ -23,8 +35,10 @@ use crate::types::object::BoundAccess;
 use crate::types::raw::RawValue;
 use crate::types::raw::{bool::Bool, f64::F64, i32::I32, i64::I64, u32::U32, u64::U64};
 use crate::utils::foreign_handlers_utils::get_foreign_handlers;
+use crate::verifier;
 use std::collections::HashMap;
 use std::path::Path;
+use std::rc::Rc;
 
 use super::stack::*;
 use super::types::*;
@@ -35,16 +49,48 @@ pub struct Vm {
     pub heap: Heap,
     pub memory: MemoryManager,
     bytecode: Vec<u8>,
+    // decoded instructions being executed (the program, a
+    // function or a module body) and the index of the next one
+    code: Rc<Code>,
     pc: usize,
     handlers: HashMap<String, Handle>,
+    // string constants are allocated once and shared by
+    // every load of the same constant
+    interned: HashMap<String, Handle>,
     ffi_handlers: ForeignHandlers,
+    line_table: LineTable,
+    // name of the module being executed
+    module: String,
+    // arguments of the program, read with os.args()
+    args: Vec<String>,
+    // programs are verified before running unless disabled
+    // for trusted bytecode
+    verify_bytecode: bool,
+    // traces every instruction, it is an option of the vm and
+    // not an argument, a program can receive -d itself
+    debug: bool,
+    // native functions being executed. they can hold values
+    // the collector cannot see (like vector.map results), so
+    // no collection happens until they return
+    native_depth: usize,
+}
+
+// a try block being executed. on error, the operands stack
+// is truncated to stack_length and the pc set to catch_pc,
+// the index of the first instruction of the catch block
+struct TryHandler {
+    catch_pc: usize,
+    stack_length: usize,
+}
+
+// what a v[i] or m["key"] access points to
+enum IndexTarget {
+    Element(usize),
+    Entry(String),
 }
 
 impl Vm {
     pub fn new(bytecode: Vec<u8>) -> Vm {
-        //let mut translator = Translator::new(bytecode);
-        //let instructions = translator.translate();
-
         // load ffi_handlers
         let mut ffi_handlers = ForeignHandlers::new();
         let foreign_handlers = get_foreign_handlers();
@@ -61,14 +107,96 @@ impl Vm {
             heap: Heap::new(),
             memory: MemoryManager::new(),
             bytecode,
+            code: Rc::new(Code::default()),
             pc: 0,
             handlers: HashMap::new(),
+            interned: HashMap::new(),
             ffi_handlers,
+            line_table: LineTable::new(),
+            module: "main".to_string(),
+            args: vec![],
+            verify_bytecode: true,
+            debug: false,
+            native_depth: 0,
+        }
+    }
+
+    pub fn new_with_line_table(bytecode: Vec<u8>, line_table: LineTable) -> Vm {
+        let mut vm = Vm::new(bytecode);
+        vm.line_table = line_table;
+        vm
+    }
+
+    // source position of the instruction being executed
+    pub fn current_location(&self) -> Option<SourceLocation> {
+        self.line_table.lookup(self.position())
+    }
+
+    // position on the program bytecode of the instruction
+    // being executed
+    fn position(&self) -> usize {
+        self.code.position(self.pc)
+    }
+
+    // each frame is located at the call of the next one, and
+    // the last one at the instruction being executed
+    pub fn traceback(&self) -> Traceback {
+        let frames = self.call_stack.frames();
+        let mut traceback = Traceback::default();
+        for (index, frame) in frames.iter().enumerate() {
+            let location = match frames.get(index + 1) {
+                Some(callee) => self.line_table.lookup(callee.call_pc),
+                None => self.current_location(),
+            };
+            traceback.frames.push(TraceFrame {
+                function: frame.function.clone(),
+                module: frame.module.clone(),
+                location,
+            });
         }
+        traceback
+    }
+
+    // checks the whole bytecode without executing it
+    pub fn verify(&self) -> Result<(), Vec<BytecodeError>> {
+        verifier::verify(&self.bytecode)
+    }
+
+    pub fn set_verification(&mut self, enabled: bool) {
+        self.verify_bytecode = enabled;
+    }
+
+    pub fn set_debug(&mut self, enabled: bool) {
+        self.debug = enabled;
+    }
+
+    pub fn args(&self) -> &[String] {
+        &self.args
     }
 
     pub fn run(&mut self, args: &Vec<String>) -> VMExecutionResult {
-        let debug = args.contains(&"-d".to_string());
+        // nothing is executed from a bytecode that does not verify
+        if self.verify_bytecode {
+            if let Err(problems) = self.verify() {
+                return VMExecutionResult::terminate_with_errors(
+                    VMErrorType::Verification(problems),
+                    self,
+                );
+            }
+        }
+        self.args = args.clone();
+        // decoded once, loops and calls run the same instructions
+        self.code = match Translator::translate(&self.bytecode) {
+            Ok(code) => Rc::new(code),
+            Err(err) => {
+                return VMExecutionResult::terminate_with_errors(
+                    VMErrorType::Verification(vec![err]),
+                    self,
+                )
+            }
+        };
+
+        let debug = self.debug;
         if debug {
             println!("last PC value: {}", self.bytecode.len());
             println!("-");
@@ -86,20 +214,61 @@ impl Vm {
         self.run_bytecode(debug)
     }
 
+    // runs the loaded bytecode. when an error happens inside a
+    // try block, the operands stack is restored to its state at
+    // the try start and the execution continues on the catch
+    // block. frames of the functions called inside the try block
+    // are already popped by run_function when the error reaches
+    // this point
     fn run_bytecode(&mut self, debug: bool) -> VMExecutionResult {
-        while self.pc < self.bytecode.len() {
-            match Opcode::to_opcode(self.bytecode[self.pc]) {
-                Opcode::LoadConst => {
-                    // parsing
-                    if self.pc + 1 >= self.bytecode.len() {
-                        panic!("Invalid LOAD_CONST instruction at position {}", self.pc);
-                    }
+        let mut handlers: Vec<TryHandler> = vec![];
+        loop {
+            let exec_result = self.run_instructions(debug, &mut handlers);
+            let handler = match (&exec_result.error, handlers.pop()) {
+                (Some(_), Some(handler)) => handler,
+                _ => return exec_result,
+            };
+            let error = exec_result.error.unwrap();
 
-                    self.pc += 1;
-                    let (data_type, value_bytes) = self.get_value_length();
+            if debug {
+                println!("CATCH <- {}: {}", error.message, error.semantic_message);
+            }
 
-                    // execution
-                    let (value, printable_value) = self.bytes_to_data(&data_type, &value_bytes);
+            self.operand_stack.truncate(handler.stack_length);
+            let error_value = self.error_to_value(error);
+            self.push_to_stack(error_value, None);
+            self.pc = handler.catch_pc;
+        }
+    }
+
+    fn run_instructions(
+        &mut self,
+        debug: bool,
+        handlers: &mut Vec<TryHandler>,
+    ) -> VMExecutionResult {
+        while self.pc < self.code.instructions.len() {
+            // between instructions every live value is on the
+            // operands stack or on a frame
+            if self.native_depth == 0 && self.memory.should_collect() {
+                let freed = self.collect_garbage();
+                if debug {
+                    println!(
+                        "GC <- freed {} objects ({} alive)",
+                        freed,
+                        self.memory.stats().live
+                    );
+                }
+            }
+
+            // the code is kept alive by this reference while
+            // calls replace the one being executed
+            let code = self.code.clone();
+            match &code.instructions[self.pc] {
+                Instruction::LoadConst { data_type, value } => {
+                    let (value, printable_value) = match self.bytes_to_data(data_type, value) {
+                        Ok(v) => v,
+                        Err(err) => return VMExecutionResult::terminate_with_errors(err, self),
+                    };
 
                     self.push_to_stack(value, None);
                     if debug {
@@ -108,22 +277,10 @@ impl Vm {
 
                     self.pc += 1;
                 }
-                Opcode::LoadVar => {
-                    // parsing
-                    if self.pc + 1 >= self.bytecode.len() {
-                        panic!("Invalid LOAD_VAR instruction at position {}", self.pc);
-                    }
-
-                    self.pc += 1;
-                    // identifier
-                    let (identifier_data_type, identifier_bytes) = self.get_value_length();
-                    if identifier_data_type != DataType::Utf8 {
-                        panic!("Identifier type should be a string encoded as utf8")
-                    }
-                    let identifier_name = String::from_utf8(identifier_bytes)
-                        .expect("Identifier bytes should be valid UTF-8");
-
-                    let identifier_value = self.call_stack.resolve(&identifier_name);
+                Instruction::LoadVar {
+                    identifier: identifier_name,
+                } => {
+                    let identifier_value = self.call_stack.resolve(identifier_name);
                     if let Some(v) = identifier_value {
                         self.push_to_stack(v, Some(identifier_name.clone()));
                         if debug {
@@ -131,105 +288,169 @@ impl Vm {
                         }
                     } else {
                         return VMExecutionResult::terminate_with_errors(
-                            VMErrorType::UndeclaredIdentifierError(identifier_name),
+                            VMErrorType::UndeclaredIdentifierError(identifier_name.clone()),
                             self,
                         );
                     }
 
                     self.pc += 1;
                 }
-                Opcode::StoreVar => {
-                    // parsing
-                    if self.pc + 1 >= self.bytecode.len() {
-                        panic!("Invalid STORE_VAR instruction at position {}.", self.pc);
+                Instruction::LoadLocal {
+                    depth,
+                    slot,
+                    identifier,
+                } => {
+                    if let Some(v) = self.call_stack.resolve_local(*depth, *slot) {
+                        self.push_to_stack(v, Some(identifier.clone()));
+                        if debug {
+                            println!("LOAD_LOCAL[{depth}:{slot}] <- {identifier}");
+                        }
                     } else {
-                        self.pc += 1;
+                        // declared on the function but not assigned yet
+                        return VMExecutionResult::terminate_with_errors(
+                            VMErrorType::UndeclaredIdentifierError(identifier.clone()),
+                            self,
+                        );
                     }
 
-                    // 0x00 inmutable | 0x00 mutable
-                    let mutable = match self.bytecode[self.pc] {
-                        0x00 => false,
-                        0x01 => true,
-                        _ => {
-                            panic!("Invalid STORE_VAR instruction at position {}. Needed mutability property.", self.pc);
-                        }
-                    };
                     self.pc += 1;
+                }
+                Instruction::LoadGlobal { identifier } => {
+                    if let Some(v) = self.call_stack.resolve_global(identifier) {
+                        self.push_to_stack(v, Some(identifier.clone()));
+                        if debug {
+                            println!("LOAD_GLOBAL <- {identifier}");
+                        }
+                    } else {
+                        return VMExecutionResult::terminate_with_errors(
+                            VMErrorType::UndeclaredIdentifierError(identifier.clone()),
+                            self,
+                        );
+                    }
 
-                    // identifier
-                    let (identifier_data_type, identifier_bytes) = self.get_value_length();
-                    if identifier_data_type != DataType::Utf8 {
-                        panic!("Identifier type should be a string encoded as utf8")
+                    self.pc += 1;
+                }
+                Instruction::StoreLocal {
+                    depth,
+                    slot,
+                    identifier,
+                } => {
+                    let Some(v) = self.operand_stack.pop() else {
+                        return VMExecutionResult::terminate_with_errors(
+                            VMErrorType::StackUnderflow {
+                                needed: 1,
+                                available: 0,
+                            },
+                            self,
+                        );
+                    };
+                    if debug {
+                        println!(
+                            "STORE_LOCAL[{depth}:{slot}] <- {:?}({}) as {identifier}",
+                            v.value.get_type(),
+                            v.value.to_string(self),
+                        );
                     }
-                    let identifier_name = String::from_utf8(identifier_bytes)
-                        .expect("Identifier bytes should be valid UTF-8");
+                    self.call_stack.assign_local(*depth, *slot, v.value);
 
-                    // execution
+                    self.pc += 1;
+                }
+                Instruction::StoreVar {
+                    identifier: identifier_name,
+                    mutable,
+                    reassign,
+                } => {
                     let stack_stored_value = self.operand_stack.pop();
                     if let Some(v) = stack_stored_value {
-                        let datatype = v.value.get_type();
-                        let printable_value = v.value.to_string(self);
-                        self.call_stack
-                            .put_to_frame(identifier_name.clone(), v.value);
                         if debug {
                             println!(
                                 "STORE_VAR[{}] <- {:?}({}) as {}",
-                                if mutable { "MUT" } else { "INMUT" },
-                                datatype,
-                                printable_value,
+                                if *mutable { "MUT" } else { "INMUT" },
+                                v.value.get_type(),
+                                v.value.to_string(self),
                                 identifier_name,
                             );
                         }
+                        if *reassign {
+                            self.call_stack.assign(identifier_name.clone(), v.value);
+                        } else {
+                            self.call_stack
+                                .put_to_frame(identifier_name.clone(), v.value);
+                        }
                     } else {
-                        // todo: use self-vm errors
-                        panic!("STACK UNDERFLOW")
+                        return VMExecutionResult::terminate_with_errors(
+                            VMErrorType::StackUnderflow {
+                                needed: 1,
+                                available: 0,
+                            },
+                            self,
+                        );
                     }
 
                     self.pc += 1;
                 }
-                Opcode::JumpIfFalse => {
-                    let offset = Vm::read_offset(&self.bytecode[self.pc + 1..self.pc + 5]);
-                    self.pc += 4;
-
-                    let condition = self.operand_stack.pop();
-                    if condition.is_none() {
-                        panic!("stack underflow");
+                Instruction::JumpIfFalse { .. } => {
+                    let target = code.targets[self.pc].unwrap_or(self.pc + 1);
+                    let condition = match self.get_stack_values(&1) {
+                        Ok(v) => v[0].clone(),
+                        Err(err) => return VMExecutionResult::terminate_with_errors(err, self),
                     };
-
-                    let condition = condition.unwrap();
-                    match condition.value {
-                        Value::RawValue(v) => match v {
-                            RawValue::Bool(execute_if) => {
-                                if debug {
-                                    println!("JUMP_IF_FALSE <- {:?}({})", execute_if.value, offset);
-                                }
-                                if !execute_if.value {
-                                    self.pc += offset as usize;
-                                }
-                            }
-                            _ => panic!("invalid expression type as condition to jump"),
-                        },
-                        _ => {
-                            panic!("invalid expression type as condition to jump")
+                    let execute_if = match condition.as_bool(self) {
+                        Ok(v) => v,
+                        Err(err) => {
+                            return VMExecutionResult::terminate_with_errors(err.error_type, self)
                         }
                     };
-
-                    self.pc += 1;
+                    if debug {
+                        println!("JUMP_IF_FALSE <- {:?}({})", execute_if, target);
+                    }
+                    if execute_if {
+                        self.pc += 1;
+                    } else {
+                        self.pc = target;
+                    }
                 }
-                Opcode::Jump => {
-                    // execution
-                    let offset = Vm::read_offset(&self.bytecode[self.pc + 1..self.pc + 5]);
-                    self.pc += 4;
-
-                    let target_pc = (self.pc as isize) + offset as isize;
+                Instruction::Jump { .. } => {
+                    let target_pc = code.targets[self.pc].unwrap_or(self.pc + 1);
                     if debug {
                         println!("JUMP <- {:?}", target_pc);
                     }
-                    self.pc = target_pc as usize;
+                    self.pc = target_pc;
+                }
+                Instruction::TryStart { .. } => {
+                    let catch_pc = code.targets[self.pc].unwrap_or(self.pc + 1);
+                    if debug {
+                        println!("TRY_START <- catch at {}", catch_pc);
+                    }
+                    handlers.push(TryHandler {
+                        catch_pc,
+                        stack_length: self.operand_stack.len(),
+                    });
+                    self.pc += 1;
+                }
+                Instruction::TryEnd => {
+                    if debug {
+                        println!("TRY_END");
+                    }
+                    handlers.pop();
+                    self.pc += 1;
                 }
-                Opcode::Print => {
-                    self.pc += 1; // consume print opcode
-                    let args = self.get_function_call_args();
+                Instruction::Throw => {
+                    let value = match self.get_stack_values(&1) {
+                        Ok(v) => v[0].clone(),
+                        Err(err) => return VMExecutionResult::terminate_with_errors(err, self),
+                    };
+                    if debug {
+                        println!("THROW <- {}", value.to_string(self));
+                    }
+                    let error = self.value_to_error(&value);
+                    return VMExecutionResult::terminate_with_errors(error, self);
+                }
+                Instruction::Print { number_of_args } => {
+                    let args = match self.get_stack_values(number_of_args) {
+                        Ok(v) => v,
+                        Err(err) => return VMExecutionResult::terminate_with_errors(err, self),
+                    };
                     let mut resolved_args = Vec::new();
                     for val in args {
                         match self.value_to_string(val) {
@@ -238,10 +459,13 @@ impl Vm {
                         }
                     }
                     print_handler(resolved_args, debug, false);
+                    self.pc += 1;
                 }
-                Opcode::Println => {
-                    self.pc += 1; // consume print opcode
-                    let args = self.get_function_call_args();
+                Instruction::Println { number_of_args } => {
+                    let args = match self.get_stack_values(number_of_args) {
+                        Ok(v) => v,
+                        Err(err) => return VMExecutionResult::terminate_with_errors(err, self),
+                    };
                     let mut resolved_args = Vec::new();
                     for val in args {
                         match self.value_to_string(val) {
@@ -250,141 +474,121 @@ impl Vm {
                         }
                     }
                     print_handler(resolved_args, debug, true);
+                    self.pc += 1;
                 }
-                Opcode::FuncDec => {
-                    // skip FuncDec opcode
-                    if self.pc + 1 >= self.bytecode.len() {
-                        panic!(
-                            "Invalid FUNCTION_DECLARATION instruction at position {}.",
-                            self.pc
-                        );
-                    } else {
-                        self.pc += 1;
-                    }
-
-                    // identifier
-                    let (identifier_data_type, identifier_bytes) = self.get_value_length();
-                    if identifier_data_type != DataType::Utf8 {
-                        panic!("Identifier type should be a string encoded as utf8")
-                    }
-                    let identifier_name = String::from_utf8(identifier_bytes)
-                        .expect("Identifier bytes should be valid UTF-8");
-
-                    // parameters
-                    if self.pc + 4 >= self.bytecode.len() {
-                        panic!("Invalid FUNC_DEC instruction at position {}", self.pc);
-                    }
-
-                    let value_bytes = &self.bytecode[self.pc + 1..self.pc + 5];
-                    let parameters_length = u32::from_le_bytes(
-                        value_bytes.try_into().expect("Provided value is incorrect"),
-                    ) as usize;
-                    // get params names from the stack
-                    let params_values = self.get_stack_values(&(parameters_length as u32));
-                    let params_names: Vec<String> = params_values
-                        .iter()
-                        .map(|val| {
-                            match val {
-                                Value::Handle(r) => match self.memory.resolve(&r) {
-                                    MemObject::String(s) => s.clone(),
-                                    _ => {
-                                        // TODO: use self-vm errors sytem
-                                        panic!("Invalid param type for a function declaration")
-                                    }
-                                },
-                                _ => {
-                                    // TODO: use self-vm errors sytem
-                                    panic!("Invalid param type for a function declaration")
-                                }
-                            }
-                        })
-                        .collect();
-
-                    self.pc += 4;
-
-                    // handle body
-                    // function body length
-                    if self.pc + 4 >= self.bytecode.len() {
-                        panic!("Invalid FUNC_DEC instruction at position {}", self.pc);
-                    }
-
-                    let value_bytes = &self.bytecode[self.pc + 1..self.pc + 5];
-                    let body_length = u32::from_le_bytes(
-                        value_bytes.try_into().expect("Provided value is incorrect"),
-                    ) as usize;
-                    self.pc += 4;
-                    self.pc += 1; // to get next opcode
-
-                    let body_bytecode = self.bytecode[self.pc..self.pc + body_length].to_vec();
-                    self.pc += body_length;
-
-                    // allocate function on the heap
-                    let func_obj = MemObject::Function(Function::new(
-                        identifier_name.clone(),
-                        params_names,
-                        Engine::Bytecode(body_bytecode),
-                    ));
-                    let func_handle = self.memory.alloc(func_obj);
+                Instruction::FuncDec {
+                    identifier,
+                    parameters_num,
+                    ..
+                } => {
+                    let body = code.bodies[self.pc].clone().unwrap_or_default();
+                    let func = match self.declare_function(identifier, *parameters_num, body) {
+                        Ok(v) => v,
+                        Err(err) => return VMExecutionResult::terminate_with_errors(err, self),
+                    };
+                    let identifier_name = func.identifier.clone();
+                    let func_handle = self.memory.alloc(MemObject::Function(func));
 
                     // make accesible on the current context
                     self.call_stack
                         .put_to_frame(identifier_name, Value::Handle(func_handle));
+                    self.pc += 1;
                 }
-                Opcode::StructDec => {
-                    // skip StructDec opcode
+                Instruction::MethodDec {
+                    identifier,
+                    parameters_num,
+                    ..
+                } => {
+                    // methods are not accesible on the current context,
+                    // they are left on the stack for the impl declaration
+                    let body = code.bodies[self.pc].clone().unwrap_or_default();
+                    let func = match self.declare_function(identifier, *parameters_num, body) {
+                        Ok(v) => v,
+                        Err(err) => return VMExecutionResult::terminate_with_errors(err, self),
+                    };
+                    let identifier_name = func.identifier.clone();
+                    let func_handle = self.memory.alloc(MemObject::Function(func));
+                    self.push_to_stack(Value::Handle(func_handle), Some(identifier_name));
                     self.pc += 1;
+                }
+                Instruction::ImplDec {
+                    identifier: identifier_name,
+                    methods_num,
+                } => {
+                    let methods = match self.get_stack_values(methods_num) {
+                        Ok(v) => v,
+                        Err(err) => return VMExecutionResult::terminate_with_errors(err, self),
+                    };
 
-                    // identifier
-                    let (identifier_data_type, identifier_bytes) = self.get_value_length();
-                    if identifier_data_type != DataType::Utf8 {
-                        // TODO: use self-vm errors
-                        panic!("Identifier type should be a string encoded as utf8")
-                    }
-
-                    // TODO: use self-vm errors
-                    let identifier_name = String::from_utf8(identifier_bytes)
-                        .expect("Identifier bytes should be valid UTF-8");
-
-                    // read fields number
-                    self.pc += 1;
-                    let fields_num = Vm::read_offset(&self.bytecode[self.pc..self.pc + 4]);
-                    self.pc += 4;
-
-                    // struct fields [raw_string][type][raw_string][type]
-                    //               (x)B        1B    (x)B        1B
-                    let mut counter = 0;
-                    let mut fields = vec![];
-                    while counter < fields_num {
-                        // field
-                        let (field_data_type, field_bytes) = self.get_value_length();
-                        if field_data_type != DataType::Utf8 {
-                            // TODO: use self-vm errors
-                            panic!("Identifier type should be a string encoded as utf8")
+                    let declaration_handle = match self.call_stack.resolve(identifier_name) {
+                        Some(Value::Handle(h)) => h,
+                        Some(_) => {
+                            return VMExecutionResult::terminate_with_errors(
+                                VMErrorType::TypeMismatch {
+                                    expected: "struct_declaration".to_string(),
+                                    received: identifier_name.clone(),
+                                },
+                                self,
+                            )
                         }
-                        let field_name = String::from_utf8(field_bytes)
-                            .expect("Field bytes should be valid UTF-8"); // TODO: use self-vm errors
-                        self.pc += 1;
-
-                        // annotation
-                        let annotation = DataType::to_opcode(self.bytecode[self.pc]);
-                        self.pc += 1;
+                        None => {
+                            return VMExecutionResult::terminate_with_errors(
+                                VMErrorType::UndeclaredIdentifierError(identifier_name.clone()),
+                                self,
+                            )
+                        }
+                    };
 
-                        fields.push((field_name, annotation));
-                        counter += 1;
+                    // methods are identified by its function name
+                    let mut named_methods = vec![];
+                    for method in methods {
+                        if let Value::Handle(h) = method {
+                            if let Ok(MemObject::Function(f)) = self.memory.resolve(&h) {
+                                named_methods.push((f.identifier.clone(), h));
+                            }
+                        }
                     }
 
+                    match self.memory.resolve_mut(&declaration_handle) {
+                        Ok(MemObject::StructDeclaration(declaration)) => {
+                            for (identifier, method) in named_methods {
+                                declaration.add_method(identifier, method);
+                            }
+                        }
+                        Err(err) => return VMExecutionResult::terminate_with_errors(err, self),
+                        Ok(obj) => {
+                            let received = obj.get_type();
+                            return VMExecutionResult::terminate_with_errors(
+                                VMErrorType::TypeMismatch {
+                                    expected: "struct_declaration".to_string(),
+                                    received,
+                                },
+                                self,
+                            );
+                        }
+                    }
+                    self.pc += 1;
+                }
+                Instruction::StructDec {
+                    identifier: identifier_name,
+                    fields,
+                } => {
                     // struct declaration
                     let struct_declaration =
-                        StructDeclaration::new(identifier_name.clone(), fields);
+                        StructDeclaration::new(identifier_name.clone(), fields.clone());
                     // push to declaration heap
                     let heap_handle = self
                         .memory
                         .alloc(MemObject::StructDeclaration(struct_declaration));
                     self.call_stack
-                        .put_to_frame(identifier_name, Value::Handle(heap_handle));
+                        .put_to_frame(identifier_name.clone(), Value::Handle(heap_handle));
+                    self.pc += 1;
                 }
-                Opcode::GetProperty => {
-                    let values = self.get_stack_values(&2);
+                Instruction::GetProperty => {
+                    let values = match self.get_stack_values(&2) {
+                        Ok(v) => v,
+                        Err(err) => return VMExecutionResult::terminate_with_errors(err, self),
+                    };
                     let (object_handle, property_handle) = match (&values[0], &values[1]) {
                         // (Value::HeapRef(obj_ref), Value::HeapRef(prop_ref)) => (
                         //     self.resolve_heap_ref(obj_ref.clone()),
@@ -393,14 +597,42 @@ impl Vm {
                         (Value::Handle(obj_handle), Value::Handle(prop_handle)) => {
                             (obj_handle, prop_handle)
                         }
-                        // TODO: use self-vm errors
-                        // here we should handle if a function returns an
-                        // nothing istead of a struct
-                        _ => panic!("Expected two Handle values for <get_property> opcode"),
+                        // nested member expression: a.b.c
+                        (Value::BoundAccess(bound), Value::Handle(prop_handle)) => {
+                            match bound.property.as_ref() {
+                                Value::Handle(obj_handle) => (obj_handle, prop_handle),
+                                object => {
+                                    return VMExecutionResult::terminate_with_errors(
+                                        VMErrorType::TypeMismatch {
+                                            expected: "struct".to_string(),
+                                            received: self.value_type_name(object),
+                                        },
+                                        self,
+                                    )
+                                }
+                            }
+                        }
+                        // a.b where a is not an object, like a
+                        // function that returns nothing
+                        (object, _) => {
+                            return VMExecutionResult::terminate_with_errors(
+                                VMErrorType::TypeMismatch {
+                                    expected: "struct".to_string(),
+                                    received: self.value_type_name(object),
+                                },
+                                self,
+                            )
+                        }
                     };
 
-                    let object = self.memory.resolve(object_handle);
-                    let property = self.memory.resolve(property_handle);
+                    let object = match self.memory.resolve(object_handle) {
+                        Ok(v) => v,
+                        Err(err) => return VMExecutionResult::terminate_with_errors(err, self),
+                    };
+                    let property = match self.memory.resolve(property_handle) {
+                        Ok(v) => v,
+                        Err(err) => return VMExecutionResult::terminate_with_errors(err, self),
+                    };
 
                     if debug {
                         println!(
@@ -413,14 +645,40 @@ impl Vm {
                     if let MemObject::String(property_key) = property {
                         match object {
                             MemObject::StructLiteral(x) => {
-                                let value = x.property_access(&property_key);
+                                // fields first, then methods of the struct
+                                let value = x.property_access(&property_key).or_else(|| {
+                                    match x.declaration.as_ref().map(|d| self.memory.resolve(d)) {
+                                        Some(Ok(MemObject::StructDeclaration(d))) => {
+                                            d.method(property_key).map(Value::Handle)
+                                        }
+                                        _ => None,
+                                    }
+                                });
                                 if let Some(prop) = value {
                                     let bound_access =
                                         BoundAccess::new(object_handle.clone(), Box::new(prop));
-                                    self.push_to_stack(
-                                        Value::BoundAccess(bound_access),
-                                        Some(object.to_string(self)),
+                                    // bound accesses are called through their
+                                    // handles, the origin is never read
+                                    self.push_to_stack(Value::BoundAccess(bound_access), None);
+                                } else {
+                                    return VMExecutionResult::terminate_with_errors(
+                                        VMErrorType::Struct(StructError::FieldNotFound {
+                                            field: property_key.to_string(),
+                                            struct_type: object.to_string(self),
+                                        }),
+                                        self,
                                     );
+                                }
+                            }
+                            // static access: Person.new()
+                            MemObject::StructDeclaration(x) => {
+                                let value = x.method(property_key).map(Value::Handle);
+                                if let Some(prop) = value {
+                                    let bound_access =
+                                        BoundAccess::new(object_handle.clone(), Box::new(prop));
+                                    // bound accesses are called through their
+                                    // handles, the origin is never read
+                                    self.push_to_stack(Value::BoundAccess(bound_access), None);
                                 } else {
                                     return VMExecutionResult::terminate_with_errors(
                                         VMErrorType::Struct(StructError::FieldNotFound {
@@ -436,10 +694,9 @@ impl Vm {
                                 if let Some(prop) = value {
                                     let bound_access =
                                         BoundAccess::new(object_handle.clone(), Box::new(prop));
-                                    self.push_to_stack(
-                                        Value::BoundAccess(bound_access),
-                                        Some(object.to_string(self)),
-                                    );
+                                    // bound accesses are called through their
+                                    // handles, the origin is never read
+                                    self.push_to_stack(Value::BoundAccess(bound_access), None);
                                 } else {
                                     return VMExecutionResult::terminate_with_errors(
                                         VMErrorType::Struct(StructError::FieldNotFound {
@@ -455,10 +712,27 @@ impl Vm {
                                 if let Some(prop) = value {
                                     let bound_access =
                                         BoundAccess::new(object_handle.clone(), Box::new(prop));
-                                    self.push_to_stack(
-                                        Value::BoundAccess(bound_access),
-                                        Some(object.to_string(self)),
+                                    // bound accesses are called through their
+                                    // handles, the origin is never read
+                                    self.push_to_stack(Value::BoundAccess(bound_access), None);
+                                } else {
+                                    return VMExecutionResult::terminate_with_errors(
+                                        VMErrorType::Struct(StructError::FieldNotFound {
+                                            field: property_key.to_string(),
+                                            struct_type: object.to_string(self),
+                                        }),
+                                        self,
                                     );
+                                }
+                            }
+                            MemObject::Map(x) => {
+                                let value = x.property_access(property_key);
+                                if let Some(prop) = value {
+                                    let bound_access =
+                                        BoundAccess::new(object_handle.clone(), Box::new(prop));
+                                    // bound accesses are called through their
+                                    // handles, the origin is never read
+                                    self.push_to_stack(Value::BoundAccess(bound_access), None);
                                 } else {
                                     return VMExecutionResult::terminate_with_errors(
                                         VMErrorType::Struct(StructError::FieldNotFound {
@@ -469,118 +743,237 @@ impl Vm {
                                     );
                                 }
                             }
-                            _ => {
-                                panic!("<get_property> opcode must be used on a Struct like type")
+                            obj => {
+                                let received = obj.get_type();
+                                return VMExecutionResult::terminate_with_errors(
+                                    VMErrorType::TypeMismatch {
+                                        expected: "struct".to_string(),
+                                        received,
+                                    },
+                                    self,
+                                );
                             }
                         }
                     } else {
-                        // TODO: use self-vm errors
-                        panic!("Struct literal field must be indexed by string")
+                        let received = property.get_type();
+                        return VMExecutionResult::terminate_with_errors(
+                            VMErrorType::TypeMismatch {
+                                expected: "string".to_string(),
+                                received,
+                            },
+                            self,
+                        );
                     }
 
                     self.pc += 1;
                 }
-                Opcode::Call => {
+                Instruction::SetProperty => {
+                    let values = match self.get_stack_values(&3) {
+                        Ok(v) => v,
+                        Err(err) => return VMExecutionResult::terminate_with_errors(err, self),
+                    };
+                    let object = match &values[0] {
+                        Value::BoundAccess(bound) => bound.property.as_ref(),
+                        v => v,
+                    };
+                    let object_handle = match object {
+                        Value::Handle(h) => h.clone(),
+                        _ => {
+                            return VMExecutionResult::terminate_with_errors(
+                                VMErrorType::TypeMismatch {
+                                    expected: "struct_literal".to_string(),
+                                    received: self.value_type_name(object),
+                                },
+                                self,
+                            )
+                        }
+                    };
+                    let property_key = match values[1].as_string_obj(self) {
+                        Ok(v) => v,
+                        Err(err) => {
+                            return VMExecutionResult::terminate_with_errors(err.error_type, self)
+                        }
+                    };
+                    let value = values[2].clone();
+
+                    if debug {
+                        println!(
+                            "SET_PROPERTY -> {}({}) = {}",
+                            object.to_string(self),
+                            property_key,
+                            value.to_string(self)
+                        );
+                    }
+
+                    // check the field exists and the value
+                    // matches its annotation
+                    let annotation = match self.memory.resolve(&object_handle) {
+                        Ok(MemObject::StructLiteral(x)) => {
+                            if !x.fields.contains_key(&property_key) {
+                                return VMExecutionResult::terminate_with_errors(
+                                    VMErrorType::Struct(StructError::FieldNotFound {
+                                        field: property_key,
+                                        struct_type: x.to_string(),
+                                    }),
+                                    self,
+                                );
+                            }
+                            match x.declaration.as_ref().map(|d| self.memory.resolve(d)) {
+                                Some(Ok(MemObject::StructDeclaration(d))) => {
+                                    d.field_type(&property_key).cloned()
+                                }
+                                _ => None,
+                            }
+                        }
+                        Err(err) => return VMExecutionResult::terminate_with_errors(err, self),
+                        Ok(obj) => {
+                            return VMExecutionResult::terminate_with_errors(
+                                VMErrorType::TypeMismatch {
+                                    expected: "struct_literal".to_string(),
+                                    received: obj.get_type(),
+                                },
+                                self,
+                            )
+                        }
+                    };
+                    if let Some(annotation) = annotation {
+                        if !self.value_matches_annotation(&value, &annotation) {
+                            return VMExecutionResult::terminate_with_errors(
+                                VMErrorType::TypeMismatch {
+                                    expected: Vm::annotation_name(&annotation),
+                                    received: self.value_type_name(&value),
+                                },
+                                self,
+                            );
+                        }
+                    }
+
+                    if let Ok(MemObject::StructLiteral(x)) = self.memory.resolve_mut(&object_handle)
+                    {
+                        x.set_property(&property_key, value);
+                    }
                     self.pc += 1;
-                    let args = self.get_function_call_args();
-                    let callee_value = self.get_stack_values(&1);
-                    let ((caller_obj, caller_handle), callee_handle): (
-                        (&MemObject, Handle),
-                        Option<Handle>,
-                    ) = match callee_value[0].clone() {
-                        // Value::HeapRef(_ref) => {
-                        //     let owned_ref = _ref.clone();
-                        //     ((self.resolve_heap_ref(_ref), owned_ref), None)
-                        // }
-                        Value::Handle(handle) => ((self.memory.resolve(&handle), handle), None),
-                        Value::BoundAccess(b) => {
-                            if let Value::Handle(callee_handle) = b.property.as_ref() {
-                                (
-                                    (self.memory.resolve(&b.object), b.object),
-                                    Some(callee_handle.clone()),
-                                )
-                            } else {
-                                // nested bound accesses
-                                panic!("Invalid type for callee string")
+                }
+                Instruction::GetIndex => {
+                    let values = match self.get_stack_values(&2) {
+                        Ok(v) => v,
+                        Err(err) => return VMExecutionResult::terminate_with_errors(err, self),
+                    };
+                    let element = match self.index_target(&values[0], &values[1]) {
+                        Ok((handle, target)) => match (self.memory.resolve(&handle), target) {
+                            (Ok(MemObject::Vector(v)), IndexTarget::Element(index)) => {
+                                v.elements[index].clone()
                             }
+                            (Ok(MemObject::Map(m)), IndexTarget::Entry(key)) => match m.get(&key) {
+                                Some(value) => value,
+                                None => {
+                                    return VMExecutionResult::terminate_with_errors(
+                                        VMErrorType::Index(IndexError::KeyNotFound(key)),
+                                        self,
+                                    )
+                                }
+                            },
+                            _ => unreachable!("index_target matches the object type"),
+                        },
+                        Err(err) => return VMExecutionResult::terminate_with_errors(err, self),
+                    };
+
+                    if debug {
+                        println!("GET_INDEX <- {}", element.to_string(self));
+                    }
+
+                    self.push_to_stack(element, None);
+                    self.pc += 1;
+                }
+                Instruction::SetIndex => {
+                    let values = match self.get_stack_values(&3) {
+                        Ok(v) => v,
+                        Err(err) => return VMExecutionResult::terminate_with_errors(err, self),
+                    };
+                    let (handle, target) = match self.index_target(&values[0], &values[1]) {
+                        Ok(v) => v,
+                        Err(err) => return VMExecutionResult::terminate_with_errors(err, self),
+                    };
+
+                    if debug {
+                        println!("SET_INDEX -> {}", values[2].to_string(self));
+                    }
+
+                    match (self.memory.resolve_mut(&handle), target) {
+                        (Ok(MemObject::Vector(v)), IndexTarget::Element(index)) => {
+                            v.elements[index] = values[2].clone();
                         }
-                        _ => {
-                            // TODO: use self-vm error system
-                            panic!("Invalid type for callee string")
+                        // assigning to a missing key adds it to the map
+                        (Ok(MemObject::Map(m)), IndexTarget::Entry(key)) => {
+                            m.insert(key, values[2].clone());
                         }
+                        _ => unreachable!("index_target matches the object type"),
+                    }
+                    self.pc += 1;
+                }
+                Instruction::Call { number_of_args } => {
+                    let args = match self.get_stack_values(number_of_args) {
+                        Ok(v) => v,
+                        Err(err) => return VMExecutionResult::terminate_with_errors(err, self),
                     };
+                    // the callee origin names it on errors
+                    let Some(callee) = self.operand_stack.pop() else {
+                        return VMExecutionResult::terminate_with_errors(
+                            VMErrorType::StackUnderflow {
+                                needed: 1,
+                                available: 0,
+                            },
+                            self,
+                        );
+                    };
+                    let callee_origin = callee.origin;
+                    let (caller_handle, callee_handle): (Handle, Option<Handle>) =
+                        match callee.value {
+                            // Value::HeapRef(_ref) => {
+                            //     let owned_ref = _ref.clone();
+                            //     ((self.resolve_heap_ref(_ref), owned_ref), None)
+                            // }
+                            Value::Handle(handle) => (handle, None),
+                            Value::BoundAccess(b) => {
+                                if let Value::Handle(callee_handle) = b.property.as_ref() {
+                                    (b.object, Some(callee_handle.clone()))
+                                } else {
+                                    // a field holding a raw value: p.age()
+                                    return VMExecutionResult::terminate_with_errors(
+                                        VMErrorType::NotCallableError(b.property.to_string(self)),
+                                        self,
+                                    );
+                                }
+                            }
+                            callee => {
+                                let name = callee_origin.unwrap_or(callee.to_string(self));
+                                return VMExecutionResult::terminate_with_errors(
+                                    VMErrorType::NotCallableError(name),
+                                    self,
+                                );
+                            }
+                        };
 
+                    let caller_obj = match self.memory.resolve(&caller_handle) {
+                        Ok(v) => v,
+                        Err(err) => return VMExecutionResult::terminate_with_errors(err, self),
+                    };
                     match caller_obj {
-                        // FOR NAMED FUNCTIONS ACCESS
-                        MemObject::String(identifier_name) => {
+                        // FOR FUNCTIONS LOADED AS VALUES
+                        MemObject::Function(func) => {
                             if debug {
-                                println!("CALL -> {}", identifier_name.to_string())
+                                println!("CALL -> {}", func.identifier)
                             };
-                            match identifier_name.as_str() {
-                                // BUILTIN FUNCTIONS
-                                "eprintln" => {
-                                    println!("------ eprintln")
-                                }
-                                // RUNTIME DEFINED FUNCTIONS
-                                _ => {
-                                    // get the identifier from the heap
-                                    let value = if let Some(value) =
-                                        self.call_stack.resolve(&identifier_name)
-                                    {
-                                        value
-                                    } else {
-                                        return VMExecutionResult::terminate_with_errors(
-                                            VMErrorType::UndeclaredIdentifierError(
-                                                identifier_name.clone(),
-                                            ),
-                                            self,
-                                        );
-                                    };
-
-                                    match value {
-                                        Value::Handle(v) => {
-                                            // clone heap_object to be able to mutate the
-                                            // vm state
-                                            let heap_object = self.memory.resolve(&v);
-                                            if let MemObject::Function(func) = heap_object {
-                                                let func = func.clone();
-                                                let exec_result = self.run_function(
-                                                    &func,
-                                                    Some(caller_handle),
-                                                    args.clone(),
-                                                    debug,
-                                                );
-                                                if exec_result.error.is_some() {
-                                                    return VMExecutionResult::terminate_with_errors(
-                                                                exec_result.error.unwrap().error_type,
-                                                                self
-                                                            );
-                                                }
-                                                if let Some(returned_value) = &exec_result.result {
-                                                    self.push_to_stack(
-                                                        returned_value.clone(),
-                                                        Some(func.identifier.clone()),
-                                                    );
-                                                }
-                                            } else {
-                                                return VMExecutionResult::terminate_with_errors(
-                                                    VMErrorType::NotCallableError(
-                                                        identifier_name.clone(),
-                                                    ),
-                                                    self,
-                                                );
-                                            }
-                                        }
-                                        _ => {
-                                            return VMExecutionResult::terminate_with_errors(
-                                                VMErrorType::NotCallableError(
-                                                    identifier_name.clone(),
-                                                ),
-                                                self,
-                                            );
-                                        }
-                                    }
-                                }
+                            let func = func.clone();
+                            let exec_result = self.run_function(&func, None, args, debug);
+                            if exec_result.error.is_some() {
+                                return exec_result;
+                            }
+                            if let Some(returned_value) = &exec_result.result {
+                                self.push_to_stack(
+                                    returned_value.clone(),
+                                    Some(func.identifier.clone()),
+                                );
                             }
                         }
 
@@ -589,12 +982,54 @@ impl Vm {
                             let callee_handle = if let Some(c) = callee_handle {
                                 c
                             } else {
-                                // TODO: use self-vm error system
-                                panic!("callee is not defined for a struct as function caller")
+                                return VMExecutionResult::terminate_with_errors(
+                                    VMErrorType::NotCallableError(caller.struct_type.clone()),
+                                    self,
+                                );
+                            };
+
+                            let callee = self.memory.resolve(&callee_handle);
+                            if let Ok(MemObject::Function(func)) = callee {
+                                let func = func.clone();
+
+                                // methods receive the instance as 'self'
+                                let mut args = args.clone();
+                                if func.parameters.first().map(|p| p.as_str()) == Some("self") {
+                                    args.insert(0, Value::Handle(caller_handle.clone()));
+                                }
+
+                                let exec_result =
+                                    self.run_function(&func, Some(caller_handle), args, debug);
+                                if exec_result.error.is_some() {
+                                    return exec_result;
+                                }
+                                if let Some(returned_value) = &exec_result.result {
+                                    self.push_to_stack(
+                                        returned_value.clone(),
+                                        Some(func.identifier.clone()),
+                                    );
+                                }
+                            } else {
+                                return VMExecutionResult::terminate_with_errors(
+                                    VMErrorType::NotCallableError(caller.struct_type.clone()),
+                                    self,
+                                );
+                            }
+                        }
+
+                        // FOR STRUCTS STATIC FUNCTIONS
+                        MemObject::StructDeclaration(caller) => {
+                            let callee_handle = if let Some(c) = callee_handle {
+                                c
+                            } else {
+                                return VMExecutionResult::terminate_with_errors(
+                                    VMErrorType::NotCallableError(caller.to_string()),
+                                    self,
+                                );
                             };
 
                             let callee = self.memory.resolve(&callee_handle);
-                            if let MemObject::Function(func) = callee {
+                            if let Ok(MemObject::Function(func)) = callee {
                                 let func = func.clone();
                                 let exec_result = self.run_function(
                                     &func,
@@ -603,10 +1038,7 @@ impl Vm {
                                     debug,
                                 );
                                 if exec_result.error.is_some() {
-                                    return VMExecutionResult::terminate_with_errors(
-                                        exec_result.error.unwrap().error_type,
-                                        self,
-                                    );
+                                    return exec_result;
                                 }
                                 if let Some(returned_value) = &exec_result.result {
                                     self.push_to_stack(
@@ -616,7 +1048,7 @@ impl Vm {
                                 }
                             } else {
                                 return VMExecutionResult::terminate_with_errors(
-                                    VMErrorType::NotCallableError(caller.struct_type.clone()),
+                                    VMErrorType::NotCallableError(caller.to_string()),
                                     self,
                                 );
                             }
@@ -627,21 +1059,20 @@ impl Vm {
                             let callee_handle = if let Some(c) = callee_handle {
                                 c
                             } else {
-                                // TODO: use self-vm error system
-                                panic!("callee is not defined for a struct as function caller")
+                                return VMExecutionResult::terminate_with_errors(
+                                    VMErrorType::NotCallableError(caller.to_string()),
+                                    self,
+                                );
                             };
 
                             let callee = self.memory.resolve(&callee_handle);
-                            if let MemObject::Function(func) = callee {
+                            if let Ok(MemObject::Function(func)) = callee {
                                 let func = func.clone();
                                 let exec_result =
                                     // instead of none callee_handle
                                     self.run_function(&func, Some(caller_handle), args.clone(), debug);
                                 if exec_result.error.is_some() {
-                                    return VMExecutionResult::terminate_with_errors(
-                                        exec_result.error.unwrap().error_type,
-                                        self,
-                                    );
+                                    return exec_result;
                                 }
                                 if let Some(returned_value) = &exec_result.result {
                                     self.push_to_stack(
@@ -657,17 +1088,19 @@ impl Vm {
                             }
                         }
 
-                        // FOR VECTOR CALLABLE MEMBERS
-                        MemObject::Vector(caller) => {
+                        // FOR VECTOR AND MAP CALLABLE MEMBERS
+                        caller @ (MemObject::Vector(_) | MemObject::Map(_)) => {
                             let callee_handle = if let Some(c) = callee_handle {
                                 c
                             } else {
-                                // TODO: use self-vm error system
-                                panic!("callee is not defined for a vec as a function caller")
+                                return VMExecutionResult::terminate_with_errors(
+                                    VMErrorType::NotCallableError(caller.get_type()),
+                                    self,
+                                );
                             };
 
                             let callee = self.memory.resolve(&callee_handle);
-                            if let MemObject::Function(func) = callee {
+                            if let Ok(MemObject::Function(func)) = callee {
                                 let func = func.clone();
                                 let exec_result = self.run_function(
                                     &func,
@@ -676,10 +1109,7 @@ impl Vm {
                                     debug,
                                 );
                                 if exec_result.error.is_some() {
-                                    return VMExecutionResult::terminate_with_errors(
-                                        exec_result.error.unwrap().error_type,
-                                        self,
-                                    );
+                                    return exec_result;
                                 }
                                 if let Some(returned_value) = &exec_result.result {
                                     self.push_to_stack(
@@ -694,20 +1124,27 @@ impl Vm {
                                 );
                             }
                         }
-                        _ => {
-                            panic!("Invalid type for callee string")
+                        caller => {
+                            let name = callee_origin.unwrap_or(caller.get_type());
+                            return VMExecutionResult::terminate_with_errors(
+                                VMErrorType::NotCallableError(name),
+                                self,
+                            );
                         }
                     }
+                    self.pc += 1;
                 }
-                Opcode::Import => {
-                    let values = self.get_stack_values(&1);
-                    let module_name_value = values[0].clone();
-                    let mod_bytecode_length =
-                        Vm::read_offset(&self.bytecode[self.pc + 1..self.pc + 5]);
-                    self.pc += 4;
+                Instruction::Import { .. } => {
+                    let module_name_value = match self.get_stack_values(&1) {
+                        Ok(v) => v[0].clone(),
+                        Err(err) => return VMExecutionResult::terminate_with_errors(err, self),
+                    };
 
                     if let Value::Handle(mod_handle) = module_name_value {
-                        let module_name = self.memory.resolve(&mod_handle).to_string(self);
+                        let module_name = match self.memory.resolve(&mod_handle) {
+                            Ok(v) => v.to_string(self),
+                            Err(err) => return VMExecutionResult::terminate_with_errors(err, self),
+                        };
                         let native_module = get_native_module_type(module_name.as_str());
                         // native module
                         if let Some(nm) = native_module {
@@ -734,17 +1171,12 @@ impl Vm {
                                 .file_name()
                                 .and_then(|s| s.to_str())
                                 .unwrap_or("unknown");
-                            let mod_bytecode = &self.bytecode
-                                [self.pc + 1..(self.pc + (mod_bytecode_length as usize)) + 1];
-                            self.pc += mod_bytecode_length as usize;
+                            let mod_code = code.bodies[self.pc].clone().unwrap_or_default();
                             // here we should generate a definition of the module
                             // and push it onto the heap and add a Handle to the stack
                             // --
-                            let exec_result = self.run_module(
-                                &mod_name.to_string(),
-                                mod_bytecode.to_vec(),
-                                debug,
-                            );
+                            let exec_result =
+                                self.run_module(&mod_name.to_string(), mod_code, debug);
                             if exec_result.error.is_some() {
                                 return exec_result;
                             }
@@ -758,17 +1190,25 @@ impl Vm {
                             }
                         }
                     } else {
-                        // TODO: use self-vm errors system
-                        panic!("invalid value type as module name for import")
+                        return VMExecutionResult::terminate_with_errors(
+                            VMErrorType::TypeMismatch {
+                                expected: "string".to_string(),
+                                received: self.value_type_name(&module_name_value),
+                            },
+                            self,
+                        );
                     }
 
                     self.pc += 1;
                 }
-                Opcode::Export => {
-                    let arg_ref = self.get_stack_values(&1)[0].clone();
+                Instruction::Export => {
+                    let arg_ref = match self.get_stack_values(&1) {
+                        Ok(v) => v[0].clone(),
+                        Err(err) => return VMExecutionResult::terminate_with_errors(err, self),
+                    };
                     if let Value::Handle(r) = arg_ref.clone() {
                         let arg = self.memory.resolve(&r);
-                        if let MemObject::String(s) = arg {
+                        if let Ok(MemObject::String(s)) = arg {
                             if debug {
                                 println!("EXPORT -> {}", s)
                             }
@@ -787,21 +1227,20 @@ impl Vm {
                     }
                     self.pc += 1;
                 }
-                Opcode::Return => {
-                    let return_value = self.get_stack_values(&1)[0].clone();
+                Instruction::Return => {
+                    let return_value = match self.get_stack_values(&1) {
+                        Ok(v) => v[0].clone(),
+                        Err(err) => return VMExecutionResult::terminate_with_errors(err, self),
+                    };
                     return VMExecutionResult::terminate(Some(return_value));
                 }
-                Opcode::Add => {
+                Instruction::Add => {
                     // execution
-                    let right_operand = self.operand_stack.pop();
-                    let left_operand = self.operand_stack.pop();
-
-                    if left_operand.is_none() || right_operand.is_none() {
-                        panic!("Operands stack underflow");
+                    let operands_stack_values = match self.pop_operands() {
+                        Ok(v) => v,
+                        Err(err) => return VMExecutionResult::terminate_with_errors(err, self),
                     };
 
-                    let operands_stack_values = (left_operand.unwrap(), right_operand.unwrap());
-
                     let error = self.run_binary_expression("+", operands_stack_values);
                     if let Some(err) = error {
                         return VMExecutionResult::terminate_with_errors(err, self);
@@ -809,17 +1248,13 @@ impl Vm {
 
                     self.pc += 1;
                 }
-                Opcode::Substract => {
+                Instruction::Substract => {
                     // execution
-                    let right_operand = self.operand_stack.pop();
-                    let left_operand = self.operand_stack.pop();
-
-                    if left_operand.is_none() || right_operand.is_none() {
-                        panic!("Operands stack underflow");
+                    let operands_stack_values = match self.pop_operands() {
+                        Ok(v) => v,
+                        Err(err) => return VMExecutionResult::terminate_with_errors(err, self),
                     };
 
-                    let operands_stack_values = (left_operand.unwrap(), right_operand.unwrap());
-
                     let error = self.run_binary_expression("-", operands_stack_values);
                     if let Some(err) = error {
                         return VMExecutionResult::terminate_with_errors(err, self);
@@ -827,17 +1262,13 @@ impl Vm {
 
                     self.pc += 1;
                 }
-                Opcode::Multiply => {
+                Instruction::Multiply => {
                     // execution
-                    let right_operand = self.operand_stack.pop();
-                    let left_operand = self.operand_stack.pop();
-
-                    if left_operand.is_none() || right_operand.is_none() {
-                        panic!("Operands stack underflow");
+                    let operands_stack_values = match self.pop_operands() {
+                        Ok(v) => v,
+                        Err(err) => return VMExecutionResult::terminate_with_errors(err, self),
                     };
 
-                    let operands_stack_values = (left_operand.unwrap(), right_operand.unwrap());
-
                     let error = self.run_binary_expression("*", operands_stack_values);
                     if let Some(err) = error {
                         return VMExecutionResult::terminate_with_errors(err, self);
@@ -845,17 +1276,13 @@ impl Vm {
 
                     self.pc += 1;
                 }
-                Opcode::Divide => {
+                Instruction::Divide => {
                     // execution
-                    let right_operand = self.operand_stack.pop();
-                    let left_operand = self.operand_stack.pop();
-
-                    if left_operand.is_none() || right_operand.is_none() {
-                        panic!("Operands stack underflow");
+                    let operands_stack_values = match self.pop_operands() {
+                        Ok(v) => v,
+                        Err(err) => return VMExecutionResult::terminate_with_errors(err, self),
                     };
 
-                    let operands_stack_values = (left_operand.unwrap(), right_operand.unwrap());
-
                     let error = self.run_binary_expression("/", operands_stack_values);
                     if let Some(err) = error {
                         return VMExecutionResult::terminate_with_errors(err, self);
@@ -863,17 +1290,13 @@ impl Vm {
 
                     self.pc += 1;
                 }
-                Opcode::GreaterThan => {
+                Instruction::GreaterThan => {
                     // execution
-                    let right_operand = self.operand_stack.pop();
-                    let left_operand = self.operand_stack.pop();
-
-                    if left_operand.is_none() || right_operand.is_none() {
-                        panic!("Operands stack underflow");
+                    let operands_stack_values = match self.pop_operands() {
+                        Ok(v) => v,
+                        Err(err) => return VMExecutionResult::terminate_with_errors(err, self),
                     };
 
-                    let operands_stack_values = (left_operand.unwrap(), right_operand.unwrap());
-
                     let error = self.run_binary_expression(">", operands_stack_values);
                     if let Some(err) = error {
                         return VMExecutionResult::terminate_with_errors(err, self);
@@ -881,17 +1304,13 @@ impl Vm {
 
                     self.pc += 1;
                 }
-                Opcode::LessThan => {
+                Instruction::LessThan => {
                     // execution
-                    let right_operand = self.operand_stack.pop();
-                    let left_operand = self.operand_stack.pop();
-
-                    if left_operand.is_none() || right_operand.is_none() {
-                        panic!("Operands stack underflow");
+                    let operands_stack_values = match self.pop_operands() {
+                        Ok(v) => v,
+                        Err(err) => return VMExecutionResult::terminate_with_errors(err, self),
                     };
 
-                    let operands_stack_values = (left_operand.unwrap(), right_operand.unwrap());
-
                     let error = self.run_binary_expression("<", operands_stack_values);
                     if let Some(err) = error {
                         return VMExecutionResult::terminate_with_errors(err, self);
@@ -899,16 +1318,40 @@ impl Vm {
 
                     self.pc += 1;
                 }
-                Opcode::Equals => {
+                Instruction::GreaterThanOrEqual => {
                     // execution
-                    let right_operand = self.operand_stack.pop();
-                    let left_operand = self.operand_stack.pop();
+                    let operands_stack_values = match self.pop_operands() {
+                        Ok(v) => v,
+                        Err(err) => return VMExecutionResult::terminate_with_errors(err, self),
+                    };
 
-                    if left_operand.is_none() || right_operand.is_none() {
-                        panic!("Operands stack underflow");
+                    let error = self.run_binary_expression(">=", operands_stack_values);
+                    if let Some(err) = error {
+                        return VMExecutionResult::terminate_with_errors(err, self);
+                    }
+
+                    self.pc += 1;
+                }
+                Instruction::LessThanOrEqual => {
+                    // execution
+                    let operands_stack_values = match self.pop_operands() {
+                        Ok(v) => v,
+                        Err(err) => return VMExecutionResult::terminate_with_errors(err, self),
                     };
 
-                    let operands_stack_values = (left_operand.unwrap(), right_operand.unwrap());
+                    let error = self.run_binary_expression("<=", operands_stack_values);
+                    if let Some(err) = error {
+                        return VMExecutionResult::terminate_with_errors(err, self);
+                    }
+
+                    self.pc += 1;
+                }
+                Instruction::Equals => {
+                    // execution
+                    let operands_stack_values = match self.pop_operands() {
+                        Ok(v) => v,
+                        Err(err) => return VMExecutionResult::terminate_with_errors(err, self),
+                    };
 
                     let error = self.run_binary_expression("==", operands_stack_values);
                     if let Some(err) = error {
@@ -917,17 +1360,13 @@ impl Vm {
 
                     self.pc += 1;
                 }
-                Opcode::NotEquals => {
+                Instruction::NotEquals => {
                     // execution
-                    let right_operand = self.operand_stack.pop();
-                    let left_operand = self.operand_stack.pop();
-
-                    if left_operand.is_none() || right_operand.is_none() {
-                        panic!("Operands stack underflow");
+                    let operands_stack_values = match self.pop_operands() {
+                        Ok(v) => v,
+                        Err(err) => return VMExecutionResult::terminate_with_errors(err, self),
                     };
 
-                    let operands_stack_values = (left_operand.unwrap(), right_operand.unwrap());
-
                     let error = self.run_binary_expression("!=", operands_stack_values);
                     if let Some(err) = error {
                         return VMExecutionResult::terminate_with_errors(err, self);
@@ -935,9 +1374,31 @@ impl Vm {
 
                     self.pc += 1;
                 }
-                Opcode::FFI_Call => {
-                    self.pc += 1; // consume call opcode
-                    let args = self.get_function_call_args();
+                Instruction::Not => {
+                    // execution
+                    let operand = match self.get_stack_values(&1) {
+                        Ok(v) => v[0].clone(),
+                        Err(err) => return VMExecutionResult::terminate_with_errors(err, self),
+                    };
+
+                    let value = match operand.as_bool(self) {
+                        Ok(v) => v,
+                        Err(err) => {
+                            return VMExecutionResult::terminate_with_errors(err.error_type, self)
+                        }
+                    };
+                    if debug {
+                        println!("NOT <- {}", value);
+                    }
+                    self.push_to_stack(Value::RawValue(RawValue::Bool(Bool::new(!value))), None);
+
+                    self.pc += 1;
+                }
+                Instruction::FFI_Call { number_of_args } => {
+                    let args = match self.get_stack_values(number_of_args) {
+                        Ok(v) => v,
+                        Err(err) => return VMExecutionResult::terminate_with_errors(err, self),
+                    };
                     let mut resolved_args = Vec::new();
                     for val in args {
                         match self.value_to_string(val) {
@@ -946,12 +1407,13 @@ impl Vm {
                         }
                     }
                     if debug {
-                        println!("CALL -> {}", resolved_args[0].to_string())
+                        if let Some(handler) = resolved_args.first() {
+                            println!("CALL -> {}", handler)
+                        }
+                    }
+                    if let Err(err) = call_handler(&self.ffi_handlers, resolved_args) {
+                        return VMExecutionResult::terminate_with_errors(err, self);
                     }
-                    call_handler(&self.ffi_handlers, resolved_args);
-                }
-                _ => {
-                    println!("unhandled opcode");
                     self.pc += 1;
                 }
             };
@@ -965,8 +1427,24 @@ impl Vm {
         operator: &str,
         operands: (OperandsStackValue, OperandsStackValue),
     ) -> Option<VMErrorType> {
-        let left = operands.0;
-        let right = operands.1;
+        let mut left = operands.0;
+        let mut right = operands.1;
+
+        // member accesses (p.x) operate with the property value
+        if let Value::BoundAccess(b) = &left.value {
+            left.value = b.property.as_ref().clone();
+        }
+        if let Value::BoundAccess(b) = &right.value {
+            right.value = b.property.as_ref().clone();
+        }
+
+        // integer division by zero would panic
+        if let Value::RawValue(r) = &right.value {
+            let is_zero = r.as_isize() == Some(0) || r.as_usize() == Some(0);
+            if operator == "/" && is_zero {
+                return Some(VMErrorType::DivisionByZero(left));
+            }
+        }
 
         let value: Value;
         // cloned here, to be able to use later on
@@ -975,55 +1453,139 @@ impl Vm {
             (Value::RawValue(l), Value::RawValue(r)) => {
                 let result_value = match (l, r) {
                     (RawValue::I32(l), RawValue::I32(r)) => match operator {
-                        "+" => RawValue::I32(I32::new(l.value + r.value)),
-                        "-" => RawValue::I32(I32::new(l.value - r.value)),
-                        "*" => RawValue::I32(I32::new(l.value * r.value)),
-                        "/" => RawValue::I32(I32::new(l.value / r.value)),
+                        "+" | "-" | "*" | "/" => {
+                            let result = match operator {
+                                "+" => l.value.checked_add(r.value),
+                                "-" => l.value.checked_sub(r.value),
+                                "*" => l.value.checked_mul(r.value),
+                                _ => l.value.checked_div(r.value),
+                            };
+                            match result {
+                                Some(v) => RawValue::I32(I32::new(v)),
+                                None => {
+                                    return Some(VMErrorType::IntegerOverflow(IntegerOverflow {
+                                        data_type: DataType::I32,
+                                        operation: format!("{} {} {}", l.value, operator, r.value),
+                                    }))
+                                }
+                            }
+                        }
                         ">" => RawValue::Bool(Bool::new(l.value > r.value)),
                         "<" => RawValue::Bool(Bool::new(l.value < r.value)),
+                        ">=" => RawValue::Bool(Bool::new(l.value >= r.value)),
+                        "<=" => RawValue::Bool(Bool::new(l.value <= r.value)),
                         "==" => RawValue::Bool(Bool::new(l.value == r.value)),
                         "!=" => RawValue::Bool(Bool::new(l.value != r.value)),
                         _ => {
-                            panic!("operator not implemented")
+                            return Some(VMErrorType::InvalidBinaryOperation(
+                                InvalidBinaryOperation {
+                                    left: DataType::I32,
+                                    right: DataType::I32,
+                                    operator: operator.to_string(),
+                                },
+                            ))
                         }
                     },
                     (RawValue::I64(l), RawValue::I64(r)) => match operator {
-                        "+" => RawValue::I64(I64::new(l.value + r.value)),
-                        "-" => RawValue::I64(I64::new(l.value - r.value)),
-                        "*" => RawValue::I64(I64::new(l.value * r.value)),
-                        "/" => RawValue::I64(I64::new(l.value / r.value)),
+                        "+" | "-" | "*" | "/" => {
+                            let result = match operator {
+                                "+" => l.value.checked_add(r.value),
+                                "-" => l.value.checked_sub(r.value),
+                                "*" => l.value.checked_mul(r.value),
+                                _ => l.value.checked_div(r.value),
+                            };
+                            match result {
+                                Some(v) => RawValue::I64(I64::new(v)),
+                                None => {
+                                    return Some(VMErrorType::IntegerOverflow(IntegerOverflow {
+                                        data_type: DataType::I64,
+                                        operation: format!("{} {} {}", l.value, operator, r.value),
+                                    }))
+                                }
+                            }
+                        }
                         ">" => RawValue::Bool(Bool::new(l.value > r.value)),
                         "<" => RawValue::Bool(Bool::new(l.value < r.value)),
+                        ">=" => RawValue::Bool(Bool::new(l.value >= r.value)),
+                        "<=" => RawValue::Bool(Bool::new(l.value <= r.value)),
                         "==" => RawValue::Bool(Bool::new(l.value == r.value)),
                         "!=" => RawValue::Bool(Bool::new(l.value != r.value)),
                         _ => {
-                            panic!("operator not implemented in i64")
+                            return Some(VMErrorType::InvalidBinaryOperation(
+                                InvalidBinaryOperation {
+                                    left: DataType::I64,
+                                    right: DataType::I64,
+                                    operator: operator.to_string(),
+                                },
+                            ))
                         }
                     },
                     (RawValue::U32(l), RawValue::U32(r)) => match operator {
-                        "+" => RawValue::U32(U32::new(l.value + r.value)),
-                        "-" => RawValue::U32(U32::new(l.value - r.value)),
-                        "*" => RawValue::U32(U32::new(l.value * r.value)),
-                        "/" => RawValue::U32(U32::new(l.value / r.value)),
+                        "+" | "-" | "*" | "/" => {
+                            let result = match operator {
+                                "+" => l.value.checked_add(r.value),
+                                "-" => l.value.checked_sub(r.value),
+                                "*" => l.value.checked_mul(r.value),
+                                _ => l.value.checked_div(r.value),
+                            };
+                            match result {
+                                Some(v) => RawValue::U32(U32::new(v)),
+                                None => {
+                                    return Some(VMErrorType::IntegerOverflow(IntegerOverflow {
+                                        data_type: DataType::U32,
+                                        operation: format!("{} {} {}", l.value, operator, r.value),
+                                    }))
+                                }
+                            }
+                        }
                         ">" => RawValue::Bool(Bool::new(l.value > r.value)),
                         "<" => RawValue::Bool(Bool::new(l.value < r.value)),
+                        ">=" => RawValue::Bool(Bool::new(l.value >= r.value)),
+                        "<=" => RawValue::Bool(Bool::new(l.value <= r.value)),
                         "==" => RawValue::Bool(Bool::new(l.value == r.value)),
                         "!=" => RawValue::Bool(Bool::new(l.value != r.value)),
                         _ => {
-                            panic!("operator not implemented in u32")
+                            return Some(VMErrorType::InvalidBinaryOperation(
+                                InvalidBinaryOperation {
+                                    left: DataType::U32,
+                                    right: DataType::U32,
+                                    operator: operator.to_string(),
+                                },
+                            ))
                         }
                     },
                     (RawValue::U64(l), RawValue::U64(r)) => match operator {
-                        "+" => RawValue::U64(U64::new(l.value + r.value)),
-                        "-" => RawValue::U64(U64::new(l.value - r.value)),
-                        "*" => RawValue::U64(U64::new(l.value * r.value)),
-                        "/" => RawValue::U64(U64::new(l.value / r.value)),
+                        "+" | "-" | "*" | "/" => {
+                            let result = match operator {
+                                "+" => l.value.checked_add(r.value),
+                                "-" => l.value.checked_sub(r.value),
+                                "*" => l.value.checked_mul(r.value),
+                                _ => l.value.checked_div(r.value),
+                            };
+                            match result {
+                                Some(v) => RawValue::U64(U64::new(v)),
+                                None => {
+                                    return Some(VMErrorType::IntegerOverflow(IntegerOverflow {
+                                        data_type: DataType::U64,
+                                        operation: format!("{} {} {}", l.value, operator, r.value),
+                                    }))
+                                }
+                            }
+                        }
                         ">" => RawValue::Bool(Bool::new(l.value > r.value)),
                         "<" => RawValue::Bool(Bool::new(l.value < r.value)),
+                        ">=" => RawValue::Bool(Bool::new(l.value >= r.value)),
+                        "<=" => RawValue::Bool(Bool::new(l.value <= r.value)),
                         "==" => RawValue::Bool(Bool::new(l.value == r.value)),
                         "!=" => RawValue::Bool(Bool::new(l.value != r.value)),
                         _ => {
-                            panic!("operator not implemented in u64")
+                            return Some(VMErrorType::InvalidBinaryOperation(
+                                InvalidBinaryOperation {
+                                    left: DataType::U64,
+                                    right: DataType::U64,
+                                    operator: operator.to_string(),
+                                },
+                            ))
                         }
                     },
                     (RawValue::F64(l), RawValue::F64(r)) => match operator {
@@ -1033,10 +1595,18 @@ impl Vm {
                         "/" => RawValue::F64(F64::new(l.value / r.value)),
                         ">" => RawValue::Bool(Bool::new(l.value > r.value)),
                         "<" => RawValue::Bool(Bool::new(l.value < r.value)),
+                        ">=" => RawValue::Bool(Bool::new(l.value >= r.value)),
+                        "<=" => RawValue::Bool(Bool::new(l.value <= r.value)),
                         "==" => RawValue::Bool(Bool::new(l.value == r.value)),
                         "!=" => RawValue::Bool(Bool::new(l.value != r.value)),
                         _ => {
-                            panic!("operator not implemented in f64")
+                            return Some(VMErrorType::InvalidBinaryOperation(
+                                InvalidBinaryOperation {
+                                    left: DataType::F64,
+                                    right: DataType::F64,
+                                    operator: operator.to_string(),
+                                },
+                            ))
                         }
                     },
                     (RawValue::Nothing, RawValue::Nothing) => {
@@ -1074,8 +1644,14 @@ impl Vm {
             (Value::Handle(l), Value::Handle(r)) => {
                 // here implement binary operations between different
                 // types once the Handle is resolved to the actual value
-                let l_heap_object = self.memory.resolve(&l);
-                let r_heap_object = self.memory.resolve(&r);
+                let l_heap_object = match self.memory.resolve(&l) {
+                    Ok(v) => v,
+                    Err(err) => return Some(err),
+                };
+                let r_heap_object = match self.memory.resolve(&r) {
+                    Ok(v) => v,
+                    Err(err) => return Some(err),
+                };
 
                 let result_value = match (l_heap_object, r_heap_object) {
                     (MemObject::String(left_string), MemObject::String(right_string)) => {
@@ -1118,7 +1694,13 @@ impl Vm {
                 return Some(VMErrorType::TypeCoercionError(right))
             }
             _ => {
-                panic!("invalid Value type for a binary expression")
+                return Some(VMErrorType::InvalidBinaryOperation(
+                    InvalidBinaryOperation {
+                        left: DataType::Unknown,
+                        right: DataType::Unknown,
+                        operator: operator.to_string(),
+                    },
+                ))
             }
         }
 
@@ -1129,14 +1711,17 @@ impl Vm {
     fn run_module(
         &mut self,
         mod_name: &String,
-        mod_bytecode: Vec<u8>,
+        mod_code: Rc<Code>,
         debug: bool,
     ) -> VMExecutionResult {
         let return_pc = self.pc;
-        let main_bytecode = std::mem::take(&mut self.bytecode);
+        let call_pc = self.position();
+        let main_code = std::mem::replace(&mut self.code, mod_code);
+        let return_module = std::mem::replace(&mut self.module, mod_name.to_string());
 
-        self.call_stack.push();
-        self.bytecode = mod_bytecode.clone();
+        // modules do not see the importer scope
+        self.call_stack
+            .push("<module>".to_string(), mod_name.to_string(), call_pc, None);
         self.pc = 0;
         let mut mod_exec_result = self.run_bytecode(debug);
 
@@ -1150,7 +1735,8 @@ impl Vm {
             mod_exec_result.result = Some(Value::Handle(exports_handle));
         }
         self.pc = return_pc;
-        self.bytecode = main_bytecode;
+        self.code = main_code;
+        self.module = return_module;
 
         mod_exec_result
     }
@@ -1163,54 +1749,55 @@ impl Vm {
         debug: bool,
     ) -> VMExecutionResult {
         let execution_result = match &func.engine {
-            Engine::Bytecode(bytecode) => {
+            Engine::Bytecode(body) => {
                 let return_pc = self.pc;
-                let main_bytecode = std::mem::take(&mut self.bytecode);
-
-                self.call_stack.push();
-                for (index, param) in func.parameters.iter().enumerate() {
-                    if index < args.len() {
-                        self.call_stack
-                            .put_to_frame(param.clone(), args[index].clone());
-                    } else {
-                        self.call_stack
-                            .put_to_frame(param.clone(), Value::RawValue(RawValue::Nothing));
-                    }
+                let call_pc = self.position();
+                let main_code = std::mem::replace(&mut self.code, body.clone());
+
+                // the new frame is chained to the captured environment,
+                // not to the caller's frame (lexical scoping)
+                self.call_stack.push(
+                    func.identifier.clone(),
+                    func.module.clone(),
+                    call_pc,
+                    func.env.clone(),
+                );
+                // parameters take the first slots of the frame
+                for index in 0..func.parameters.len() {
+                    let value = match args.get(index) {
+                        Some(arg) => arg.clone(),
+                        None => Value::RawValue(RawValue::Nothing),
+                    };
+                    self.call_stack.assign_local(0, index as u32, value);
                 }
-                self.bytecode = bytecode.clone();
                 self.pc = 0;
 
                 let function_exec_result = self.run_bytecode(debug);
                 // recover state after execution
                 self.call_stack.pop();
                 self.pc = return_pc;
-                self.bytecode = main_bytecode;
+                self.code = main_code;
 
                 function_exec_result
             }
             Engine::Native(native) => {
                 if args.len() < func.parameters.len() {
-                    // TODO: use self-vm errors system
-                    panic!(
-                        "function '{}' requires {} parameters, provided {}",
-                        func.identifier,
-                        func.parameters.len(),
-                        args.len()
-                    )
+                    return VMExecutionResult::terminate_with_errors(
+                        VMErrorType::TypeError(TypeError::InvalidArgsCount {
+                            expected: func.parameters.len() as u32,
+                            received: args.len() as u32,
+                        }),
+                        self,
+                    );
                 }
+                self.native_depth += 1;
                 let execution_result = native(self, caller, args, debug);
-                if let Ok(result) = execution_result {
+                self.native_depth -= 1;
+                match execution_result {
                     // we could return the result value, using
                     // it as the return value of the function
-                    VMExecutionResult {
-                        error: None,
-                        result: Some(result),
-                    }
-                } else {
-                    VMExecutionResult {
-                        error: Some(execution_result.unwrap_err()),
-                        result: None,
-                    }
+                    Ok(result) => VMExecutionResult::terminate(Some(result)),
+                    Err(error) => VMExecutionResult::terminate_with_error(error),
                 }
             }
         };
@@ -1218,124 +1805,62 @@ impl Vm {
         return execution_result;
     }
 
-    // REFACTOR: this function should return a Result<(DataType, Vec<u8>), VMError>
-    fn get_value_length(&mut self) -> (DataType, Vec<u8>) {
-        let data_type = DataType::to_opcode(self.bytecode[self.pc]);
-        let value_length = match data_type {
-            DataType::I32 => 4,
-            DataType::I64 => 8,
-            DataType::U32 => 4,
-            DataType::U64 => 8,
-            DataType::F64 => 8,
-            DataType::Nothing => 0,
-            DataType::Bool => 1,
-            DataType::Utf8 => {
-                self.pc += 1;
-                let (data_type, value) = self.get_value_length();
-                if data_type != DataType::U32 {
-                    panic!("bad utf8 value length")
-                }
-
-                let (string_length, _) = self.bytes_to_data(&DataType::U32, &value);
-                if let Value::RawValue(RawValue::U32(val)) = string_length {
-                    val.value as usize
-                } else {
-                    panic!("Unexpected value type for string length");
-                }
-            }
-            DataType::StructLiteral => 4, // fields count
-            DataType::Vector => 4,        // elements count
-            _ => {
-                println!("data_type: {:#?}", data_type);
-                panic!("Unsupported datatype")
-            }
-        };
-
-        if (self.pc + value_length) >= self.bytecode.len() {
-            panic!("Invalid value size at position {}", self.pc + 1);
-        };
-
-        let value_bytes = self.bytecode[self.pc + 1..self.pc + 1 + value_length].to_vec();
-        self.pc += value_length;
-
-        (data_type, value_bytes)
-    }
-
-    // REFACTOR: this function should return a Result<(Value, String), VMError>
-    pub fn bytes_to_data(&mut self, data_type: &DataType, value: &Vec<u8>) -> (Value, String) {
+    pub fn bytes_to_data(
+        &mut self,
+        data_type: &DataType,
+        value: &[u8],
+    ) -> Result<(Value, String), VMErrorType> {
         let printable_value;
         let value = match data_type {
             DataType::I32 => {
-                let value = i32::from_le_bytes(
-                    value
-                        .as_slice()
-                        .try_into()
-                        .expect("Provided value is incorrect"),
-                );
+                let value = i32::from_le_bytes(self.fixed_bytes(value)?);
                 printable_value = value.to_string();
                 Value::RawValue(RawValue::I32(I32::new(value)))
             }
             DataType::I64 => {
-                let value = i64::from_le_bytes(
-                    value
-                        .as_slice()
-                        .try_into()
-                        .expect("Provided value is incorrect"),
-                );
+                let value = i64::from_le_bytes(self.fixed_bytes(value)?);
                 printable_value = value.to_string();
                 Value::RawValue(RawValue::I64(I64::new(value)))
             }
             DataType::U32 => {
-                let value = u32::from_le_bytes(
-                    value
-                        .as_slice()
-                        .try_into()
-                        .expect("Provided value is incorrect"),
-                );
+                let value = u32::from_le_bytes(self.fixed_bytes(value)?);
                 printable_value = value.to_string();
                 Value::RawValue(RawValue::U32(U32::new(value)))
             }
             DataType::U64 => {
-                let value = u64::from_le_bytes(
-                    value
-                        .as_slice()
-                        .try_into()
-                        .expect("Provided value is incorrect"),
-                );
+                let value = u64::from_le_bytes(self.fixed_bytes(value)?);
                 printable_value = value.to_string();
                 Value::RawValue(RawValue::U64(U64::new(value)))
             }
             DataType::F64 => {
-                let value = f64::from_le_bytes(
-                    value
-                        .as_slice()
-                        .try_into()
-                        .expect("Provided value is incorrect"),
-                );
+                let value = f64::from_le_bytes(self.fixed_bytes(value)?);
                 printable_value = value.to_string();
                 Value::RawValue(RawValue::F64(F64::new(value)))
             }
             DataType::Utf8 => {
-                let value =
-                    String::from_utf8(value.clone()).expect("Provided value is not valid UTF-8");
+                let value = match std::str::from_utf8(value) {
+                    Ok(v) => v,
+                    Err(_) => {
+                        return Err(VMErrorType::Bytecode(BytecodeError::InvalidUtf8 {
+                            at: self.position(),
+                        }))
+                    }
+                };
                 printable_value = value.to_string();
 
-                let value_handle = self.memory.alloc(MemObject::String(value));
+                let value_handle = match self.interned.get(value) {
+                    Some(handle) => handle.clone(),
+                    None => {
+                        let handle = self.memory.alloc(MemObject::String(value.to_string()));
+                        self.interned.insert(value.to_string(), handle.clone());
+                        handle
+                    }
+                };
                 Value::Handle(value_handle)
             }
             DataType::Vector => {
-                let elements_count_bytes = if value.len() >= 4 {
-                    &value[value.len() - 4..]
-                } else {
-                    panic!("Struct literal must contain more than 4 bytes");
-                };
-
-                let elements_count = u32::from_le_bytes(
-                    elements_count_bytes
-                        .try_into()
-                        .expect("Provided value is incorrect"),
-                );
-                let elements = self.get_stack_values(&elements_count);
+                let elements_count = u32::from_le_bytes(self.fixed_bytes(value)?);
+                let elements = self.get_stack_values(&elements_count)?;
 
                 let mut vector = Vector::new(elements);
                 vector::init_vector_members(&mut vector, &self);
@@ -1344,61 +1869,111 @@ impl Vm {
                 let value_handle = self.memory.alloc(MemObject::Vector(vector));
                 Value::Handle(value_handle)
             }
-            DataType::StructLiteral => {
-                let struct_type = self.get_stack_values(&1)[0].clone();
-                let fields_count_bytes = if value.len() >= 4 {
-                    &value[value.len() - 4..]
-                } else {
-                    panic!("Struct literal must contain more than 4 bytes");
-                };
+            DataType::Map => {
+                let entries_count = u32::from_le_bytes(self.fixed_bytes(value)?);
+
+                // each entry is stored as the key followed by its value
+                let mut entries = vec![];
+                let flat_entries = self.get_stack_values(&(entries_count * 2))?;
+                for i in (0..entries_count * 2).step_by(2) {
+                    let key_handle = flat_entries[i as usize].clone();
+                    let entry_value = flat_entries[(i + 1) as usize].clone();
+
+                    // keys are interned constants, so they are
+                    // copied and not freed
+                    if let Value::Handle(key_handle) = key_handle {
+                        match self.memory.resolve(&key_handle)? {
+                            MemObject::String(key) => entries.push((key.clone(), entry_value)),
+                            key => {
+                                return Err(VMErrorType::Index(IndexError::InvalidKeyType(
+                                    key.get_type(),
+                                )))
+                            }
+                        }
+                    } else {
+                        return Err(VMErrorType::Index(IndexError::InvalidKeyType(
+                            self.value_type_name(&key_handle),
+                        )));
+                    }
+                }
 
-                let fields_count = u32::from_le_bytes(
-                    fields_count_bytes
-                        .try_into()
-                        .expect("Provided value is incorrect"),
-                );
+                let mut map = Map::new(entries);
+                map::init_map_members(&mut map, self);
+                printable_value = map.to_string(self);
+
+                let value_handle = self.memory.alloc(MemObject::Map(map));
+                Value::Handle(value_handle)
+            }
+            DataType::StructLiteral => {
+                let struct_type = self.get_stack_values(&1)?[0].clone();
+                let fields_count = u32::from_le_bytes(self.fixed_bytes(value)?);
 
                 // we made *2 because, we're storing the field_value and the field_name
                 let mut fields: HashMap<String, Value> = HashMap::new();
-                let flat_fields = self.get_stack_values(&(fields_count * 2));
+                let flat_fields = self.get_stack_values(&(fields_count * 2))?;
                 for i in (0..fields_count * 2).step_by(2) {
                     let field_name_handle = flat_fields[i as usize].clone();
                     let field_value = flat_fields[(i + 1) as usize].clone();
 
-                    // this is because we're using the existent infra for utf8 values
-                    // and they are a heap allocated value, but there is also infra to
-                    // storing strings in the stack and not in the heap
+                    // field names are loaded as interned utf8 constants
                     if let Value::Handle(field_handle) = field_name_handle {
-                        let field_name = self.memory.free(field_handle);
+                        let field_name = self.memory.resolve(&field_handle)?;
                         if let MemObject::String(field_name) = field_name {
                             // add field with it's value to StructLiteral fields
-                            fields.insert(field_name, field_value);
+                            fields.insert(field_name.clone(), field_value);
                         } else {
-                            // TODO: handle with self-vm errors system
-                            panic!("struct field_name must be a MemObject of type string");
+                            return Err(VMErrorType::TypeMismatch {
+                                expected: "string".to_string(),
+                                received: field_name.get_type(),
+                            });
                         }
                     } else {
-                        // TODO: handle with self-vm errors system
-                        panic!("struct field_name must be a Handle of a string");
+                        return Err(VMErrorType::TypeMismatch {
+                            expected: "string".to_string(),
+                            received: self.value_type_name(&field_name_handle),
+                        });
                     };
                 }
 
-                let resolved_struct_type = struct_type.as_mem_obj(self).unwrap();
+                let resolved_struct_type = match struct_type.as_mem_obj(self) {
+                    Ok(v) => v,
+                    Err(err) => return Err(err.error_type),
+                };
                 printable_value = resolved_struct_type.to_string(self);
 
+                // keep the declaration to type check field
+                // updates and to resolve methods
+                let struct_type_name = resolved_struct_type.to_string(self);
+                let declaration = match &struct_type {
+                    // struct declared on another module: mod.Person {...}
+                    Value::BoundAccess(x) => match x.property.as_ref() {
+                        Value::Handle(h) => Some(h.clone()),
+                        _ => None,
+                    },
+                    // struct declared on the current module: Person {...}
+                    _ => match self.call_stack.resolve(&struct_type_name) {
+                        Some(Value::Handle(h)) => Some(h),
+                        _ => None,
+                    },
+                };
+                let declaration = declaration.filter(|h| {
+                    matches!(self.memory.resolve(h), Ok(MemObject::StructDeclaration(_)))
+                });
+
                 // here we should check if the struct exists and the each field
                 // before allocating it in the heap
-                let struct_literal =
-                    StructLiteral::new(resolved_struct_type.to_string(self), fields);
+                let struct_literal = match declaration {
+                    Some(declaration) => {
+                        StructLiteral::new_with_declaration(struct_type_name, declaration, fields)
+                    }
+                    None => StructLiteral::new(struct_type_name, fields),
+                };
                 let value_handle = self.memory.alloc(MemObject::StructLiteral(struct_literal));
                 Value::Handle(value_handle)
             }
             DataType::Bool => {
-                if value.len() > 1 {
-                    panic!("Bad boolean value")
-                }
-
-                let value = if value[0] == 0x00 {
+                let [byte] = self.fixed_bytes(value)?;
+                let value = if byte == 0x00 {
                     printable_value = "false".to_string();
                     false
                 } else {
@@ -1412,11 +1987,248 @@ impl Vm {
                 Value::RawValue(RawValue::Nothing)
             }
             _ => {
-                panic!("Unsupported type to get data from")
+                return Err(VMErrorType::Bytecode(BytecodeError::UnsupportedDataType {
+                    data_type: data_type.as_str().to_string(),
+                    at: self.position(),
+                }))
+            }
+        };
+
+        Ok((value, printable_value))
+    }
+
+    // raw values are stored with a fixed size
+    fn fixed_bytes<const N: usize>(&self, value: &[u8]) -> Result<[u8; N], VMErrorType> {
+        match value.try_into() {
+            Ok(bytes) => Ok(bytes),
+            Err(_) => Err(VMErrorType::Bytecode(BytecodeError::InvalidOperand {
+                instruction: "load_const".to_string(),
+                at: self.position(),
+            })),
+        }
+    }
+
+    // (left, right) operands of a binary operation
+    fn pop_operands(&mut self) -> Result<(OperandsStackValue, OperandsStackValue), VMErrorType> {
+        if self.operand_stack.len() < 2 {
+            return Err(VMErrorType::StackUnderflow {
+                needed: 2,
+                available: self.operand_stack.len(),
+            });
+        }
+        let right = self.operand_stack.pop().unwrap();
+        let left = self.operand_stack.pop().unwrap();
+        Ok((left, right))
+    }
+
+    // function_declaration and method_declaration share the same
+    // layout. the parameters names are taken from the stack
+    fn declare_function(
+        &mut self,
+        identifier: &str,
+        parameters_num: u32,
+        body: Rc<Code>,
+    ) -> Result<Function, VMErrorType> {
+        let params_values = self.get_stack_values(&parameters_num)?;
+        let mut params_names: Vec<String> = vec![];
+        for val in params_values.iter() {
+            let param = match val {
+                Value::Handle(r) => match self.memory.resolve(r)? {
+                    MemObject::String(s) => Some(s.clone()),
+                    _ => None,
+                },
+                _ => None,
+            };
+            match param {
+                Some(param) => params_names.push(param),
+                None => {
+                    return Err(VMErrorType::TypeMismatch {
+                        expected: "string".to_string(),
+                        received: self.value_type_name(val),
+                    })
+                }
+            }
+        }
+
+        // the function captures the environment where it is declared
+        Ok(Function::new_closure(
+            identifier.to_string(),
+            params_names,
+            Engine::Bytecode(body),
+            self.call_stack.current_env(),
+            self.module.clone(),
+        ))
+    }
+
+    fn value_matches_annotation(&self, value: &Value, annotation: &DataType) -> bool {
+        match value {
+            Value::RawValue(x) => match (annotation, x.get_type()) {
+                (
+                    DataType::F64,
+                    DataType::I32 | DataType::I64 | DataType::U32 | DataType::U64 | DataType::F64,
+                ) => true,
+                (annotation, data_type) => *annotation == data_type,
+            },
+            Value::Handle(h) => match self.memory.resolve(h) {
+                Ok(MemObject::String(_)) => *annotation == DataType::Utf8,
+                Ok(MemObject::Vector(_)) => *annotation == DataType::Vector,
+                Ok(MemObject::Map(_)) => *annotation == DataType::Map,
+                _ => false,
+            },
+            Value::BoundAccess(x) => self.value_matches_annotation(&x.property, annotation),
+            Value::HeapRef(_) => false,
+        }
+    }
+
+    fn annotation_name(data_type: &DataType) -> String {
+        match data_type {
+            DataType::I32 | DataType::I64 | DataType::U32 | DataType::U64 | DataType::F64 => {
+                "number".to_string()
+            }
+            DataType::Utf8 => "string".to_string(),
+            _ => data_type.as_str().to_string(),
+        }
+    }
+
+    fn value_type_name(&self, value: &Value) -> String {
+        match value {
+            Value::RawValue(x) => Vm::annotation_name(&x.get_type()),
+            Value::Handle(h) => match self.memory.resolve(h) {
+                Ok(obj) => obj.get_type(),
+                Err(_) => "freed_object".to_string(),
+            },
+            Value::BoundAccess(x) => self.value_type_name(&x.property),
+            _ => value.get_type(),
+        }
+    }
+
+    // resolves the object handle and the target of a v[i] or
+    // m["key"] access. vector indexes are checked to be inside
+    // the bounds, map keys are only checked to be strings
+    fn index_target(
+        &self,
+        object: &Value,
+        index: &Value,
+    ) -> Result<(Handle, IndexTarget), VMErrorType> {
+        let object = match object {
+            Value::BoundAccess(b) => b.property.as_ref(),
+            _ => object,
+        };
+        let handle = match object {
+            Value::Handle(h) => h.clone(),
+            _ => {
+                return Err(VMErrorType::Index(IndexError::NotIndexable(
+                    object.get_type(),
+                )))
+            }
+        };
+
+        let index = match index {
+            Value::BoundAccess(b) => b.property.as_ref(),
+            _ => index,
+        };
+
+        match self.memory.resolve(&handle)? {
+            MemObject::Vector(v) => {
+                let index = match index {
+                    Value::RawValue(RawValue::I32(x)) => x.value as i64,
+                    Value::RawValue(RawValue::I64(x)) => x.value,
+                    Value::RawValue(RawValue::U32(x)) => x.value as i64,
+                    Value::RawValue(RawValue::U64(x)) => x.value as i64,
+                    _ => {
+                        return Err(VMErrorType::Index(IndexError::InvalidIndexType(
+                            self.value_type_name(index),
+                        )))
+                    }
+                };
+                if index < 0 {
+                    return Err(VMErrorType::Index(IndexError::NegativeIndex(index)));
+                }
+
+                let index = index as usize;
+                if index >= v.elements.len() {
+                    return Err(VMErrorType::Index(IndexError::OutOfRange {
+                        index,
+                        length: v.elements.len(),
+                    }));
+                }
+                Ok((handle, IndexTarget::Element(index)))
+            }
+            MemObject::Map(_) => {
+                let key = match index {
+                    Value::Handle(h) => match self.memory.resolve(h)? {
+                        MemObject::String(key) => key.clone(),
+                        obj => {
+                            return Err(VMErrorType::Index(IndexError::InvalidKeyType(
+                                obj.get_type(),
+                            )))
+                        }
+                    },
+                    Value::RawValue(RawValue::Utf8(key)) => key.value.clone(),
+                    _ => {
+                        return Err(VMErrorType::Index(IndexError::InvalidKeyType(
+                            self.value_type_name(index),
+                        )))
+                    }
+                };
+                Ok((handle, IndexTarget::Entry(key)))
             }
+            obj => Err(VMErrorType::Index(IndexError::NotIndexable(obj.get_type()))),
+        }
+    }
+
+    // builds the struct a catch block receives
+    fn error_to_value(&mut self, error: VMError) -> Value {
+        let mut fields = HashMap::new();
+        for (field, value) in [
+            ("error_type", error.error_type.name()),
+            ("message", error.message),
+            ("semantic_message", error.semantic_message),
+        ] {
+            let value_handle = self.memory.alloc(MemObject::String(value));
+            fields.insert(field.to_string(), Value::Handle(value_handle));
+        }
+
+        let error_struct = StructLiteral::new("Error".to_string(), fields);
+        Value::Handle(self.memory.alloc(MemObject::StructLiteral(error_struct)))
+    }
+
+    // throw accepts a message or a caught error to rethrow it
+    fn value_to_error(&self, value: &Value) -> VMErrorType {
+        let value = match value {
+            Value::BoundAccess(b) => b.property.as_ref(),
+            _ => value,
         };
+        if let Value::Handle(h) = value {
+            match self.memory.resolve(h) {
+                // the thrown text is the message, there are no details
+                Ok(MemObject::String(s)) => {
+                    return VMErrorType::Thrown(Box::new(ThrownError {
+                        error_type: "Error".to_string(),
+                        message: s.clone(),
+                        semantic_message: String::new(),
+                    }))
+                }
+                Ok(MemObject::StructLiteral(x)) if x.struct_type == "Error" => {
+                    let field = |name: &str| match x.property_access(name) {
+                        Some(v) => v.to_string(self),
+                        None => "".to_string(),
+                    };
+                    return VMErrorType::Thrown(Box::new(ThrownError {
+                        error_type: field("error_type"),
+                        message: field("message"),
+                        semantic_message: field("semantic_message"),
+                    }));
+                }
+                _ => {}
+            }
+        }
 
-        (value, printable_value)
+        VMErrorType::Thrown(Box::new(ThrownError {
+            error_type: "Error".to_string(),
+            message: value.to_string(self),
+            semantic_message: String::new(),
+        }))
     }
 
     fn value_to_string(&mut self, value: Value) -> Result<String, VMErrorType> {
@@ -1435,43 +2247,45 @@ impl Vm {
         Ok(resolved_args)
     }
 
-    pub fn read_offset(bytes: &[u8]) -> i32 {
-        // TODO: use self-vm errors
-        let arr: [u8; 4] = bytes.try_into().expect("slice with incorrect length");
-        i32::from_le_bytes(arr)
-    }
-
-    fn get_function_call_args(&mut self) -> Vec<Value> {
-        // get u32 value. 4 bytes based on the type plus the current
-        let value_length = 3;
-        if self.pc + value_length >= self.bytecode.len() {
-            panic!("Invalid instruction at position {}", self.pc);
+    pub fn get_stack_values(&mut self, num_of_values: &u32) -> Result<Vec<Value>, VMErrorType> {
+        let needed = *num_of_values as usize;
+        if self.operand_stack.len() < needed {
+            return Err(VMErrorType::StackUnderflow {
+                needed,
+                available: self.operand_stack.len(),
+            });
         }
 
-        let value_bytes = &self.bytecode[self.pc..self.pc + 4];
-        let number_of_args =
-            u32::from_le_bytes(value_bytes.try_into().expect("Provided value is incorrect"));
-        self.pc += 4; // 4 => 3 + 1 extra to leave the pc in the next opcode
-
-        // execution
-        let args = self.get_stack_values(&number_of_args);
-        args
+        let args = self
+            .operand_stack
+            .split_off(self.operand_stack.len() - needed)
+            .into_iter()
+            .map(|v| v.value)
+            .collect(); // invocation order
+        Ok(args)
     }
 
-    pub fn get_stack_values(&mut self, num_of_values: &u32) -> Vec<Value> {
-        let mut args = Vec::with_capacity(*num_of_values as usize);
-
-        for _ in 0..*num_of_values {
-            match self.operand_stack.pop() {
-                Some(v) => args.push(v.value),
-                None => {
-                    panic!("Stack underflow: trying to get '{num_of_values}' values from the stack")
-                }
-            }
-        }
+    // roots are the operands stack, the environment of every
+    // frame, the builtin handlers and the interned strings
+    pub fn collect_garbage(&mut self) -> usize {
+        let mut roots: Vec<Value> = self
+            .operand_stack
+            .iter()
+            .map(|operand| operand.value.clone())
+            .collect();
+        roots.extend(self.handlers.values().cloned().map(Value::Handle));
+        roots.extend(self.interned.values().cloned().map(Value::Handle));
+        let envs = self
+            .call_stack
+            .frames()
+            .iter()
+            .map(|frame| frame.env())
+            .collect();
+        self.memory.collect(roots, envs)
+    }
 
-        args.reverse(); // invocation order
-        args
+    pub fn gc_stats(&self) -> GcStats {
+        self.memory.stats()
     }
 
     pub fn get_handler(&self, handler: &str) -> Option<Handle> {
@@ -1518,7 +2332,7 @@ impl Vm {
 
         //     if pc >= target_pc {
         //         // print instruction
-        //         let (instruction, offset) = Translator::get_instruction(pc, &self.bytecode);
+        //         let (instruction, length) = Translator::get_instruction(pc, &self.bytecode).unwrap();
         //         let raw_instruction = format!("{}|    {:#?}", printable_index, self.bytecode[pc]);
         //         println!("{} <---- {}", raw_instruction, instruction.get_type());
 
@@ -1526,8 +2340,7 @@ impl Vm {
         //         if instruction_info.len() > 0 {
         //             println!("------------ \n{}\n------------", instruction_info);
         //         }
-        //         // + 1  the normal iteration increment over the bytecode
-        //         target_pc = pc + offset + 1;
+        //         target_pc = pc + length;
         //     } else {
         //         // print bytecode index
         //         println!("{}|    {:#?}", printable_index, self.bytecode[pc]);
@@ -1539,3 +2352,70 @@ impl Vm {
         //println!("{:#?}", Translator::new(self.bytecode.clone()).translate());
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn operand(value: RawValue) -> OperandsStackValue {
+        OperandsStackValue {
+            value: Value::RawValue(value),
+            origin: None,
+        }
+    }
+
+    fn run_operation(left: RawValue, operator: &str, right: RawValue) -> (Vm, Option<VMErrorType>) {
+        let mut vm = Vm::new(vec![]);
+        let error = vm.run_binary_expression(operator, (operand(left), operand(right)));
+        (vm, error)
+    }
+
+    #[test]
+    fn integer_overflow_is_an_error() {
+        for (left, operator, right) in [
+            (i32::MAX, "+", 1),
+            (i32::MIN, "-", 1),
+            (i32::MAX, "*", 2),
+            (i32::MIN, "/", -1),
+        ] {
+            let (_, error) = run_operation(
+                RawValue::I32(I32::new(left)),
+                operator,
+                RawValue::I32(I32::new(right)),
+            );
+            match error {
+                Some(VMErrorType::IntegerOverflow(overflow)) => {
+                    assert_eq!(overflow.operation, format!("{left} {operator} {right}"))
+                }
+                other => panic!("{left} {operator} {right} gave {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn unsigned_underflow_is_an_error() {
+        let (_, error) = run_operation(RawValue::U32(U32::new(0)), "-", RawValue::U32(U32::new(1)));
+        assert!(matches!(error, Some(VMErrorType::IntegerOverflow(_))));
+
+        let (_, error) = run_operation(
+            RawValue::U64(U64::new(u64::MAX)),
+            "+",
+            RawValue::U64(U64::new(1)),
+        );
+        assert!(matches!(error, Some(VMErrorType::IntegerOverflow(_))));
+    }
+
+    #[test]
+    fn arithmetic_in_range_pushes_the_result() {
+        let (mut vm, error) = run_operation(
+            RawValue::I64(I64::new(i64::MIN + 1)),
+            "-",
+            RawValue::I64(I64::new(1)),
+        );
+        assert!(error.is_none());
+        match vm.operand_stack.pop().map(|operand| operand.value) {
+            Some(Value::RawValue(RawValue::I64(v))) => assert_eq!(v.value, i64::MIN),
+            other => panic!("unexpected result {other:?}"),
+        }
+    }
+}